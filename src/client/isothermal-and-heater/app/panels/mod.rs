@@ -7,6 +7,7 @@ use egui_plot::{Legend, Line, Plot, PlotPoints};
 
 pub mod first_order_transfer_fn;
 pub mod opcua_panel;
+pub mod opcua_connection_options;
 
 impl GuiClient {
     pub fn simple_panel_ui(&mut self, ui: &mut Ui) {
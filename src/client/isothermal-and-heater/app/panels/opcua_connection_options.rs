@@ -0,0 +1,193 @@
+//! options for connecting the client to the CIET OPC-UA server
+//!
+//! these match the server configuration: the port and endpoint
+//! path, the endpoint security (none, or Basic256Sha256 Sign or
+//! SignAndEncrypt) and how the user logs in (anonymous, username
+//! and password, or X.509 certificate)
+//!
+//! they are read from the command line, eg.
+//!
+//! cargo run --bin client -- --security sign_and_encrypt \
+//!     --user operator --password operator_password
+use std::path::PathBuf;
+
+use opcua::client::prelude::*;
+
+/// security policy and message security mode of the endpoint
+/// the client connects to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClientEndpointSecurity {
+    /// no signing or encryption
+    #[default]
+    None,
+    /// messages signed with Basic256Sha256
+    Basic256Sha256Sign,
+    /// messages signed and encrypted with Basic256Sha256
+    Basic256Sha256SignAndEncrypt,
+}
+
+/// how the client logs in to the server
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ClientIdentity {
+    #[default]
+    Anonymous,
+    UserName {
+        user: String,
+        password: String,
+    },
+    /// user certificate (.der) and its private key (.pem)
+    X509 {
+        certificate_path: PathBuf,
+        private_key_path: PathBuf,
+    },
+}
+
+/// options for connecting to the CIET OPC-UA server
+#[derive(Debug, Clone, PartialEq)]
+pub struct OpcuaConnectionOptions {
+    pub port: u16,
+    pub endpoint_path: String,
+    pub security: ClientEndpointSecurity,
+    pub identity: ClientIdentity,
+    /// directory with the client key pair and the trusted and
+    /// rejected server certificate folders
+    pub pki_dir: PathBuf,
+    /// trust any server certificate, only for testing, otherwise 
+    /// the server certificate must be in the trusted folder
+    pub trust_server_certs: bool,
+}
+
+impl Default for OpcuaConnectionOptions {
+    fn default() -> Self {
+        return Self {
+            port: 4840,
+            endpoint_path: "/rust_ciet_opcua_server".to_string(),
+            security: ClientEndpointSecurity::default(),
+            identity: ClientIdentity::default(),
+            pki_dir: PathBuf::from("pki"),
+            trust_server_certs: false,
+        };
+    }
+}
+
+impl OpcuaConnectionOptions {
+
+    /// reads the options from command line arguments
+    ///
+    /// --port <port>
+    /// --endpoint-path <path>
+    /// --security <none|sign|sign_and_encrypt>
+    /// --user <name> --password <password>
+    /// --user-certificate <cert.der> --user-private-key <private.pem>
+    /// --pki-dir <path>
+    /// --trust-server-certs (trust any server certificate rather 
+    /// than only those in the trusted folder, only for testing)
+    pub fn from_args(args: &[String]) -> Result<Self, String> {
+
+        let mut options = Self::default();
+
+        if let Some(port) = get_arg_value(args, "--port") {
+            options.port = port.parse().map_err(
+                |_| format!("port must be a number, got {:?}", port))?;
+        }
+
+        if let Some(endpoint_path) = get_arg_value(args, "--endpoint-path") {
+            options.endpoint_path = endpoint_path;
+        }
+
+        if let Some(security) = get_arg_value(args, "--security") {
+            options.security = match security.trim().to_lowercase().as_str() {
+                "none" => ClientEndpointSecurity::None,
+                "sign" => ClientEndpointSecurity::Basic256Sha256Sign,
+                "sign_and_encrypt" =>
+                    ClientEndpointSecurity::Basic256Sha256SignAndEncrypt,
+                other => return Err(format!(
+                        "unknown security {:?}, expected none, sign \
+                        or sign_and_encrypt", other)),
+            };
+        }
+
+        let user = get_arg_value(args, "--user");
+        let password = get_arg_value(args, "--password");
+        let user_certificate = get_arg_value(args, "--user-certificate");
+        let user_private_key = get_arg_value(args, "--user-private-key");
+
+        options.identity = match (user, password, user_certificate, user_private_key) {
+            (None, None, None, None) => ClientIdentity::Anonymous,
+            (Some(user), Some(password), None, None) =>
+                ClientIdentity::UserName { user, password },
+            (None, None, Some(certificate_path), Some(private_key_path)) =>
+                ClientIdentity::X509 {
+                    certificate_path: certificate_path.into(),
+                    private_key_path: private_key_path.into(),
+                },
+            _ => return Err("give either --user and --password, or \
+                --user-certificate and --user-private-key".to_string()),
+        };
+
+        if let Some(pki_dir) = get_arg_value(args, "--pki-dir") {
+            options.pki_dir = pki_dir.into();
+        }
+
+        if args.iter().any(|arg| arg == "--trust-server-certs") {
+            options.trust_server_certs = true;
+        }
+
+        return Ok(options);
+    }
+
+    /// returns the endpoint url for the server at the given
+    /// ip address
+    pub fn get_endpoint_url(&self, ip_addr: &str) -> String {
+        return format!("opc.tcp://{}:{}{}", ip_addr, self.port, self.endpoint_path);
+    }
+
+    pub fn get_security_policy(&self) -> SecurityPolicy {
+        match self.security {
+            ClientEndpointSecurity::None => SecurityPolicy::None,
+            ClientEndpointSecurity::Basic256Sha256Sign
+                | ClientEndpointSecurity::Basic256Sha256SignAndEncrypt =>
+                SecurityPolicy::Basic256Sha256,
+        }
+    }
+
+    pub fn get_message_security_mode(&self) -> MessageSecurityMode {
+        match self.security {
+            ClientEndpointSecurity::None => MessageSecurityMode::None,
+            ClientEndpointSecurity::Basic256Sha256Sign =>
+                MessageSecurityMode::Sign,
+            ClientEndpointSecurity::Basic256Sha256SignAndEncrypt =>
+                MessageSecurityMode::SignAndEncrypt,
+        }
+    }
+
+    pub fn get_identity_token(&self) -> IdentityToken {
+        match &self.identity {
+            ClientIdentity::Anonymous => IdentityToken::Anonymous,
+            ClientIdentity::UserName { user, password } =>
+                IdentityToken::UserName(user.clone(), password.clone()),
+            ClientIdentity::X509 { certificate_path, private_key_path } =>
+                IdentityToken::X509(certificate_path.clone(),
+                    private_key_path.clone()),
+        }
+    }
+}
+
+/// reads the value of a command line argument given either as
+/// "--flag value" or "--flag=value"
+fn get_arg_value(args: &[String], flag: &str) -> Option<String> {
+
+    let prefix = format!("{}=", flag);
+    let mut args_iter = args.iter();
+
+    while let Some(arg) = args_iter.next() {
+        if arg == flag {
+            return args_iter.next().cloned();
+        }
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
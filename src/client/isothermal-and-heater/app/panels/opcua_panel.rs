@@ -8,6 +8,8 @@ use opcua::client::prelude::*;
 use opcua::sync::RwLock;
 use std::sync::{Arc, Mutex};
 
+use super::opcua_connection_options::OpcuaConnectionOptions;
+
 impl GuiClient {
     
     pub fn ciet_isothermal_panel_ui(&mut self, ui: &mut Ui) {
@@ -426,11 +428,12 @@ pub fn print_value(item: &MonitoredItem) {
     }
 }
 pub fn try_connect_to_server_and_run_client(endpoint: &str,
+    connection_options: &OpcuaConnectionOptions,
     ns: u16,
     loop_pressure_drop_input_ptr: Arc<Mutex<f32>>,
     isothermal_mass_flow_output_ptr: Arc<Mutex<f32>>,
     bt12_temp_deg_c_output_ptr: Arc<Mutex<f32>>,
-    bt11_temp_deg_c_input_ptr: Arc<Mutex<f32>>,
+    bt11_temp_deg_c_output_ptr: Arc<Mutex<f32>>,
     heater_power_kilowatts_input_ptr: Arc<Mutex<f32>>,
 ) -> Result<(),StatusCode>{
 
@@ -439,7 +442,8 @@ pub fn try_connect_to_server_and_run_client(endpoint: &str,
         .application_name("Simple Client")
         .application_uri("urn:SimpleClient")
         .product_uri("urn:SimpleClient")
-        .trust_server_certs(true)
+        .pki_dir(connection_options.pki_dir.clone())
+        .trust_server_certs(connection_options.trust_server_certs)
         .create_sample_keypair(true)
         .session_retry_limit(5)
         .client()
@@ -447,10 +451,10 @@ pub fn try_connect_to_server_and_run_client(endpoint: &str,
 
     let session = client.connect_to_endpoint(
         (endpoint,
-         SecurityPolicy::None.to_str(),
-         MessageSecurityMode::None,
+         connection_options.get_security_policy().to_str(),
+         connection_options.get_message_security_mode(),
          UserTokenPolicy::anonymous(),
-        ), IdentityToken::Anonymous,
+        ), connection_options.get_identity_token(),
         )?;
 
     //subscribe_to_variables(session.clone(), ns)?;
@@ -467,6 +471,11 @@ pub fn try_connect_to_server_and_run_client(endpoint: &str,
     let bt12_temperature_node = NodeId::new(ns, "bt12_temperature_degC");
     let heater_power_node = NodeId::new(ns, "heater_power_kilowatts");
 
+    // controller nodes are written by calling write_controller_value
+    // on the Controller folder, which checks the user's role
+    let controller_folder_node = NodeId::new(ns, "controller");
+    let write_controller_value_method_node = NodeId::new(ns, "write_controller_value");
+
     // i will also need another thread to run the polling loop 
 
     thread::spawn( move ||{
@@ -515,6 +524,19 @@ pub fn try_connect_to_server_and_run_client(endpoint: &str,
 
                 *heater_exit_temp_to_gui = bt12_exit_temp_deg_c;
 
+                // bt11 is calculated by the server as well, 
+                // it is at index 4
+                let mut heater_inlet_temp_to_gui = 
+                bt11_temp_deg_c_output_ptr.lock().unwrap();
+
+                let bt11_inlet_temp_data_val = &results[4];
+
+                let bt11_inlet_temp_deg_c: f32 = 
+                bt11_inlet_temp_data_val.value.clone().unwrap()
+                    .as_f64().unwrap() as f32;
+
+                *heater_inlet_temp_to_gui = bt11_inlet_temp_deg_c;
+
 
             }
 
@@ -526,48 +548,35 @@ pub fn try_connect_to_server_and_run_client(endpoint: &str,
                 let user_input_pressure_drop: f32 = 
                 loop_pressure_drop_input_ptr.lock().unwrap().to_owned();
 
-                let user_input_heater_inlet_temp: f32 = 
-                bt11_temp_deg_c_input_ptr.lock().unwrap().to_owned();
-
                 let user_input_heater_power_kilowatts: f32 = 
                 heater_power_kilowatts_input_ptr.lock().unwrap().to_owned();
 
                 //dbg!(&user_input_heater_power_kilowatts);
 
 
-                // next, create the write values
-                let ctah_pump_node_write: WriteValue = WriteValue {
-                        node_id: ctah_pump_pressure_node.clone(),
-                        attribute_id: AttributeId::Value as u32,
-                        index_range: UAString::null(),
-                        value: Variant::Float(user_input_pressure_drop).into(),
-                    };
-
-
-                let heater_inlet_temp_node_write: WriteValue = WriteValue {
-                        node_id: bt11_temperature_node.clone(),
-                        attribute_id: AttributeId::Value as u32,
-                        index_range: UAString::null(),
-                        value: Variant::Float(user_input_heater_inlet_temp).into(),
-                    };
-
-                let heater_power_node_write: WriteValue = WriteValue {
-                        node_id: heater_power_node.clone(),
-                        attribute_id: AttributeId::Value as u32,
-                        index_range: UAString::null(),
-                        value: Variant::Float(user_input_heater_power_kilowatts).into(),
-                    };
+                // next, create the write values, ie. the node name 
+                // and value to write
+                let ctah_pump_node_write: Vec<Variant> = vec![
+                    Variant::from("ctah_pump_pressure_pa"),
+                    Variant::Float(user_input_pressure_drop),
+                ];
+
+                let heater_power_node_write: Vec<Variant> = vec![
+                    Variant::from("heater_power_kilowatts"),
+                    Variant::Float(user_input_heater_power_kilowatts),
+                ];
                 // now mutex lock the session, 
                 let session_lock = session.read();
-                // put write values into the write session lock
-
-                let _ = session_lock
-                    .write(&[
-                        ctah_pump_node_write,
-                        heater_inlet_temp_node_write,
-                        heater_power_node_write,
-                    ])
-                    .unwrap();
+                // and call write_controller_value for each, an 
+                // observer gets BadUserAccessDenied
+
+                for node_write in [ctah_pump_node_write, heater_power_node_write] {
+                    let _ = session_lock.call((
+                        controller_folder_node.clone(),
+                        write_controller_value_method_node.clone(),
+                        Some(node_write),
+                    ));
+                }
             }
 
             // tbc, need to understand how the reading works here
@@ -10,11 +10,18 @@ fn main() -> eframe::Result<()> {
     use uom::si::{f64::Time, time::{millisecond, second}};
     use crate::panels::opcua_panel::try_connect_to_server_and_run_client;
     use crate::first_order_transfer_fn::FirstOrderTransferFn;
+    use crate::panels::opcua_connection_options::OpcuaConnectionOptions;
     
     
 
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    // port, endpoint path, security and user login, these must 
+    // match the server
+    let args: Vec<String> = std::env::args().collect();
+    let connection_options = OpcuaConnectionOptions::from_args(&args)
+        .unwrap_or_else(|error| panic!("{}", error));
+
     let native_options = eframe::NativeOptions {
         initial_window_size: Some([400.0, 300.0].into()),
         min_window_size: Some([300.0, 220.0].into()),
@@ -101,11 +108,11 @@ fn main() -> eframe::Result<()> {
 
         let my_local_ip = local_ip().unwrap();
         let ip_addr: String = my_local_ip.to_string();        
-        let endpoint: String = "opc.tcp://".to_owned()
-        +&ip_addr+":4840/rust_ciet_opcua_server";
+        let endpoint: String = connection_options.get_endpoint_url(&ip_addr);
 
         let mut connection_result = try_connect_to_server_and_run_client(
             &endpoint,
+            &connection_options,
             2,
             opcua_input_clone.clone(),
             opcua_output_clone.clone(),
@@ -119,12 +126,12 @@ fn main() -> eframe::Result<()> {
 
             let ip_addr: String = opcua_ip_addr_ptr_clone.lock().unwrap().deref_mut()
             .to_string();
-            let endpoint: String = "opc.tcp://".to_owned()
-            +&ip_addr+":4840/rust_ciet_opcua_server";
+            let endpoint: String = connection_options.get_endpoint_url(&ip_addr);
 
             if let Err(_) = connection_result.clone() {
                 connection_result = try_connect_to_server_and_run_client(
                     &endpoint,
+                    &connection_options,
                     2,
                     opcua_input_clone.clone(),
                     opcua_output_clone.clone(),
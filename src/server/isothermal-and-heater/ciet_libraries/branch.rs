@@ -1,8 +1,507 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::reciprocal_length::reciprocal_meter;
 
+use crate::{CIETComponent, CIETComponentDefinition, CIETLoopDefinition,
+    LoopDefinitionError,
+    BranchValve, PumpCurve, ComponentPressureBreakdown,
+    FlowSolverError, find_root_with_bracket_expansion};
 
 
-/// a branch is a collection of pipes and components
-pub trait TherminolSeriesComponents {}
+/// a branch of the CIET primary loop, ie. a series of pipes and
+/// components listed from the top of the branch to the bottom,
+/// with a branch valve at the bottom
+///
+/// the heater, ctah and dhx branches are all of this type and
+/// only differ in their components, the ctah branch has the ctah
+/// pump in it and the dhx branch has a check valve, which only
+/// allows flow from top to bottom
+///
+/// the branch owns its components, so it only needs to
+/// be constructed once, and can then be calculated as many
+/// times as needed, eg. once every timestep
+pub struct CIETBranch {
 
+    // eg. heater_branch, used for the valve name and errors
+    branch_name: String,
 
+    // listed from top to bottom
+    components: Vec<CIETComponent>,
 
+    // the branch valve, located at the bottom of the branch
+    valve: BranchValve,
+
+    // if true, flow is zero rather than from bottom to top
+    check_valve: bool,
+
+    pump: Option<BranchPump>,
+}
+
+/// a pump within a branch, it is either a pressure source set
+/// by the user (speed is None) or runs along its pump curve at
+/// its speed
+struct BranchPump {
+    // index of the pump within the branch components
+    component_index: usize,
+    curve: PumpCurve,
+    speed: Option<Frequency>,
+}
+
+impl CIETBranch {
+
+    /// constructs a branch with no pump and no check valve from
+    /// a list of component definitions, listed from top to bottom
+    ///
+    /// the branch is named after the loop definition entry it
+    /// was built from, eg. heater_branch
+    pub fn from_definition(branch_name: &str,
+        component_definitions: &[CIETComponentDefinition])
+        -> Result<Self, LoopDefinitionError> {
+
+        let components: Vec<CIETComponent> = component_definitions.iter()
+            .map(|component_definition| component_definition.build())
+            .collect();
+
+        // the valve is at the bottom of the branch and has the
+        // same flow area as the pipe it is installed in
+        let valve_flow_area = components.last()
+            .ok_or(LoopDefinitionError::EmptyBranch(branch_name.to_string()))?
+            .get_cross_sectional_area();
+
+        let valve = BranchValve::new(&format!("{}_valve", branch_name),
+            valve_flow_area);
+
+        return Ok(Self {
+            branch_name: branch_name.to_string(),
+            components,
+            valve,
+            check_valve: false,
+            pump: None,
+        });
+    }
+
+    /// heater branch of the default CIET loop definition,
+    /// from pipe 4 at the top of the branch to pipe 18 at the
+    /// bottom, with all its components at 21C and the valve open
+    pub fn new_heater_branch() -> Self {
+        return CIETLoopDefinition::default().build_heater_branch()
+            .expect("default heater branch definition should be valid");
+    }
+
+    /// ctah branch of the default CIET loop definition,
+    /// from branch 5 at the top of the branch to branch 17 at the
+    /// bottom, with all its components at 21C, zero pump pressure
+    /// and the valve open
+    pub fn new_ctah_branch() -> Self {
+        return CIETLoopDefinition::default().build_ctah_branch()
+            .expect("default ctah branch definition should be valid");
+    }
+
+    /// dhx branch of the default CIET loop definition,
+    /// from pipe 26 at the top of the branch to pipe 19 at the
+    /// bottom, with all its components at 21C and the valve open
+    pub fn new_dhx_branch() -> Self {
+        return CIETLoopDefinition::default().build_dhx_branch()
+            .expect("default dhx branch definition should be valid");
+    }
+
+    /// makes the component named pump_name the pump of this
+    /// branch, which follows pump_curve when driven by speed
+    pub fn set_pump(&mut self, pump_name: &str,
+        pump_curve: PumpCurve) -> Result<(), LoopDefinitionError> {
+
+        let component_index = self.components.iter()
+            .position(|component| component.get_name() == pump_name)
+            .ok_or(LoopDefinitionError::MissingComponent(
+                    pump_name.to_string()))?;
+
+        self.pump = Some(BranchPump {
+            component_index,
+            curve: pump_curve,
+            speed: None,
+        });
+
+        return Ok(());
+    }
+
+    /// adds (or removes) a check valve, which only allows
+    /// flow from the top of the branch to the bottom
+    pub fn set_check_valve(&mut self, check_valve: bool){
+        self.check_valve = check_valve;
+    }
+
+    pub fn has_check_valve(&self) -> bool {
+        return self.check_valve;
+    }
+
+    /// sets the pump pressure to whatever value the user specifies
+    ///
+    /// the pump is owned by the branch, so its internal pressure
+    /// source is just changed in place, the pump then stops
+    /// following its pump curve
+    ///
+    /// does nothing if the branch has no pump
+    pub fn set_pump_pressure(&mut self,
+        user_specified_pressure: Pressure){
+
+        if let Some(pump) = self.pump.as_mut() {
+            self.components[pump.component_index]
+                .set_internal_pressure_source(user_specified_pressure);
+            pump.speed = None;
+        }
+    }
+
+    /// sets the pump speed (VFD frequency), the pump pressure
+    /// then follows the pump curve, so it depends on the
+    /// branch flowrate
+    ///
+    /// does nothing if the branch has no pump
+    pub fn set_pump_speed(&mut self, pump_speed: Frequency){
+
+        if let Some(pump) = self.pump.as_mut() {
+            // the pump curve supplies all of the pump pressure
+            self.components[pump.component_index]
+                .set_internal_pressure_source(Pressure::new::<pascal>(0.0));
+            pump.speed = Some(pump_speed);
+        }
+    }
+
+    /// returns the pump speed, or None if the pump is a set
+    /// pressure source or there is no pump
+    pub fn get_pump_speed(&self) -> Option<Frequency> {
+        return self.pump.as_ref().and_then(|pump| pump.speed);
+    }
+
+    /// returns the pump curve, or None if there is no pump
+    pub fn get_pump_curve(&self) -> Option<&PumpCurve> {
+        return self.pump.as_ref().map(|pump| &pump.curve);
+    }
+
+    /// replaces the pump curve, does nothing if the branch
+    /// has no pump
+    pub fn set_pump_curve(&mut self, pump_curve: PumpCurve){
+        if let Some(pump) = self.pump.as_mut() {
+            pump.curve = pump_curve;
+        }
+    }
+
+    /// returns the user specified pump pressure, for a speed
+    /// driven pump, use get_pump_pressure_at
+    ///
+    /// this is zero if there is no pump
+    pub fn get_pump_pressure(&self) -> Pressure {
+        match &self.pump {
+            Some(pump) => return self.components[pump.component_index]
+                .get_internal_pressure_source(),
+            None => return Pressure::new::<pascal>(0.0),
+        }
+    }
+
+    /// returns the pump pressure at a given branch flowrate, this
+    /// is the user specified pressure or the pressure from the
+    /// pump curve
+    pub fn get_pump_pressure_at(&self,
+        mass_flowrate: MassRate) -> Pressure {
+
+        match &self.pump {
+            Some(BranchPump { curve, speed: Some(pump_speed), .. }) =>
+                return curve.get_pressure_rise(mass_flowrate, *pump_speed),
+            _ => return self.get_pump_pressure(),
+        }
+    }
+
+    /// returns the number of components in the branch,
+    /// excluding the valve
+    pub fn get_component_count(&self) -> usize {
+        return self.components.len();
+    }
+
+    /// returns the names of the components in the branch,
+    /// listed from top to bottom, excluding the valve
+    pub fn get_component_names(&self) -> Vec<String> {
+        return self.components.iter()
+            .map(|component| component.get_name().to_string())
+            .collect();
+    }
+
+    /// returns the sum of L/A over every component in the branch,
+    /// the fluid momentum equation for the branch is then
+    ///
+    /// (sum of L/A) dm/dt = branch pressure change(m) - pressure change
+    ///
+    /// where branch pressure change(m) is the quasi-static pressure
+    /// change (losses, hydrostatics and pumps) and pressure change
+    /// is the actual pressure change across the branch
+    pub fn get_length_to_area_sum(&self) -> ReciprocalLength {
+        let mut length_to_area_sum = ReciprocalLength::new::<reciprocal_meter>(0.0);
+
+        for component in self.components.iter() {
+            length_to_area_sum += component.get_length_to_area_ratio();
+        }
+
+        return length_to_area_sum;
+    }
+
+    /// returns references to the components in the branch,
+    /// listed from top to bottom
+    pub fn get_fluid_component_vector(&self) -> Vec<&dyn FluidComponent> {
+        return self.components.iter()
+            .map(|component| component.as_fluid_component())
+            .collect();
+    }
+
+    /// sets the fluid temperature of each component, listed from
+    /// top to bottom
+    ///
+    /// returns an error, leaving the temperatures unchanged, if
+    /// there is not exactly one temperature per component
+    pub fn set_fluid_temperatures(&mut self,
+        component_temperatures: &[ThermodynamicTemperature])
+        -> Result<(), LoopDefinitionError> {
+
+        if component_temperatures.len() != self.components.len() {
+            return Err(LoopDefinitionError::MismatchedTemperatures(
+                    self.branch_name.clone()));
+        }
+
+        for (component, fluid_temp) in self.components.iter_mut()
+            .zip(component_temperatures.iter()) {
+            component.set_fluid_temperature(*fluid_temp);
+        }
+
+        // the valve is at the bottom of the branch
+        if let Some(bottom_temp) = component_temperatures.last() {
+            self.valve.set_fluid_temperature(*bottom_temp);
+        }
+
+        return Ok(());
+    }
+
+    /// sets every component (and the valve) to the same fluid
+    /// temperature
+    pub fn set_uniform_fluid_temperature(&mut self,
+        fluid_temp: ThermodynamicTemperature){
+
+        for component in self.components.iter_mut() {
+            component.set_fluid_temperature(fluid_temp);
+        }
+
+        self.valve.set_fluid_temperature(fluid_temp);
+    }
+
+    /// returns the friction, form loss, hydrostatic and pump
+    /// contributions of each component at a given mass flowrate,
+    /// using the fluid temperatures last set on the branch
+    ///
+    /// listed from top to bottom, with the branch valve last
+    pub fn get_pressure_breakdown(&self,
+        mass_flowrate: MassRate) -> Vec<ComponentPressureBreakdown> {
+
+        let mut pressure_breakdown: Vec<ComponentPressureBreakdown> =
+            self.components.iter()
+            .map(|component| component.get_pressure_breakdown(mass_flowrate))
+            .collect();
+
+        // a speed driven pump gets its pressure from the pump curve
+        if let Some(pump) = &self.pump {
+            pressure_breakdown[pump.component_index].internal_pressure_source =
+                self.get_pump_pressure_at(mass_flowrate);
+        }
+
+        pressure_breakdown.push(
+            self.valve.get_pressure_breakdown(mass_flowrate));
+
+        return pressure_breakdown;
+    }
+
+    /// opens or closes the branch valve fully
+    pub fn set_valve_open(&mut self, valve_open: bool){
+        self.valve.set_open(valve_open);
+    }
+
+    /// returns true if the branch valve is not fully closed
+    pub fn is_valve_open(&self) -> bool {
+        return !self.valve.is_closed();
+    }
+
+    /// sets the branch valve opening, 0.0 is closed and
+    /// 1.0 is fully open
+    pub fn set_valve_opening(&mut self, opening: f64){
+        self.valve.set_opening(opening);
+    }
+
+    /// returns the branch valve opening
+    pub fn get_valve_opening(&self) -> f64 {
+        return self.valve.get_opening();
+    }
+
+    /// calculates mass flowrate from pressure change
+    ///
+    /// if the valve is closed, the flowrate is zero, with a check
+    /// valve, flow is also zero if the pressure change would drive
+    /// flow from bottom to top
+    ///
+    /// returns an error rather than panicking if the
+    /// flowrate cannot be solved for
+    pub fn try_get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> Result<MassRate, FlowSolverError> {
+
+        let zero_flowrate =
+            MassRate::new::<kilogram_per_second>(0.0);
+
+        if self.valve.is_closed() {
+            return Ok(zero_flowrate);
+        }
+
+        // here is where i implement the check valve behaviour
+        if self.check_valve {
+            let hydrostatic_pressure =
+                self.get_pressure_change(zero_flowrate);
+
+            if pressure_change.value > hydrostatic_pressure.value {
+                return Ok(zero_flowrate);
+            }
+        }
+
+        // we start with a bracket of -1 to +1 kg/s, which is
+        // plenty for ciet, and widen it if needed
+        let mass_flow_from_pressure_chg_root =
+            |mass_flow_kg_per_s: f64| -> f64 {
+
+            let mass_rate =
+                MassRate::new::<kilogram_per_second>(
+                    mass_flow_kg_per_s);
+
+            // this includes the valve pressure loss
+            let pressure_change_tested =
+                self.get_pressure_change(mass_rate);
+
+            // since we are finding root, then we must also
+            // subtract it from our pressure change value
+            return pressure_change.value -
+                pressure_change_tested.value;
+
+        };
+
+        let mass_flowrate_kg_per_s =
+            find_root_with_bracket_expansion(
+                mass_flow_from_pressure_chg_root,
+                0.0,
+                1.0)?;
+
+        return Ok(MassRate::new::<kilogram_per_second>(
+                mass_flowrate_kg_per_s));
+    }
+
+    /// returns the branch flowrate at the end of a timestep given
+    /// the pressure change across the branch and the flowrate at the
+    /// start of the timestep, accounting for the inertia of the fluid
+    ///
+    /// the momentum equation is discretised implicitly (backward
+    /// euler), ie.
+    ///
+    /// (sum of L/A) (m - m_previous)/dt = branch pressure change(m)
+    /// - pressure change
+    ///
+    /// so that it is stable for any timestep, as the timestep grows
+    /// this tends to try_get_mass_flowrate_from_pressure_change
+    ///
+    /// if the valve is closed, the flowrate is zero, and a check
+    /// valve shuts if the flowrate would reverse
+    pub fn try_get_mass_flowrate_with_inertia(
+        &self,
+        pressure_change: Pressure,
+        previous_mass_flowrate: MassRate,
+        timestep: Time) -> Result<MassRate, FlowSolverError> {
+
+        let zero_flowrate =
+            MassRate::new::<kilogram_per_second>(0.0);
+
+        if self.valve.is_closed() {
+            return Ok(zero_flowrate);
+        }
+
+        let length_to_area_sum = self.get_length_to_area_sum();
+
+        let mass_flow_with_inertia_root =
+            |mass_flow_kg_per_s: f64| -> f64 {
+
+            let mass_rate =
+                MassRate::new::<kilogram_per_second>(
+                    mass_flow_kg_per_s);
+
+            let inertial_pressure: Pressure = length_to_area_sum
+                * (mass_rate - previous_mass_flowrate) / timestep;
+
+            return pressure_change.value
+                + inertial_pressure.value
+                - self.get_pressure_change(mass_rate).value;
+
+        };
+
+        // the flowrate should not have moved far from the
+        // previous one, so we bracket the root around it
+        let mass_flowrate_kg_per_s =
+            find_root_with_bracket_expansion(
+                mass_flow_with_inertia_root,
+                previous_mass_flowrate.get::<kilogram_per_second>(),
+                1.0)?;
+
+        if self.check_valve && mass_flowrate_kg_per_s < 0.0 {
+            return Ok(zero_flowrate);
+        }
+
+        return Ok(MassRate::new::<kilogram_per_second>(
+                mass_flowrate_kg_per_s));
+    }
+}
+
+impl FluidComponentCollectionMethods for CIETBranch {
+
+    /// calculates pressure change when given a mass flowrate
+    fn get_pressure_change(
+        &self,
+        fluid_mass_flowrate: MassRate) -> Pressure{
+        let fluid_component_collection_vector =
+            self.get_fluid_component_vector();
+
+        let pressure_change =
+            <Self as FluidComponentCollectionSeriesAssociatedFunctions>
+            ::calculate_pressure_change_from_mass_flowrate(
+                fluid_mass_flowrate,
+                &fluid_component_collection_vector);
+
+        // a speed driven pump adds its pump curve pressure
+        let pressure_change = match self.get_pump_speed() {
+            Some(_) => pressure_change +
+                self.get_pump_pressure_at(fluid_mass_flowrate),
+            None => pressure_change,
+        };
+
+        // a closed valve only allows for zero flow, at which point
+        // there is no valve pressure loss
+        if self.valve.is_closed() {
+            return pressure_change;
+        }
+
+        return pressure_change -
+            self.valve.get_pressure_loss_immutable(fluid_mass_flowrate);
+    }
+
+    /// calculates mass flowrate from pressure change
+    ///
+    /// panics if the flowrate cannot be solved for, use
+    /// try_get_mass_flowrate_from_pressure_change to
+    /// handle the error instead
+    fn get_mass_flowrate_from_pressure_change(
+        &self,
+        pressure_change: Pressure) -> MassRate{
+
+        return self.try_get_mass_flowrate_from_pressure_change(
+            pressure_change).unwrap();
+    }
+
+
+}
+
+impl FluidComponentCollectionSeriesAssociatedFunctions for CIETBranch {}
@@ -0,0 +1,307 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// loss coefficient (K) versus valve opening for a quarter turn
+/// ball valve, listed from most open to least open
+///
+/// opening is a fraction where 1.0 is fully open, the
+/// K values are for a ball valve closed by 0, 10, 20 ... 70
+/// degrees (opening = 1 - angle/90)
+pub const BALL_VALVE_K_VERSUS_OPENING: [(f64, f64); 8] = [
+    (1.0, 0.05),
+    (0.8889, 0.52),
+    (0.7778, 1.56),
+    (0.6667, 5.17),
+    (0.5556, 17.3),
+    (0.4444, 52.6),
+    (0.3333, 206.0),
+    (0.2222, 485.0),
+];
+
+/// errors in a valve K versus opening curve
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValveCurveError {
+    /// the curve has no points
+    Empty,
+    /// the openings are not listed from most open to least open,
+    /// the point at this index is at least as open as the one
+    /// before it
+    NotDescending(usize),
+}
+
+impl fmt::Display for ValveCurveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValveCurveError::Empty =>
+                write!(f, "valve K versus opening curve must not be empty"),
+            ValveCurveError::NotDescending(index) =>
+                write!(f, "valve K versus opening curve must be listed \
+                    from most open to least open, point {} is not", index),
+        }
+    }
+}
+
+impl std::error::Error for ValveCurveError {}
+
+/// a valve sitting within one of the CIET branches
+///
+/// the valve adds a form loss to the branch,
+/// dp_loss = K * m |m| / (2 rho A^2)
+///
+/// where K is interpolated from a K versus opening curve,
+/// so the valve may be partially opened. A fully closed valve
+/// (opening of zero) allows no flow through the branch
+pub struct BranchValve {
+
+    therminol_properties: TherminolVP1Properties,
+    fluid_temp: ThermodynamicTemperature,
+    flow_area: Area,
+
+    // fraction from 0.0 (closed) to 1.0 (fully open)
+    opening: f64,
+    // (opening, K) pairs listed from most open to least open
+    k_versus_opening: Vec<(f64, f64)>,
+    name: String,
+}
+
+impl BranchValve {
+
+    /// constructor, returns a fully open ball valve at 21C
+    ///
+    /// the flow area is usually that of the pipe the valve
+    /// is installed in
+    pub fn new(name: &str, flow_area: Area) -> Self {
+
+        return Self::new_with_k_curve(name, flow_area,
+            BALL_VALVE_K_VERSUS_OPENING.to_vec())
+            .expect("ball valve K versus opening curve should be valid");
+    }
+
+    /// constructor with a user specified K versus opening curve
+    ///
+    /// the curve is a list of (opening, K) pairs listed
+    /// from most open to least open, an empty curve or one
+    /// out of order returns an error
+    pub fn new_with_k_curve(name: &str,
+        flow_area: Area,
+        k_versus_opening: Vec<(f64, f64)>) -> Result<Self, ValveCurveError> {
+
+        if k_versus_opening.is_empty() {
+            return Err(ValveCurveError::Empty);
+        }
+
+        // strictly descending, so that every opening between the
+        // ends of the curve falls between two points
+        for (index, pair) in k_versus_opening.windows(2).enumerate() {
+            let (upper_opening, _) = pair[0];
+            let (lower_opening, _) = pair[1];
+
+            if lower_opening.partial_cmp(&upper_opening) != Some(Ordering::Less) {
+                return Err(ValveCurveError::NotDescending(index + 1));
+            }
+        }
+
+        return Ok(Self {
+            therminol_properties: TherminolVP1Properties::new(),
+            fluid_temp: ThermodynamicTemperature::new::<degree_celsius>(21.0),
+            flow_area,
+            opening: 1.0,
+            k_versus_opening,
+            name: name.to_string(),
+        });
+    }
+
+    pub fn get_name(&self) -> &str {
+        return &self.name;
+    }
+
+    /// sets the valve opening, 0.0 is closed and 1.0 is fully
+    /// open, values outside this range are clamped
+    pub fn set_opening(&mut self, opening: f64){
+        self.opening = opening.clamp(0.0, 1.0);
+    }
+
+    pub fn get_opening(&self) -> f64 {
+        return self.opening;
+    }
+
+    /// opens the valve fully or closes it fully
+    pub fn set_open(&mut self, valve_open: bool){
+        if valve_open {
+            self.set_opening(1.0);
+        } else {
+            self.set_opening(0.0);
+        }
+    }
+
+    /// a closed valve allows no flow at all
+    pub fn is_closed(&self) -> bool {
+        return self.opening <= 0.0;
+    }
+
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        return self.fluid_temp;
+    }
+
+    pub fn set_fluid_temperature(&mut self,
+        fluid_temp: ThermodynamicTemperature){
+        self.fluid_temp = fluid_temp;
+    }
+
+    /// returns the loss coefficient at the current opening
+    ///
+    /// the K versus opening curve is linearly interpolated,
+    /// below the least open point on the curve, K is scaled
+    /// with the inverse square of the opening (as the flow area
+    /// shrinks roughly in proportion to the opening)
+    ///
+    /// a closed valve has an infinite K
+    pub fn get_loss_coefficient(&self) -> f64 {
+
+        if self.is_closed() {
+            return f64::INFINITY;
+        }
+
+        let opening = self.opening;
+        let curve = &self.k_versus_opening;
+
+        // more open than the most open point on the curve
+        let (most_open, most_open_k) = curve[0];
+        if opening >= most_open {
+            return most_open_k;
+        }
+
+        // less open than the least open point on the curve
+        let (least_open, least_open_k) = curve[curve.len()-1];
+        if opening <= least_open {
+            return least_open_k * (least_open/opening).powi(2);
+        }
+
+        // otherwise we interpolate between two points
+        for pair in curve.windows(2) {
+            let (upper_opening, upper_k) = pair[0];
+            let (lower_opening, lower_k) = pair[1];
+
+            if opening <= upper_opening && opening >= lower_opening {
+                let fraction = (opening - lower_opening)/
+                    (upper_opening - lower_opening);
+                return lower_k + fraction * (upper_k - lower_k);
+            }
+        }
+
+        // the curve is checked to be in order on construction,
+        // so only a NaN opening gets here, which is taken as
+        // fully open
+        return most_open_k;
+    }
+
+    /// returns the pressure loss across the valve given
+    /// a mass flowrate, the loss takes the sign of the flow
+    ///
+    /// should not be called for a closed valve
+    pub fn get_pressure_loss_immutable(&self,
+        mass_flowrate: MassRate) -> Pressure {
+
+        let fluid_density = self.therminol_properties.density(
+            self.fluid_temp);
+
+        let loss_coefficient = self.get_loss_coefficient();
+
+        let flow_area = self.flow_area;
+
+        let pressure_loss: Pressure =
+            0.5 * loss_coefficient * mass_flowrate * mass_flowrate.abs()
+            / fluid_density / flow_area / flow_area;
+
+        return pressure_loss;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_valve() -> BranchValve {
+        return BranchValve::new("test_valve",
+            Area::new::<square_meter>(6.11e-4));
+    }
+
+    #[test]
+    fn fully_open_valve_has_the_most_open_k() {
+        let valve = test_valve();
+
+        assert_eq!(valve.get_opening(), 1.0);
+        assert_eq!(valve.get_loss_coefficient(), 0.05);
+    }
+
+    #[test]
+    fn k_is_interpolated_between_curve_points() {
+        let mut valve = test_valve();
+
+        // on a curve point
+        valve.set_opening(0.6667);
+        assert!((valve.get_loss_coefficient() - 5.17).abs() < 1e-12);
+
+        // halfway between the two most open points
+        valve.set_opening(0.5 * (1.0 + 0.8889));
+        assert!((valve.get_loss_coefficient() - 0.285).abs() < 1e-9);
+    }
+
+    #[test]
+    fn k_scales_with_inverse_opening_squared_below_the_curve() {
+        let mut valve = test_valve();
+
+        // half the least open point
+        valve.set_opening(0.1111);
+        assert!((valve.get_loss_coefficient() - 4.0 * 485.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn closed_valve_has_infinite_k() {
+        let mut valve = test_valve();
+
+        valve.set_open(false);
+        assert!(valve.is_closed());
+        assert_eq!(valve.get_loss_coefficient(), f64::INFINITY);
+    }
+
+    #[test]
+    fn opening_is_clamped() {
+        let mut valve = test_valve();
+
+        valve.set_opening(1.5);
+        assert_eq!(valve.get_opening(), 1.0);
+        valve.set_opening(-0.5);
+        assert_eq!(valve.get_opening(), 0.0);
+    }
+
+    #[test]
+    fn pressure_loss_takes_the_sign_of_the_flow() {
+        let valve = test_valve();
+        let forward_loss = valve.get_pressure_loss_immutable(
+            MassRate::new::<kilogram_per_second>(0.2));
+        let reverse_loss = valve.get_pressure_loss_immutable(
+            MassRate::new::<kilogram_per_second>(-0.2));
+        let double_flow_loss = valve.get_pressure_loss_immutable(
+            MassRate::new::<kilogram_per_second>(0.4));
+
+        assert!(forward_loss.get::<pascal>() > 0.0);
+        assert!((forward_loss + reverse_loss).get::<pascal>().abs() < 1e-9);
+        assert!(((double_flow_loss/forward_loss).value - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unusable_k_curves_are_rejected() {
+        let flow_area = Area::new::<square_meter>(6.11e-4);
+
+        assert_eq!(BranchValve::new_with_k_curve("empty", flow_area, vec![])
+            .err(), Some(ValveCurveError::Empty));
+        assert_eq!(BranchValve::new_with_k_curve("ascending", flow_area,
+                vec![(0.5, 10.0), (1.0, 0.05)]).err(),
+            Some(ValveCurveError::NotDescending(1)));
+        assert_eq!(BranchValve::new_with_k_curve("repeated", flow_area,
+                vec![(1.0, 0.05), (0.5, 10.0), (0.5, 20.0)]).err(),
+            Some(ValveCurveError::NotDescending(2)));
+    }
+}
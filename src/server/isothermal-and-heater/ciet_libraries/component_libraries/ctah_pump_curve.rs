@@ -0,0 +1,248 @@
+use serde::{Deserialize, Serialize};
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::frequency::hertz;
+use std::cmp::Ordering;
+
+use crate::LoopDefinitionError;
+
+/// rated speed (VFD frequency) of the ctah pump
+pub const CTAH_PUMP_RATED_SPEED_HERTZ: f64 = 60.0;
+
+/// pressure rise (Pa) versus mass flowrate (kg/s) of the ctah
+/// pump at its rated speed, listed in increasing flowrate
+///
+/// PLACEHOLDER: this is not the measured curve of the CIET pump,
+/// and no published source is available for it. It is a generic
+/// centrifugal pump shape sized so that the shutoff pressure and
+/// runout flowrate bracket the CIET flowrates. Replace it with the
+/// measured curve through ctah_pump_curve in the loop definition
+/// file before comparing speed driven runs against experiment
+pub const CTAH_PUMP_CURVE_AT_RATED_SPEED: [(f64, f64); 6] = [
+    (0.0, 20000.0),
+    (0.1, 19000.0),
+    (0.2, 17000.0),
+    (0.3, 13500.0),
+    (0.4, 8500.0),
+    (0.5, 2000.0),
+];
+
+/// pressure rise versus flowrate curve of a pump, scaled with
+/// pump speed using the affinity laws
+///
+/// at a speed N, with the curve given at the rated speed N0,
+///
+/// flowrate scales with N/N0,
+/// pressure rise scales with (N/N0)^2
+///
+/// so the pressure rise at a mass flowrate m is
+/// dp(m, N) = (N/N0)^2 dp0(m N0/N)
+///
+/// beyond either end of the curve, the pressure rise is linearly
+/// extrapolated from the nearest segment, so that the loop
+/// solvers still see a monotonic curve for reverse flow or
+/// flow beyond pump runout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PumpCurve {
+    pub rated_speed_hertz: f64,
+    /// (mass flowrate kg/s, pressure rise Pa) pairs at the rated
+    /// speed, listed in increasing flowrate
+    pub pressure_versus_flowrate: Vec<(f64, f64)>,
+}
+
+impl PumpCurve {
+
+    /// constructor, the curve needs at least two points listed in
+    /// increasing flowrate and a positive rated speed
+    pub fn new(rated_speed: Frequency,
+        pressure_versus_flowrate: Vec<(f64, f64)>) 
+        -> Result<Self, LoopDefinitionError> {
+
+        let pump_curve = Self {
+            rated_speed_hertz: rated_speed.get::<hertz>(),
+            pressure_versus_flowrate,
+        };
+
+        pump_curve.validate()?;
+
+        return Ok(pump_curve);
+    }
+
+    /// checks that the curve has at least two points, that the
+    /// flowrates strictly increase (so every flowrate falls on
+    /// one segment) and that the rated speed is positive (the
+    /// affinity laws divide by it)
+    pub fn validate(&self) -> Result<(), LoopDefinitionError> {
+
+        if self.pressure_versus_flowrate.len() < 2 {
+            return Err(LoopDefinitionError::InvalidPumpCurve(
+                    "the curve needs at least two points".to_string()));
+        }
+
+        for (index, pair) in self.pressure_versus_flowrate.windows(2).enumerate() {
+            let (lower_flowrate, _) = pair[0];
+            let (upper_flowrate, _) = pair[1];
+
+            if lower_flowrate.partial_cmp(&upper_flowrate) != Some(Ordering::Less) {
+                return Err(LoopDefinitionError::InvalidPumpCurve(format!(
+                            "flowrates must strictly increase, point {} \
+                            does not", index + 1)));
+            }
+        }
+
+        let pressures_finite = self.pressure_versus_flowrate.iter()
+            .all(|(_, pressure_rise)| pressure_rise.is_finite());
+
+        if !pressures_finite {
+            return Err(LoopDefinitionError::InvalidPumpCurve(
+                    "pressure rises must be finite".to_string()));
+        }
+
+        if !(self.rated_speed_hertz.is_finite() && self.rated_speed_hertz > 0.0) {
+            return Err(LoopDefinitionError::InvalidPumpCurve(format!(
+                        "rated speed must be positive, got {} Hz",
+                        self.rated_speed_hertz)));
+        }
+
+        return Ok(());
+    }
+
+    pub fn get_rated_speed(&self) -> Frequency {
+        return Frequency::new::<hertz>(self.rated_speed_hertz);
+    }
+
+    /// returns the pump pressure rise at a given mass flowrate
+    /// and pump speed
+    ///
+    /// a stopped pump (zero speed) has no pressure rise
+    pub fn get_pressure_rise(&self,
+        mass_flowrate: MassRate,
+        pump_speed: Frequency) -> Pressure {
+
+        let speed_ratio = pump_speed.get::<hertz>() / self.rated_speed_hertz;
+
+        if speed_ratio <= 0.0 {
+            return Pressure::new::<pascal>(0.0);
+        }
+
+        // affinity laws
+        let rated_speed_mass_flowrate =
+            mass_flowrate.get::<kilogram_per_second>() / speed_ratio;
+
+        let rated_speed_pressure_rise =
+            self.get_rated_speed_pressure_rise(rated_speed_mass_flowrate);
+
+        return Pressure::new::<pascal>(
+            speed_ratio * speed_ratio * rated_speed_pressure_rise);
+    }
+
+    // linear interpolation along the curve, extrapolated
+    // beyond either end
+    fn get_rated_speed_pressure_rise(&self, mass_flowrate_kg_per_s: f64) -> f64 {
+
+        let curve = &self.pressure_versus_flowrate;
+
+        // pick the segment containing the flowrate, or the end
+        // segment nearest to it
+        let last_segment = curve.len() - 2;
+        let segment_index = curve.windows(2)
+            .position(|pair| mass_flowrate_kg_per_s <= pair[1].0)
+            .unwrap_or(last_segment);
+
+        let (lower_flowrate, lower_pressure) = curve[segment_index];
+        let (upper_flowrate, upper_pressure) = curve[segment_index + 1];
+
+        let fraction = (mass_flowrate_kg_per_s - lower_flowrate)/
+            (upper_flowrate - lower_flowrate);
+
+        return lower_pressure + fraction * (upper_pressure - lower_pressure);
+    }
+}
+
+impl Default for PumpCurve {
+    /// the ctah pump curve, which is a placeholder
+    /// (see CTAH_PUMP_CURVE_AT_RATED_SPEED)
+    fn default() -> Self {
+        return Self::new(
+            Frequency::new::<hertz>(CTAH_PUMP_RATED_SPEED_HERTZ),
+            CTAH_PUMP_CURVE_AT_RATED_SPEED.to_vec())
+            .expect("default ctah pump curve should be valid");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated_speed() -> Frequency {
+        return Frequency::new::<hertz>(CTAH_PUMP_RATED_SPEED_HERTZ);
+    }
+
+    #[test]
+    fn rated_speed_follows_the_curve() {
+        let pump_curve = PumpCurve::default();
+
+        let shutoff_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.0), rated_speed());
+        let midpoint_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.25), rated_speed());
+
+        assert!((shutoff_pressure.get::<pascal>() - 20000.0).abs() < 1e-9);
+        assert!((midpoint_pressure.get::<pascal>() - 15250.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn pressure_rise_follows_the_affinity_laws() {
+        let pump_curve = PumpCurve::default();
+        let half_speed = 0.5 * rated_speed();
+
+        // half speed, half the flowrate and a quarter of the
+        // pressure rise
+        let rated_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.3), rated_speed());
+        let half_speed_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.15), half_speed);
+
+        assert!(((half_speed_pressure/rated_pressure).value - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn stopped_pump_has_no_pressure_rise() {
+        let pump_curve = PumpCurve::default();
+
+        let pressure_rise = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.1),
+            Frequency::new::<hertz>(0.0));
+
+        assert_eq!(pressure_rise.get::<pascal>(), 0.0);
+    }
+
+    #[test]
+    fn curve_is_extrapolated_beyond_either_end() {
+        let pump_curve = PumpCurve::default();
+
+        // reverse flow extends the first segment, past runout
+        // extends the last one
+        let reverse_flow_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(-0.1), rated_speed());
+        let past_runout_pressure = pump_curve.get_pressure_rise(
+            MassRate::new::<kilogram_per_second>(0.6), rated_speed());
+
+        assert!((reverse_flow_pressure.get::<pascal>() - 21000.0).abs() < 1e-9);
+        assert!((past_runout_pressure.get::<pascal>() + 4500.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn unusable_curves_are_rejected() {
+        let curves = [
+            (rated_speed(), vec![(0.0, 20000.0)]),
+            (rated_speed(), vec![(0.2, 20000.0), (0.1, 10000.0)]),
+            (rated_speed(), vec![(0.0, f64::NAN), (0.1, 10000.0)]),
+            (Frequency::new::<hertz>(0.0), vec![(0.0, 20000.0), (0.1, 10000.0)]),
+        ];
+
+        for (rated_speed, pressure_versus_flowrate) in curves {
+            assert!(matches!(PumpCurve::new(rated_speed, pressure_versus_flowrate),
+                Err(LoopDefinitionError::InvalidPumpCurve(_))));
+        }
+    }
+}
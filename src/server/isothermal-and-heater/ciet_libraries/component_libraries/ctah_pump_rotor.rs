@@ -0,0 +1,296 @@
+use serde::{Deserialize, Serialize};
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::frequency::hertz;
+use uom::si::moment_of_inertia::kilogram_square_meter;
+use uom::si::torque::newton_meter;
+use uom::si::time::second;
+
+use crate::LoopDefinitionError;
+
+/// moment of inertia of the ctah pump motor rotor and impeller
+///
+/// PROVISIONAL: this has no published source, it is an order of 
+/// magnitude estimate for a small (under 1 kW) centrifugal pump,
+/// it is only the default for ctah_pump_rotor in the loop 
+/// definition and should be replaced there with a value fitted 
+/// to a measured coast-down of the CIET pump
+pub const CTAH_PUMP_ROTOR_MOMENT_OF_INERTIA_KILOGRAM_SQUARE_METERS: f64 = 0.005;
+
+/// torque acting against the ctah pump rotor at its rated
+/// speed (hydraulic, bearing and windage losses together)
+///
+/// PROVISIONAL: as for the moment of inertia, this is an 
+/// estimate without a published source, only the ratio of the 
+/// two sets the coast-down time
+pub const CTAH_PUMP_RATED_TORQUE_NEWTON_METERS: f64 = 1.0;
+
+/// rotor parameters of a pump as given in a loop definition,
+/// the rated speed comes from the pump curve
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PumpRotorDefinition {
+    pub moment_of_inertia_kilogram_square_meters: f64,
+    pub rated_torque_newton_meters: f64,
+}
+
+impl PumpRotorDefinition {
+
+    /// checks that the moment of inertia and rated torque are
+    /// positive, the coast-down time constant divides by the
+    /// rated torque
+    pub fn validate(&self) -> Result<(), LoopDefinitionError> {
+
+        let parameters = [
+            ("moment of inertia", self.moment_of_inertia_kilogram_square_meters),
+            ("rated torque", self.rated_torque_newton_meters),
+        ];
+
+        for (parameter_name, value) in parameters {
+            if !(value.is_finite() && value > 0.0) {
+                return Err(LoopDefinitionError::InvalidPumpRotor(format!(
+                            "{} must be positive, got {}",
+                            parameter_name, value)));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// builds the rotor, which starts out running
+    pub fn build(&self, rated_speed: Frequency) -> PumpRotor {
+        return PumpRotor::new(
+            MomentOfInertia::new::<kilogram_square_meter>(
+                self.moment_of_inertia_kilogram_square_meters),
+            Torque::new::<newton_meter>(
+                self.rated_torque_newton_meters),
+            rated_speed);
+    }
+}
+
+impl Default for PumpRotorDefinition {
+    /// the provisional ctah pump rotor parameters
+    fn default() -> Self {
+        return Self {
+            moment_of_inertia_kilogram_square_meters:
+                CTAH_PUMP_ROTOR_MOMENT_OF_INERTIA_KILOGRAM_SQUARE_METERS,
+            rated_torque_newton_meters:
+                CTAH_PUMP_RATED_TORQUE_NEWTON_METERS,
+        };
+    }
+}
+
+/// rotor of a pump, used to model the pump coasting down
+/// after it is tripped
+///
+/// while the pump runs, its motor holds it at the set speed,
+/// once tripped, the rotor slows down under the torque acting
+/// against it, which is taken to scale with speed squared
+/// as for hydraulic torque (affinity laws)
+///
+/// J dw/dt = - rated_torque (w/w_rated)^2
+///
+/// the speed ratio s = w/w_trip (speed over speed at trip)
+/// then follows
+///
+/// ds/dt = - s^2 / t_c
+/// t_c = J w_rated^2 / (rated_torque w_trip)
+///
+/// which has the exact solution s = 1/(1 + t/t_c), this is
+/// used to advance the rotor so that any timestep is stable
+///
+/// t_c is the time taken for the pump to coast down to
+/// half its speed at trip
+#[derive(Debug, Clone, PartialEq)]
+pub struct PumpRotor {
+    moment_of_inertia: MomentOfInertia,
+    rated_torque: Torque,
+    rated_speed: Frequency,
+    tripped: bool,
+    speed_ratio: f64,
+}
+
+impl PumpRotor {
+
+    /// constructor, the pump starts out running
+    pub fn new(moment_of_inertia: MomentOfInertia,
+        rated_torque: Torque,
+        rated_speed: Frequency) -> Self {
+
+        return Self {
+            moment_of_inertia,
+            rated_torque,
+            rated_speed,
+            tripped: false,
+            speed_ratio: 1.0,
+        };
+    }
+
+    /// trips the pump, its motor no longer drives it and
+    /// it starts coasting down from its current speed
+    pub fn trip(&mut self){
+        if !self.tripped {
+            self.tripped = true;
+            self.speed_ratio = 1.0;
+        }
+    }
+
+    /// restarts a tripped pump, its motor brings it back
+    /// to the set speed straightaway
+    pub fn reset(&mut self){
+        self.tripped = false;
+        self.speed_ratio = 1.0;
+    }
+
+    pub fn is_tripped(&self) -> bool {
+        return self.tripped;
+    }
+
+    /// returns the pump speed over its speed at trip,
+    /// this is 1.0 while the pump is running
+    pub fn get_speed_ratio(&self) -> f64 {
+        return self.speed_ratio;
+    }
+
+    /// returns the time taken for the pump to coast down to
+    /// half its speed at trip
+    pub fn get_coastdown_time_constant(&self, speed_at_trip: Frequency) -> Time {
+
+        let rated_angular_speed = 2.0 * std::f64::consts::PI
+            * self.rated_speed.get::<hertz>();
+        let angular_speed_at_trip = 2.0 * std::f64::consts::PI
+            * speed_at_trip.get::<hertz>();
+
+        return Time::new::<second>(
+            self.moment_of_inertia.get::<kilogram_square_meter>()
+            * rated_angular_speed * rated_angular_speed
+            / self.rated_torque.get::<newton_meter>()
+            / angular_speed_at_trip);
+    }
+
+    /// advances the coast-down by one timestep, this does
+    /// nothing while the pump is running
+    pub fn advance_timestep(&mut self,
+        timestep: Time,
+        speed_at_trip: Frequency){
+
+        if !self.tripped {
+            return;
+        }
+
+        // a pump which was stopped at trip stays stopped
+        if speed_at_trip.get::<hertz>() <= 0.0 {
+            self.speed_ratio = 0.0;
+            return;
+        }
+
+        let coastdown_time_constant =
+            self.get_coastdown_time_constant(speed_at_trip);
+
+        let timestep_ratio: f64 = (timestep / coastdown_time_constant).value;
+
+        self.speed_ratio = self.speed_ratio
+            / (1.0 + self.speed_ratio * timestep_ratio);
+    }
+}
+
+impl Default for PumpRotor {
+    /// the ctah pump rotor, with provisional parameters
+    fn default() -> Self {
+        return PumpRotorDefinition::default().build(
+            Frequency::new::<hertz>(crate::CTAH_PUMP_RATED_SPEED_HERTZ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated_speed() -> Frequency {
+        return Frequency::new::<hertz>(crate::CTAH_PUMP_RATED_SPEED_HERTZ);
+    }
+
+    #[test]
+    fn running_pump_does_not_coast_down() {
+        let mut pump_rotor = PumpRotor::default();
+
+        pump_rotor.advance_timestep(Time::new::<second>(10.0), rated_speed());
+
+        assert!(!pump_rotor.is_tripped());
+        assert_eq!(pump_rotor.get_speed_ratio(), 1.0);
+    }
+
+    #[test]
+    fn tripped_pump_is_at_half_speed_after_one_time_constant() {
+        let mut pump_rotor = PumpRotor::default();
+        let coastdown_time_constant =
+            pump_rotor.get_coastdown_time_constant(rated_speed());
+
+        pump_rotor.trip();
+        pump_rotor.advance_timestep(coastdown_time_constant, rated_speed());
+
+        assert!(pump_rotor.is_tripped());
+        assert!((pump_rotor.get_speed_ratio() - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn coast_down_does_not_depend_on_the_timestep() {
+        let mut one_step_rotor = PumpRotor::default();
+        let mut many_step_rotor = PumpRotor::default();
+
+        one_step_rotor.trip();
+        many_step_rotor.trip();
+
+        one_step_rotor.advance_timestep(Time::new::<second>(5.0), rated_speed());
+        for _ in 0..500 {
+            many_step_rotor.advance_timestep(Time::new::<second>(0.01), rated_speed());
+        }
+
+        // s = 1/(1 + t/t_c)
+        let coastdown_time_constant: f64 = one_step_rotor
+            .get_coastdown_time_constant(rated_speed()).get::<second>();
+        let expected_speed_ratio = 1.0/(1.0 + 5.0/coastdown_time_constant);
+
+        assert!((one_step_rotor.get_speed_ratio() - expected_speed_ratio).abs() < 1e-12);
+        assert!((many_step_rotor.get_speed_ratio() - expected_speed_ratio).abs() < 1e-9);
+    }
+
+    #[test]
+    fn stopped_pump_stays_stopped_after_a_trip() {
+        let mut pump_rotor = PumpRotor::default();
+
+        pump_rotor.trip();
+        pump_rotor.advance_timestep(Time::new::<second>(0.1),
+            Frequency::new::<hertz>(0.0));
+
+        assert_eq!(pump_rotor.get_speed_ratio(), 0.0);
+    }
+
+    #[test]
+    fn reset_restarts_the_pump() {
+        let mut pump_rotor = PumpRotor::default();
+
+        pump_rotor.trip();
+        pump_rotor.advance_timestep(Time::new::<second>(5.0), rated_speed());
+        pump_rotor.reset();
+
+        assert!(!pump_rotor.is_tripped());
+        assert_eq!(pump_rotor.get_speed_ratio(), 1.0);
+    }
+
+    #[test]
+    fn rotor_parameters_must_be_positive() {
+        let zero_torque_rotor = PumpRotorDefinition {
+            rated_torque_newton_meters: 0.0,
+            ..PumpRotorDefinition::default()
+        };
+        let negative_inertia_rotor = PumpRotorDefinition {
+            moment_of_inertia_kilogram_square_meters: -0.005,
+            ..PumpRotorDefinition::default()
+        };
+
+        assert!(PumpRotorDefinition::default().validate().is_ok());
+        assert!(matches!(zero_torque_rotor.validate(),
+            Err(LoopDefinitionError::InvalidPumpRotor(_))));
+        assert!(matches!(negative_inertia_rotor.validate(),
+            Err(LoopDefinitionError::InvalidPumpRotor(_))));
+    }
+}
@@ -0,0 +1,392 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use crate::LoopDefinitionError;
+
+// reynolds numbers at which a closure is sampled to turn it
+// into a correlation, twenty points a decade from 0.1 to 1e7
+const SAMPLED_LOWEST_REYNOLDS_EXPONENT: f64 = -1.0;
+const SAMPLED_HIGHEST_REYNOLDS_EXPONENT: f64 = 7.0;
+const SAMPLED_POINTS_PER_DECADE: usize = 20;
+
+/// a form loss (K) or darcy friction factor correlation
+/// for custom components
+///
+/// unlike a closure, a correlation is plain data, so components
+/// built with it can be cloned, sent across threads, compared,
+/// saved to (and read from) a loop definition file and edited
+/// at runtime
+///
+/// reverse flow: unless the ReverseFlow form is used, the
+/// correlation is evaluated at |Re| and takes the sign of Re,
+/// this is how all the custom K and darcy functions in CIET
+/// handled reverse flow
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "form", rename_all = "snake_case")]
+pub enum Correlation {
+    /// always zero, eg. the darcy friction factor of components
+    /// whose losses are all lumped into K
+    Zero,
+    /// a constant value
+    Constant { value: f64 },
+    /// a + b Re^c
+    ///
+    /// eg. static mixers have K = 21 + 4000/Re,
+    /// which is a = 21, b = 4000, c = -1
+    PowerLaw { a: f64, b: f64, c: f64 },
+    /// churchill friction factor correlation, using the
+    /// roughness ratio of the component
+    Churchill,
+    /// (Re, value) pairs listed in increasing Re, the value
+    /// is linearly interpolated between points and
+    /// held constant beyond either end of the table
+    PiecewiseTable { points: Vec<(f64, f64)> },
+    /// a different correlation for forward (Re >= 0) and
+    /// reverse (Re < 0) flow, each is evaluated at |Re| and
+    /// the reverse one takes a negative sign
+    ///
+    /// eg. an artificial check valve with a very large reverse
+    /// flow K
+    ReverseFlow {
+        forward: Box<Correlation>,
+        reverse: Box<Correlation>,
+    },
+}
+
+impl Correlation {
+
+    /// evaluates the correlation at a given reynolds number
+    /// and roughness ratio, the result takes the sign of the
+    /// reynolds number
+    pub fn evaluate(&self, reynolds_number: f64, roughness_ratio: f64) -> f64 {
+
+        if let Correlation::ReverseFlow { forward, reverse } = self {
+            if reynolds_number < 0.0 {
+                return -reverse.evaluate(-reynolds_number, roughness_ratio);
+            }
+            return forward.evaluate(reynolds_number, roughness_ratio);
+        }
+
+        let value = self.evaluate_forward_flow(
+            reynolds_number.abs(), roughness_ratio);
+
+        // the user account for reverse flow scenarios...
+        if reynolds_number < 0.0 {
+            return -value;
+        }
+
+        return value;
+    }
+
+    /// tabulates a K function of Re as a correlation
+    ///
+    /// the function is sampled at twenty points a decade for 
+    /// 0.1 < |Re| < 1e7 in both flow directions, and is held 
+    /// constant beyond these, so this is only an approximation 
+    /// of the function
+    pub fn from_sampled_k(custom_k: &dyn Fn(f64) -> f64) -> Self {
+        return Self::from_sampled_function(custom_k);
+    }
+
+    /// tabulates a darcy friction factor function of Re and 
+    /// roughness ratio as a correlation, at the roughness ratio 
+    /// of the component it is for
+    ///
+    /// the function is sampled in the same way as from_sampled_k
+    pub fn from_sampled_darcy(custom_darcy: &dyn Fn(f64, f64) -> f64,
+        roughness_ratio: f64) -> Self {
+        return Self::from_sampled_function(
+            &|reynolds_number| custom_darcy(reynolds_number, roughness_ratio));
+    }
+
+    // samples a function of Re into forward and reverse flow 
+    // tables, the reverse table is negated since the reverse 
+    // correlation takes a negative sign
+    fn from_sampled_function(function: &dyn Fn(f64) -> f64) -> Self {
+
+        let decades = SAMPLED_HIGHEST_REYNOLDS_EXPONENT 
+            - SAMPLED_LOWEST_REYNOLDS_EXPONENT;
+        let point_count = (decades as usize) * SAMPLED_POINTS_PER_DECADE + 1;
+
+        let reynolds_numbers: Vec<f64> = (0..point_count)
+            .map(|index| 10.0_f64.powf(SAMPLED_LOWEST_REYNOLDS_EXPONENT 
+                    + (index as f64) / (SAMPLED_POINTS_PER_DECADE as f64)))
+            .collect();
+
+        let forward_points = reynolds_numbers.iter()
+            .map(|reynolds_number| (*reynolds_number, function(*reynolds_number)))
+            .collect();
+        let reverse_points = reynolds_numbers.iter()
+            .map(|reynolds_number| (*reynolds_number, -function(-reynolds_number)))
+            .collect();
+
+        return Correlation::ReverseFlow {
+            forward: Box::new(Correlation::PiecewiseTable { points: forward_points }),
+            reverse: Box::new(Correlation::PiecewiseTable { points: reverse_points }),
+        };
+    }
+
+    /// checks that piecewise tables are not empty, are listed 
+    /// in strictly increasing Re and have finite values, and 
+    /// that the roughness ratio the correlation is evaluated 
+    /// at is not negative (churchill needs this)
+    pub fn validate(&self, roughness_ratio: f64) -> Result<(), LoopDefinitionError> {
+
+        if !(roughness_ratio >= 0.0) {
+            return Err(LoopDefinitionError::InvalidCorrelation(format!(
+                        "roughness ratio must not be negative, got {}",
+                        roughness_ratio)));
+        }
+
+        match self {
+            Correlation::PiecewiseTable { points } => {
+
+                if points.is_empty() {
+                    return Err(LoopDefinitionError::InvalidCorrelation(
+                            "piecewise table must not be empty".to_string()));
+                }
+
+                for (index, pair) in points.windows(2).enumerate() {
+                    let (lower_reynolds_number, _) = pair[0];
+                    let (upper_reynolds_number, _) = pair[1];
+
+                    if lower_reynolds_number.partial_cmp(&upper_reynolds_number) 
+                        != Some(Ordering::Less) {
+                        return Err(LoopDefinitionError::InvalidCorrelation(format!(
+                                    "piecewise table reynolds numbers must \
+                                    strictly increase, point {} does not", 
+                                    index + 1)));
+                    }
+                }
+
+                let values_finite = points.iter()
+                    .all(|(_, value)| value.is_finite());
+
+                if !values_finite {
+                    return Err(LoopDefinitionError::InvalidCorrelation(
+                            "piecewise table values must be finite".to_string()));
+                }
+
+                return Ok(());
+            },
+            Correlation::ReverseFlow { forward, reverse } => {
+                forward.validate(roughness_ratio)?;
+                return reverse.validate(roughness_ratio);
+            },
+            _ => return Ok(()),
+        }
+    }
+
+    /// evaluates the correlation as a form loss K, for which
+    /// there is no roughness ratio (churchill is then
+    /// evaluated for a smooth pipe)
+    pub fn evaluate_k(&self, reynolds_number: f64) -> f64 {
+        return self.evaluate(reynolds_number, 0.0);
+    }
+
+    // evaluates the correlation for a positive reynolds number
+    fn evaluate_forward_flow(&self,
+        reynolds_number: f64,
+        roughness_ratio: f64) -> f64 {
+
+        match self {
+            Correlation::Zero => return 0.0,
+            Correlation::Constant { value } => return *value,
+            Correlation::PowerLaw { a, b, c } =>
+                return a + b * reynolds_number.powf(*c),
+            Correlation::Churchill => {
+
+                if roughness_ratio < 0.0 {
+                    panic!("roughness_ratio < 0.0");
+                }
+
+                use thermal_hydraulics_rs::fluid_mechanics_lib::churchill_friction_factor;
+                return churchill_friction_factor::darcy(reynolds_number,
+                    roughness_ratio);
+            },
+            Correlation::PiecewiseTable { points } =>
+                return Self::interpolate(points, reynolds_number),
+            Correlation::ReverseFlow { forward, .. } =>
+                return forward.evaluate_forward_flow(
+                    reynolds_number, roughness_ratio),
+        }
+    }
+
+    // linear interpolation within a (Re, value) table, held
+    // constant beyond either end
+    //
+    // tables set at runtime are not validated, so an empty table
+    // or a NaN reynolds number gives NaN rather than a panic, 
+    // the flow solvers then report a non finite residual
+    fn interpolate(points: &[(f64, f64)], reynolds_number: f64) -> f64 {
+
+        if points.is_empty() || reynolds_number.is_nan() {
+            return f64::NAN;
+        }
+
+        let (lowest_reynolds_number, lowest_value) = points[0];
+        if reynolds_number <= lowest_reynolds_number {
+            return lowest_value;
+        }
+
+        let (highest_reynolds_number, highest_value) = points[points.len()-1];
+        if reynolds_number >= highest_reynolds_number {
+            return highest_value;
+        }
+
+        for pair in points.windows(2) {
+            let (lower_reynolds_number, lower_value) = pair[0];
+            let (upper_reynolds_number, upper_value) = pair[1];
+
+            if reynolds_number >= lower_reynolds_number
+                && reynolds_number <= upper_reynolds_number {
+                let fraction = (reynolds_number - lower_reynolds_number)/
+                    (upper_reynolds_number - lower_reynolds_number);
+                return lower_value + fraction * (upper_value - lower_value);
+            }
+        }
+
+        // only reached if the table is not listed in 
+        // increasing Re
+        return f64::NAN;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn power_law_takes_the_sign_of_re() {
+        // static mixer, K = 21 + 4000/Re
+        let static_mixer_k = Correlation::PowerLaw { a: 21.0, b: 4000.0, c: -1.0 };
+
+        assert!((static_mixer_k.evaluate_k(1000.0) - 25.0).abs() < 1e-12);
+        assert!((static_mixer_k.evaluate_k(-1000.0) + 25.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn constant_and_zero_ignore_re() {
+        assert_eq!(Correlation::Constant { value: 3.9 }.evaluate_k(5000.0), 3.9);
+        assert_eq!(Correlation::Zero.evaluate(5000.0, 1e-3), 0.0);
+    }
+
+    #[test]
+    fn churchill_matches_the_library_correlation() {
+        use thermal_hydraulics_rs::fluid_mechanics_lib::churchill_friction_factor;
+
+        let darcy = Correlation::Churchill.evaluate(5000.0, 1e-4);
+        let expected = churchill_friction_factor::darcy(5000.0, 1e-4);
+
+        assert!((darcy - expected).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reverse_flow_uses_the_reverse_correlation() {
+        let check_valve_k = Correlation::ReverseFlow {
+            forward: Box::new(Correlation::Constant { value: 1.0 }),
+            reverse: Box::new(Correlation::Constant { value: 1000.0 }),
+        };
+
+        assert_eq!(check_valve_k.evaluate_k(10.0), 1.0);
+        assert_eq!(check_valve_k.evaluate_k(-10.0), -1000.0);
+    }
+
+    #[test]
+    fn piecewise_table_interpolates_and_holds_at_the_ends() {
+        let table = Correlation::PiecewiseTable {
+            points: vec![(100.0, 10.0), (200.0, 20.0), (400.0, 0.0)],
+        };
+
+        assert!((table.evaluate_k(150.0) - 15.0).abs() < 1e-12);
+        assert!((table.evaluate_k(300.0) - 10.0).abs() < 1e-12);
+        assert_eq!(table.evaluate_k(200.0), 20.0);
+        assert_eq!(table.evaluate_k(50.0), 10.0);
+        assert_eq!(table.evaluate_k(1000.0), 0.0);
+        assert!((table.evaluate_k(-150.0) + 15.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn piecewise_table_gives_nan_rather_than_panicking() {
+        let table = Correlation::PiecewiseTable {
+            points: vec![(100.0, 10.0), (200.0, 20.0)],
+        };
+        let empty_table = Correlation::PiecewiseTable { points: vec![] };
+
+        assert!(table.evaluate_k(f64::NAN).is_nan());
+        assert!(empty_table.evaluate_k(150.0).is_nan());
+    }
+
+    #[test]
+    fn validate_accepts_usable_correlations() {
+        let table = Correlation::PiecewiseTable {
+            points: vec![(100.0, 10.0), (200.0, 20.0)],
+        };
+
+        assert!(table.validate(0.0).is_ok());
+        assert!(Correlation::Churchill.validate(1e-4).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_unusable_tables_and_roughness() {
+        let empty_table = Correlation::PiecewiseTable { points: vec![] };
+        let unordered_table = Correlation::PiecewiseTable {
+            points: vec![(200.0, 10.0), (100.0, 20.0)],
+        };
+        let repeated_re_table = Correlation::PiecewiseTable {
+            points: vec![(100.0, 10.0), (100.0, 20.0)],
+        };
+        let nan_re_table = Correlation::PiecewiseTable {
+            points: vec![(100.0, 10.0), (f64::NAN, 20.0)],
+        };
+        // invalid tables are found within reverse flow forms too
+        let reverse_flow = Correlation::ReverseFlow {
+            forward: Box::new(Correlation::Zero),
+            reverse: Box::new(empty_table.clone()),
+        };
+
+        for correlation in [&empty_table, &unordered_table,
+            &repeated_re_table, &nan_re_table, &reverse_flow] {
+            assert!(matches!(correlation.validate(0.0),
+                Err(LoopDefinitionError::InvalidCorrelation(_))));
+        }
+
+        assert!(matches!(Correlation::Churchill.validate(-1e-4),
+            Err(LoopDefinitionError::InvalidCorrelation(_))));
+        assert!(matches!(Correlation::Churchill.validate(f64::NAN),
+            Err(LoopDefinitionError::InvalidCorrelation(_))));
+    }
+
+    #[test]
+    fn sampled_closure_approximates_it_in_both_directions() {
+        let static_mixer_k = |reynolds_number: f64| {
+            if reynolds_number < 0.0 {
+                return -(100.0 + 4000.0/reynolds_number.abs());
+            }
+            return 21.0 + 4000.0/reynolds_number;
+        };
+
+        let sampled_k = Correlation::from_sampled_k(&static_mixer_k);
+
+        assert!(sampled_k.validate(0.0).is_ok());
+
+        for reynolds_number in [5.0, 1000.0, 1500.0, -1500.0, 80000.0] {
+            let relative_error = (sampled_k.evaluate_k(reynolds_number)
+                - static_mixer_k(reynolds_number)).abs()
+                / static_mixer_k(reynolds_number).abs();
+            assert!(relative_error < 1e-2);
+        }
+    }
+
+    #[test]
+    fn sampled_darcy_uses_the_given_roughness_ratio() {
+        let churchill = |reynolds_number: f64, roughness_ratio: f64|
+            Correlation::Churchill.evaluate(reynolds_number, roughness_ratio);
+
+        let sampled_darcy = Correlation::from_sampled_darcy(&churchill, 1e-3);
+        // sampled exactly at 10^5
+        let darcy = sampled_darcy.evaluate(1e5, 0.0);
+
+        assert!((darcy - churchill(1e5, 1e-3)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,16 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use super::CoiledTubeAirHeatExchanger;
+
+
+impl CoiledTubeAirHeatExchanger {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// CoiledTubeAirHeatExchanger
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        self.tube_wall.advance_timestep_mut_self(timestep).unwrap();
+        
+    }
+}
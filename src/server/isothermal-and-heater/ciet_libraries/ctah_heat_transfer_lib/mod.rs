@@ -0,0 +1,264 @@
+//! The CTAH module here represents the coiled tube air heat 
+//! exchanger (CTAH) of CIET, from BT-41 to BT-43
+//!
+//! BT-41 is the CTAH inlet temperature and BT-43 is the CTAH
+//! outlet temperature
+//!
+//! Hydraulically, the CTAH is represented by the CTAH vertical (7a)
+//! and CTAH horizontal (7b) components in the ctah branch. Here, 
+//! both of them are lumped into one thermal model 
+//! with therminol flowing through the tubes and air blown over 
+//! the finned tube bundle by the CTAH fan
+//!
+//! The tube side is a FluidArray of therminol, and the tube walls 
+//! (copper) are a SolidColumn, as with HeaterVersion2Bare
+//!
+//! The air side is not given its own array, instead each tube wall
+//! node is connected to the air inlet temperature through an 
+//! effectiveness (NTU) conductance,
+//!
+//! G = C_air (1 - exp (-hA/C_air))
+//!
+//! where C_air is the heat capacity rate of the air crossing that 
+//! node and hA is the air side conductance of that node, so that 
+//! the heating up of the air as it crosses the tubes is accounted 
+//! for. This matters at low fan speeds, where little air flows 
+//! across the bundle
+//!
+//! Both the air flowrate and air side heat transfer coefficient 
+//! depend on fan speed. With the fan off, there is still natural 
+//! convection and some natural draft through the bundle.
+//!
+//! Geometry and heat transfer parameters here are representative
+//! values, sized such that the CTAH removes about 8 kW at nominal 
+//! flowrates and a 100 C inlet with the fan at rated speed. They 
+//! should be callibrated against BT-41 and BT-43 data
+//!
+//! The DRACS TCHX is also a finned tube air cooler with a fan, 
+//! so it uses the same model (see new_dracs_tchx) with its 
+//! own geometry
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use uom::si::area::square_meter;
+use uom::si::frequency::hertz;
+use uom::si::heat_transfer::watt_per_square_meter_kelvin;
+use uom::si::ratio::ratio;
+use uom::si::pressure::atmosphere;
+
+/// rated speed (VFD frequency) of the CTAH fan
+pub const CTAH_FAN_RATED_SPEED_HERTZ: f64 = 60.0;
+
+/// length of the CTAH vertical section (7a)
+pub const CTAH_VERTICAL_LENGTH_METERS: f64 = 0.3302;
+
+/// length of the CTAH horizontal section (7b)
+pub const CTAH_HORIZONTAL_LENGTH_METERS: f64 = 1.2342;
+
+/// rated speed (VFD frequency) of the TCHX fan
+pub const TCHX_FAN_RATED_SPEED_HERTZ: f64 = 60.0;
+
+/// length of the TCHX horizontal (35a) and vertical (35b)
+/// sections together
+pub const TCHX_LENGTH_METERS: f64 = 1.2342 + 0.3302;
+
+/// represents the coiled tube air heat exchanger (CTAH) 
+///
+/// note that the hydraulics of the CTAH are calculated in the 
+/// ctah branch, this is only the thermal model
+#[derive(Debug,Clone,PartialEq)]
+pub struct CoiledTubeAirHeatExchanger {
+
+    inner_nodes: usize,
+
+    pub therminol_array: HeatTransferEntity,
+
+    pub tube_wall: HeatTransferEntity,
+
+    pub air_inlet_temperature: ThermodynamicTemperature,
+
+    fan_speed: Frequency,
+
+    rated_fan_speed: Frequency,
+
+    /// total inner surface area of the tubes
+    tube_side_heat_transfer_area: Area,
+
+    /// total air side (finned) surface area of the tube bundle
+    air_side_heat_transfer_area: Area,
+
+    /// air side heat transfer coefficient with the fan off
+    air_side_natural_convection_htc: HeatTransfer,
+
+    /// air side heat transfer coefficient at rated fan speed
+    air_side_rated_htc: HeatTransfer,
+
+    /// air flowrate across the bundle at rated fan speed
+    air_rated_mass_flowrate: MassRate,
+
+    /// air flowrate across the bundle with the fan off
+    air_natural_draft_mass_flowrate: MassRate,
+
+}
+
+impl CoiledTubeAirHeatExchanger {
+
+    /// CIET CTAH constructor, the fan starts off
+    ///
+    /// the tube side is therminol flowing through the combined
+    /// length of the CTAH vertical (7a) and horizontal (7b) 
+    /// sections
+    pub fn new_ciet_ctah(initial_temperature: ThermodynamicTemperature,
+        air_inlet_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        // flow area and hydraulic diameter follow the 7a and 
+        // 7b components in the loop definition
+        let flow_area = Area::new::<square_meter>(0.00133);
+        let tube_length = Length::new::<meter>(
+            CTAH_VERTICAL_LENGTH_METERS + CTAH_HORIZONTAL_LENGTH_METERS);
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+        let dummy_pipe_form_loss = Ratio::new::<ratio>(0.1);
+        let hydraulic_diameter = Length::new::<meter>(0.0119);
+
+        // the ctah is mostly horizontal, not that this is 
+        // particularly important for the thermal model
+        let pipe_incline_angle = Angle::new::<uom::si::angle::degree>(0.0);
+
+        // the tubes are lumped into a single copper shell with
+        // the same flow area and approximately the same wall 
+        // volume (thermal inertia) as the tube bundle
+        let id = Length::new::<meter>(0.04115);
+        let od = Length::new::<meter>(0.04393);
+
+        // inner therminol array
+        let therminol_array: FluidArray = 
+        FluidArray::new_odd_shaped_pipe(
+            tube_length,
+            hydraulic_diameter,
+            flow_area,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Copper,
+            LiquidMaterial::TherminolVP1,
+            dummy_pipe_form_loss,
+            user_specified_inner_nodes,
+            pipe_incline_angle
+        );
+
+        // outer tube wall array
+        let tube_wall_array = 
+        SolidColumn::new_cylindrical_shell(
+            tube_length,
+            id,
+            od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Copper,
+            user_specified_inner_nodes 
+        );
+
+        return Self { inner_nodes: user_specified_inner_nodes,
+            therminol_array: therminol_array.into(),
+            tube_wall: tube_wall_array.into(),
+            air_inlet_temperature,
+            fan_speed: Frequency::new::<hertz>(0.0),
+            rated_fan_speed: Frequency::new::<hertz>(CTAH_FAN_RATED_SPEED_HERTZ),
+            tube_side_heat_transfer_area: Area::new::<square_meter>(4.0),
+            air_side_heat_transfer_area: Area::new::<square_meter>(20.0),
+            air_side_natural_convection_htc: 
+                HeatTransfer::new::<watt_per_square_meter_kelvin>(4.0),
+            air_side_rated_htc: 
+                HeatTransfer::new::<watt_per_square_meter_kelvin>(30.0),
+            air_rated_mass_flowrate: 
+                MassRate::new::<kilogram_per_second>(1.5),
+            air_natural_draft_mass_flowrate: 
+                MassRate::new::<kilogram_per_second>(0.1),
+        };
+    }
+
+    /// DRACS TCHX constructor, the fan starts off
+    ///
+    /// the tube side is therminol flowing through the TCHX 
+    /// horizontal (35a) and vertical (35b) sections of the
+    /// DRACS loop, the TCHX is sized to reject the few kW 
+    /// that the DHX picks up, so it has a smaller finned 
+    /// area and fan than the CTAH
+    pub fn new_dracs_tchx(initial_temperature: ThermodynamicTemperature,
+        air_inlet_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        let mut tchx = Self::new_ciet_ctah(initial_temperature,
+            air_inlet_temperature,
+            user_specified_inner_nodes);
+
+        // the flow area and hydraulic diameter follow the 35a 
+        // and 35b components in the DRACS loop definition,
+        // which are the same as the CTAH 
+        let flow_area = Area::new::<square_meter>(0.00133);
+        let tube_length = Length::new::<meter>(TCHX_LENGTH_METERS);
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+        let dummy_pipe_form_loss = Ratio::new::<ratio>(0.1);
+        let hydraulic_diameter = Length::new::<meter>(0.0119);
+        let pipe_incline_angle = Angle::new::<uom::si::angle::degree>(0.0);
+
+        let id = Length::new::<meter>(0.04115);
+        let od = Length::new::<meter>(0.04393);
+
+        let therminol_array: FluidArray = 
+        FluidArray::new_odd_shaped_pipe(
+            tube_length,
+            hydraulic_diameter,
+            flow_area,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Copper,
+            LiquidMaterial::TherminolVP1,
+            dummy_pipe_form_loss,
+            user_specified_inner_nodes,
+            pipe_incline_angle
+        );
+
+        let tube_wall_array = 
+        SolidColumn::new_cylindrical_shell(
+            tube_length,
+            id,
+            od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Copper,
+            user_specified_inner_nodes 
+        );
+
+        tchx.therminol_array = therminol_array.into();
+        tchx.tube_wall = tube_wall_array.into();
+        tchx.rated_fan_speed = Frequency::new::<hertz>(TCHX_FAN_RATED_SPEED_HERTZ);
+        tchx.tube_side_heat_transfer_area = Area::new::<square_meter>(2.0);
+        tchx.air_side_heat_transfer_area = Area::new::<square_meter>(8.0);
+        tchx.air_rated_mass_flowrate = MassRate::new::<kilogram_per_second>(0.6);
+        tchx.air_natural_draft_mass_flowrate = 
+            MassRate::new::<kilogram_per_second>(0.05);
+
+        return tchx;
+    }
+
+    /// sets the fan speed (VFD frequency), negative speeds 
+    /// are taken as the fan being off
+    pub fn set_fan_speed(&mut self, fan_speed: Frequency){
+        if fan_speed.get::<hertz>() < 0.0 {
+            self.fan_speed = Frequency::new::<hertz>(0.0);
+            return;
+        }
+        self.fan_speed = fan_speed;
+    }
+
+    pub fn get_fan_speed(&self) -> Frequency {
+        return self.fan_speed;
+    }
+}
+
+pub mod preprocessing;
+pub use preprocessing::*;
+
+pub mod calculation;
+
+pub mod postprocessing;
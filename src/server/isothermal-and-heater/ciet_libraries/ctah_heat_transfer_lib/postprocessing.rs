@@ -0,0 +1,78 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::pressure::atmosphere;
+use uom::si::power::watt;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use super::{CoiledTubeAirHeatExchanger, 
+    CTAH_VERTICAL_LENGTH_METERS, CTAH_HORIZONTAL_LENGTH_METERS};
+
+impl CoiledTubeAirHeatExchanger {
+
+    pub fn _tube_wall_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.tube_wall.get_temperature_vector().unwrap()
+    }
+
+    pub fn therminol_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.therminol_array.get_temperature_vector().unwrap()
+    }
+
+    /// the therminol temperature leaving the heat exchanger,
+    /// this is BT-43 for the CTAH
+    pub fn get_outlet_temperature(&mut self) -> ThermodynamicTemperature {
+        self.therminol_array_temperature().into_iter().last().unwrap()
+    }
+
+    /// returns the heat removed from the therminol given its 
+    /// mass flowrate and inlet (BT-41 for the CTAH) temperature
+    pub fn get_heat_removal_rate(&mut self,
+        mass_flowrate: MassRate,
+        inlet_temperature: ThermodynamicTemperature) -> Power {
+
+        let outlet_temperature = self.get_outlet_temperature();
+
+        let therminol_cp: SpecificHeatCapacity = try_get_cp(
+            LiquidMaterial::TherminolVP1.into(),
+            outlet_temperature,
+            Pressure::new::<atmosphere>(1.0)).unwrap();
+
+        let temperature_drop_kelvin: f64 = 
+        inlet_temperature.get::<kelvin>() - outlet_temperature.get::<kelvin>();
+
+        return Power::new::<watt>(
+            mass_flowrate.get::<kilogram_per_second>()
+            * therminol_cp.get::<joule_per_kilogram_kelvin>()
+            * temperature_drop_kelvin);
+    }
+
+    /// returns the average therminol temperatures of the CTAH 
+    /// vertical (7a) and horizontal (7b) sections, so that the
+    /// hydraulics can account for buoyancy within the CTAH
+    ///
+    /// the nodes are split between the two sections by length
+    pub fn get_vertical_and_horizontal_temperatures(&mut self) 
+        -> (ThermodynamicTemperature, ThermodynamicTemperature) {
+
+        let temperature_vector = self.therminol_array_temperature();
+        let number_of_temperature_nodes = temperature_vector.len();
+
+        let vertical_length_fraction = CTAH_VERTICAL_LENGTH_METERS / 
+            (CTAH_VERTICAL_LENGTH_METERS + CTAH_HORIZONTAL_LENGTH_METERS);
+
+        let vertical_nodes: usize = 
+            ((number_of_temperature_nodes as f64 * vertical_length_fraction)
+            .round() as usize)
+            .clamp(1, number_of_temperature_nodes - 1);
+
+        let average_temperature = |temperatures: &[ThermodynamicTemperature]| {
+            let sum_kelvin: f64 = temperatures.iter()
+                .map(|temperature| temperature.get::<kelvin>())
+                .sum();
+            ThermodynamicTemperature::new::<kelvin>(
+                sum_kelvin / temperatures.len() as f64)
+        };
+
+        return (average_temperature(&temperature_vector[..vertical_nodes]),
+            average_temperature(&temperature_vector[vertical_nodes..]));
+    }
+}
@@ -0,0 +1,312 @@
+use super::CoiledTubeAirHeatExchanger;
+use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::input_structs::GnielinskiData;
+use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::enums::NusseltCorrelation;
+use thermal_hydraulics_rs::fluid_mechanics_lib::churchill_friction_factor;
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use crate::therminol_convection::{floor_at_laminar_nusselt, get_heat_transfer_reynolds,
+    LAMINAR_NUSSELT_FLOOR};
+use uom::si::frequency::hertz;
+use uom::si::ratio::ratio;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::ConstZero;
+
+/// specific heat capacity of air, taken as constant over 
+/// the temperatures seen in the CTAH
+pub const AIR_SPECIFIC_HEAT_CAPACITY_JOULE_PER_KILOGRAM_KELVIN: f64 = 1005.0;
+
+impl CoiledTubeAirHeatExchanger {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrate (and fan speed 
+    /// beforehand)
+    ///
+    /// the mass flowrate is positive going from BT-41 (inlet)
+    /// to BT-43 (outlet)
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate){
+
+        // first let's get all the conductances 
+        let air_to_tube_wall_conductance: ThermalConductance 
+        = self.get_air_tube_wall_nodal_conductance();
+
+        let mut therminol_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        // note, must set mass flowrate first 
+        // otherwise there is by default zero flow through 
+        // the array
+        therminol_array_clone.set_mass_flowrate(mass_flowrate);
+        self.therminol_array.set(therminol_array_clone.clone().into()).unwrap();
+
+        let therminol_to_tube_wall_conductance: ThermalConductance 
+        = self.get_therminol_node_tube_wall_conductance();
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        // lateral connections 
+        {
+            let ambient_temperature_vector: Vec<ThermodynamicTemperature> 
+            = vec![self.air_inlet_temperature; number_of_temperature_nodes];
+
+            let mut tube_wall_clone: SolidColumn = 
+            self.tube_wall.clone().try_into().unwrap();
+
+            let tube_wall_temp_vector: Vec<ThermodynamicTemperature> 
+            = tube_wall_clone.get_temperature_vector().unwrap();
+
+            let fluid_temp_vector: Vec<ThermodynamicTemperature> 
+            = therminol_array_clone.get_temperature_vector().unwrap();
+
+            // tube wall to air interaction
+            tube_wall_clone.lateral_link_new_temperature_vector_avg_conductance(
+                air_to_tube_wall_conductance,
+                ambient_temperature_vector
+            ).unwrap();
+
+            // tube wall to therminol interaction
+            tube_wall_clone.lateral_link_new_temperature_vector_avg_conductance(
+                therminol_to_tube_wall_conductance,
+                fluid_temp_vector
+            ).unwrap();
+
+            therminol_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+                therminol_to_tube_wall_conductance,
+                tube_wall_temp_vector
+            ).unwrap();
+
+            self.therminol_array.set(therminol_array_clone.into()).unwrap();
+
+            self.tube_wall.set(tube_wall_clone.into()).unwrap();
+        }
+
+        // axial connections 
+        self.zero_power_bc_connection();
+    }
+
+    /// the end of each node should have a zero power boundary condition 
+    /// connected to each of them at the bare minimum
+    ///
+    /// the therminol array inlet and outlet are then linked to 
+    /// the rest of the loop with advection interactions
+    #[inline]
+    fn zero_power_bc_connection(&mut self){
+
+        let zero_power: Power = Power::ZERO;
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(zero_power).into();
+
+        let interaction: HeatTransferInteractionType = 
+        HeatTransferInteractionType::UserSpecifiedHeatAddition;
+
+        self.therminol_array.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.therminol_array.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_wall.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_wall.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+    }
+
+    /// fraction of rated fan speed, zero with the fan off
+    #[inline]
+    pub fn get_fan_speed_fraction(&self) -> f64 {
+        return self.fan_speed.get::<hertz>() 
+            / self.rated_fan_speed.get::<hertz>();
+    }
+
+    /// returns the air flowrate across the tube bundle
+    ///
+    /// this scales with fan speed (fan affinity laws), but 
+    /// does not drop below the natural draft flowrate
+    #[inline]
+    pub fn get_air_mass_flowrate(&self) -> MassRate {
+
+        let fan_driven_air_flowrate: MassRate = 
+        self.air_rated_mass_flowrate * self.get_fan_speed_fraction();
+
+        if fan_driven_air_flowrate < self.air_natural_draft_mass_flowrate {
+            return self.air_natural_draft_mass_flowrate;
+        }
+
+        return fan_driven_air_flowrate;
+    }
+
+    /// returns the air side heat transfer coefficient 
+    ///
+    /// the forced convection part scales with Re^0.6, as for 
+    /// crossflow over tube banks, and the air velocity (and 
+    /// hence Re) scales with fan speed
+    ///
+    /// h = h_natural + (h_rated - h_natural) (N/N_rated)^0.6
+    #[inline]
+    pub fn get_air_side_heat_transfer_coeff(&self) -> HeatTransfer {
+
+        let fan_speed_fraction = self.get_fan_speed_fraction();
+
+        return self.air_side_natural_convection_htc 
+            + (self.air_side_rated_htc - self.air_side_natural_convection_htc)
+            * fan_speed_fraction.powf(0.6);
+    }
+
+    /// obtains air to tube wall conductance per node
+    ///
+    /// each node sees an equal share of the air flowrate, which 
+    /// heats up as it crosses the tubes, so the conductance is 
+    /// that of a stream exchanging heat with a constant 
+    /// temperature wall
+    ///
+    /// G = C_air (1 - exp (-hA/C_air))
+    #[inline]
+    pub fn get_air_tube_wall_nodal_conductance(&self) -> ThermalConductance {
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let air_cp = SpecificHeatCapacity::new::<joule_per_kilogram_kelvin>(
+            AIR_SPECIFIC_HEAT_CAPACITY_JOULE_PER_KILOGRAM_KELVIN);
+
+        let air_heat_capacity_rate_per_node: ThermalConductance = 
+        self.get_air_mass_flowrate() * air_cp 
+            / number_of_temperature_nodes as f64;
+
+        let air_side_conductance_per_node: ThermalConductance = 
+        self.get_air_side_heat_transfer_coeff() 
+            * self.air_side_heat_transfer_area 
+            / number_of_temperature_nodes as f64;
+
+        let number_of_transfer_units: f64 = 
+        (air_side_conductance_per_node / air_heat_capacity_rate_per_node)
+            .get::<ratio>();
+
+        return air_heat_capacity_rate_per_node 
+            * (1.0 - (-number_of_transfer_units).exp());
+    }
+
+    /// obtains therminol to tube wall conductance per node
+    ///
+    /// the copper wall conduction resistance is neglected
+    #[inline]
+    pub fn get_therminol_node_tube_wall_conductance(&mut self) 
+        -> ThermalConductance {
+
+        let mut therminol_fluid_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        let mut tube_wall_clone: SolidColumn = 
+        self.tube_wall.clone().try_into().unwrap();
+
+        let tube_wall_temperature: ThermodynamicTemperature 
+        = tube_wall_clone.try_get_bulk_temperature().unwrap();
+
+        let h_to_therminol: HeatTransfer = 
+        get_therminol_tube_heat_transfer_coeff(
+            &mut therminol_fluid_array_clone,
+            tube_wall_temperature);
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let heat_transfer_area_per_node: Area = 
+        self.tube_side_heat_transfer_area / number_of_temperature_nodes as f64;
+
+        return h_to_therminol * heat_transfer_area_per_node;
+    }
+}
+
+/// returns the heat transfer coefficient between therminol 
+/// flowing in a tube (or channel) and its wall
+///
+/// the nusselt number comes from the gnielinski correlation, 
+/// floored at the fully developed laminar value since the flow 
+/// is mostly laminar or transitional
+pub fn get_therminol_tube_heat_transfer_coeff(
+    therminol_array: &mut FluidArray,
+    wall_temperature: ThermodynamicTemperature) -> HeatTransfer {
+
+    let mass_flowrate: MassRate = 
+    therminol_array.get_mass_flowrate();
+
+    let fluid_temperature: ThermodynamicTemperature 
+    = therminol_array.try_get_bulk_temperature().unwrap();
+
+    let fluid_pressure: Pressure 
+    = therminol_array.pressure_control_volume;
+
+    let flow_area: Area = therminol_array
+        .get_cross_sectional_area();
+    let hydraulic_diameter: Length = therminol_array
+        .get_hydraulic_diameter();
+    let tube_length: Length = therminol_array
+        .get_component_length();
+
+    let viscosity: DynamicViscosity = 
+    LiquidMaterial::TherminolVP1.try_get_dynamic_viscosity(
+        fluid_temperature).unwrap();
+
+    let reynolds: Ratio = get_heat_transfer_reynolds(
+        mass_flowrate, flow_area, hydraulic_diameter, viscosity);
+
+    let nusselt_estimate: Ratio = if reynolds.get::<ratio>() < 1.0 {
+        Ratio::new::<ratio>(LAMINAR_NUSSELT_FLOOR)
+    } else {
+
+        // the therminol properties only range from 20C to 180C,
+        // so the wall prandtl number uses a wall temperature 
+        // clamped within that range
+        let bulk_prandtl: Ratio = LiquidMaterial::TherminolVP1
+            .try_get_prandtl_liquid(fluid_temperature, fluid_pressure)
+            .unwrap();
+        let wall_prandtl: Ratio = LiquidMaterial::TherminolVP1
+            .try_get_prandtl_liquid(
+                clamp_to_therminol_range(wall_temperature),
+                fluid_pressure)
+            .unwrap();
+
+        let darcy_friction_factor: f64 = 
+        churchill_friction_factor::darcy(reynolds.get::<ratio>(), 0.0);
+
+        let mut gnielinski_data = GnielinskiData::default();
+        gnielinski_data.reynolds = reynolds;
+        gnielinski_data.prandtl_bulk = bulk_prandtl;
+        gnielinski_data.prandtl_wall = wall_prandtl;
+        gnielinski_data.darcy_friction_factor = 
+            Ratio::new::<ratio>(darcy_friction_factor);
+        gnielinski_data.length_to_diameter = tube_length/hydraulic_diameter;
+
+        let gnielinski_nusselt: Ratio = 
+        NusseltCorrelation::PipeGnielinskiGeneric(gnielinski_data)
+            .try_get().unwrap();
+
+        floor_at_laminar_nusselt(gnielinski_nusselt)
+    };
+
+    let k_fluid_average: ThermalConductivity = 
+    LiquidMaterial::TherminolVP1.try_get_thermal_conductivity(
+        fluid_temperature).unwrap();
+
+    return nusselt_estimate * k_fluid_average / hydraulic_diameter;
+}
+
+// therminol property correlations are only valid from 
+// 20C to 180C
+#[inline]
+fn clamp_to_therminol_range(temperature: ThermodynamicTemperature) 
+    -> ThermodynamicTemperature {
+
+    let lowest_temperature = 
+        ThermodynamicTemperature::new::<degree_celsius>(20.0);
+    let highest_temperature = 
+        ThermodynamicTemperature::new::<degree_celsius>(180.0);
+
+    if temperature < lowest_temperature {
+        return lowest_temperature;
+    }
+    if temperature > highest_temperature {
+        return highest_temperature;
+    }
+    return temperature;
+}
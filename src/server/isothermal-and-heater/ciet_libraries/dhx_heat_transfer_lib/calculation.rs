@@ -0,0 +1,17 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use super::DHXShellAndTubeHeatExchanger;
+
+
+impl DHXShellAndTubeHeatExchanger {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// DHXShellAndTubeHeatExchanger
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.shell_side_therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        self.tube_wall.advance_timestep_mut_self(timestep).unwrap();
+        self.tube_side_therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        
+    }
+}
@@ -0,0 +1,142 @@
+//! The DHX module here represents the thermal side of the DRACS 
+//! heat exchanger (DHX) of CIET, a shell and tube heat exchanger
+//! which passes heat from the primary loop to the DRACS loop
+//!
+//! Hydraulically, the shell side is the DHX shell side (24) 
+//! component in the dhx branch and the tube side is the DHX 
+//! tube side (30) component in the DRACS loop. Here, both are
+//! lumped into one thermal model:
+//!
+//! The shell side is a FluidArray of primary loop therminol, 
+//! flowing down the DHX (top to bottom of the dhx branch)
+//!
+//! The tube side is a FluidArray of DRACS loop therminol, 
+//! flowing up the DHX in counterflow 
+//!
+//! The tube walls (stainless steel) are a SolidColumn between 
+//! them, as with HeaterVersion2Bare
+//!
+//! The shell and tube wall arrays are ordered from the top of
+//! the DHX to the bottom, while the tube side array is ordered 
+//! from the bottom to the top, so the tube side temperatures are 
+//! reversed when linking it to the tube walls
+//!
+//! The outer shell is insulated, its heat loss is neglected
+//!
+//! Geometry follows the 19 tube CIET DHX, the heat transfer 
+//! parameters should be callibrated against DHX data
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use uom::si::area::square_meter;
+use uom::si::ratio::ratio;
+use uom::si::pressure::atmosphere;
+
+/// length of the DHX tube bundle, the same as the DHX shell 
+/// side (24) and DHX tube side (30) components
+pub const DHX_LENGTH_METERS: f64 = 1.18745;
+
+/// represents the DRACS heat exchanger (DHX)
+///
+/// note that the hydraulics of the DHX are calculated in the 
+/// dhx branch (shell side) and DRACS loop (tube side), this 
+/// is only the thermal model
+#[derive(Debug,Clone,PartialEq)]
+pub struct DHXShellAndTubeHeatExchanger {
+
+    inner_nodes: usize,
+
+    /// primary loop therminol, ordered top to bottom
+    pub shell_side_therminol_array: HeatTransferEntity,
+
+    /// tube walls, ordered top to bottom
+    pub tube_wall: HeatTransferEntity,
+
+    /// DRACS loop therminol, ordered bottom to top
+    pub tube_side_therminol_array: HeatTransferEntity,
+
+    /// total outer surface area of the tubes
+    shell_side_heat_transfer_area: Area,
+
+    /// total inner surface area of the tubes
+    tube_side_heat_transfer_area: Area,
+
+}
+
+impl DHXShellAndTubeHeatExchanger {
+
+    /// CIET DHX constructor
+    pub fn new_ciet_dhx(initial_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        let dhx_length = Length::new::<meter>(DHX_LENGTH_METERS);
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+        let dummy_pipe_form_loss = Ratio::new::<ratio>(0.1);
+
+        // shell side flow area and hydraulic diameter follow 
+        // the dhx shell side (24) component, flowing downwards
+        let shell_side_therminol_array: FluidArray = 
+        FluidArray::new_odd_shaped_pipe(
+            dhx_length,
+            Length::new::<meter>(0.00565),
+            Area::new::<square_meter>(0.000943),
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::SteelSS304L,
+            LiquidMaterial::TherminolVP1,
+            dummy_pipe_form_loss,
+            user_specified_inner_nodes,
+            Angle::new::<uom::si::angle::degree>(-90.0)
+        );
+
+        // tube side flow area and hydraulic diameter follow
+        // the dhx tube side (30) component, 19 tubes of 
+        // 0.1800 in (4.572 mm) inner diameter flowing upwards
+        let tube_side_therminol_array: FluidArray = 
+        FluidArray::new_odd_shaped_pipe(
+            dhx_length,
+            Length::new::<meter>(0.004572),
+            Area::new::<square_meter>(0.000312),
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::SteelSS304L,
+            LiquidMaterial::TherminolVP1,
+            dummy_pipe_form_loss,
+            user_specified_inner_nodes,
+            Angle::new::<uom::si::angle::degree>(90.0)
+        );
+
+        // the 19 tubes (6.35 mm outer diameter) are lumped into 
+        // a single steel shell with the same metal cross 
+        // sectional area (thermal inertia)
+        let id = Length::new::<meter>(0.0200);
+        let od = Length::new::<meter>(0.02773);
+
+        let tube_wall_array = 
+        SolidColumn::new_cylindrical_shell(
+            dhx_length,
+            id,
+            od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::SteelSS304L,
+            user_specified_inner_nodes 
+        );
+
+        return Self { inner_nodes: user_specified_inner_nodes,
+            shell_side_therminol_array: shell_side_therminol_array.into(),
+            tube_wall: tube_wall_array.into(),
+            tube_side_therminol_array: tube_side_therminol_array.into(),
+            shell_side_heat_transfer_area: Area::new::<square_meter>(0.450),
+            tube_side_heat_transfer_area: Area::new::<square_meter>(0.324),
+        };
+    }
+}
+
+// these only add methods to DHXShellAndTubeHeatExchanger,
+// so they are kept private to avoid clashing with the 
+// CTAH modules of the same name when glob re-exported
+mod preprocessing;
+
+mod calculation;
+
+mod postprocessing;
@@ -0,0 +1,67 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::pressure::atmosphere;
+use uom::si::power::watt;
+use uom::si::specific_heat_capacity::joule_per_kilogram_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use super::DHXShellAndTubeHeatExchanger;
+
+impl DHXShellAndTubeHeatExchanger {
+
+    pub fn shell_side_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.shell_side_therminol_array.get_temperature_vector().unwrap()
+    }
+
+    pub fn tube_side_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.tube_side_therminol_array.get_temperature_vector().unwrap()
+    }
+
+    /// primary loop therminol temperature leaving the bottom 
+    /// of the DHX shell
+    pub fn get_shell_side_outlet_temperature(&mut self) -> ThermodynamicTemperature {
+        self.shell_side_array_temperature().into_iter().last().unwrap()
+    }
+
+    /// DRACS loop therminol temperature leaving the top of 
+    /// the DHX tubes
+    pub fn get_tube_side_outlet_temperature(&mut self) -> ThermodynamicTemperature {
+        self.tube_side_array_temperature().into_iter().last().unwrap()
+    }
+
+    pub fn get_shell_side_bulk_temperature(&mut self) -> ThermodynamicTemperature {
+        let mut shell_side_array_clone: FluidArray = 
+        self.shell_side_therminol_array.clone().try_into().unwrap();
+
+        return shell_side_array_clone.try_get_bulk_temperature().unwrap();
+    }
+
+    pub fn get_tube_side_bulk_temperature(&mut self) -> ThermodynamicTemperature {
+        let mut tube_side_array_clone: FluidArray = 
+        self.tube_side_therminol_array.clone().try_into().unwrap();
+
+        return tube_side_array_clone.try_get_bulk_temperature().unwrap();
+    }
+
+    /// returns the heat removed from the primary loop given 
+    /// the shell side mass flowrate and inlet temperature
+    pub fn get_heat_removal_rate(&mut self,
+        shell_side_mass_flowrate: MassRate,
+        shell_side_inlet_temperature: ThermodynamicTemperature) -> Power {
+
+        let outlet_temperature = self.get_shell_side_outlet_temperature();
+
+        let therminol_cp: SpecificHeatCapacity = try_get_cp(
+            LiquidMaterial::TherminolVP1.into(),
+            outlet_temperature,
+            Pressure::new::<atmosphere>(1.0)).unwrap();
+
+        let temperature_drop_kelvin: f64 = 
+        shell_side_inlet_temperature.get::<kelvin>() 
+            - outlet_temperature.get::<kelvin>();
+
+        return Power::new::<watt>(
+            shell_side_mass_flowrate.get::<kilogram_per_second>()
+            * therminol_cp.get::<joule_per_kilogram_kelvin>()
+            * temperature_drop_kelvin);
+    }
+}
@@ -0,0 +1,195 @@
+use super::DHXShellAndTubeHeatExchanger;
+use crate::ctah_heat_transfer_lib::get_therminol_tube_heat_transfer_coeff;
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::ConstZero;
+
+impl DHXShellAndTubeHeatExchanger {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrates beforehand
+    ///
+    /// the shell side mass flowrate is the dhx branch flowrate,
+    /// positive going down the DHX, and the tube side mass 
+    /// flowrate is the DRACS loop flowrate, positive going up 
+    /// the DHX
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        shell_side_mass_flowrate: MassRate,
+        tube_side_mass_flowrate: MassRate){
+
+        let mut shell_side_array_clone: FluidArray = 
+        self.shell_side_therminol_array.clone().try_into().unwrap();
+
+        let mut tube_side_array_clone: FluidArray = 
+        self.tube_side_therminol_array.clone().try_into().unwrap();
+
+        // note, must set mass flowrate first 
+        // otherwise there is by default zero flow through 
+        // the array
+        shell_side_array_clone.set_mass_flowrate(shell_side_mass_flowrate);
+        tube_side_array_clone.set_mass_flowrate(tube_side_mass_flowrate);
+
+        self.shell_side_therminol_array.set(
+            shell_side_array_clone.clone().into()).unwrap();
+        self.tube_side_therminol_array.set(
+            tube_side_array_clone.clone().into()).unwrap();
+
+        let shell_side_to_tube_wall_conductance: ThermalConductance 
+        = self.get_shell_side_node_tube_wall_conductance();
+
+        let tube_side_to_tube_wall_conductance: ThermalConductance 
+        = self.get_tube_side_node_tube_wall_conductance();
+
+        // lateral connections 
+        {
+            let mut tube_wall_clone: SolidColumn = 
+            self.tube_wall.clone().try_into().unwrap();
+
+            // top to bottom
+            let tube_wall_temp_vector: Vec<ThermodynamicTemperature> 
+            = tube_wall_clone.get_temperature_vector().unwrap();
+
+            // top to bottom
+            let shell_side_temp_vector: Vec<ThermodynamicTemperature> 
+            = shell_side_array_clone.get_temperature_vector().unwrap();
+
+            // the tube side array runs bottom to top, so its 
+            // temperatures are flipped to line up with the 
+            // tube wall, and vice versa
+            let tube_side_temp_vector_top_to_bottom: Vec<ThermodynamicTemperature> 
+            = tube_side_array_clone.get_temperature_vector().unwrap()
+                .into_iter().rev().collect();
+
+            let tube_wall_temp_vector_bottom_to_top: Vec<ThermodynamicTemperature> 
+            = tube_wall_temp_vector.iter().rev().cloned().collect();
+
+            // tube wall to shell side interaction
+            tube_wall_clone.lateral_link_new_temperature_vector_avg_conductance(
+                shell_side_to_tube_wall_conductance,
+                shell_side_temp_vector
+            ).unwrap();
+
+            shell_side_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+                shell_side_to_tube_wall_conductance,
+                tube_wall_temp_vector
+            ).unwrap();
+
+            // tube wall to tube side interaction
+            tube_wall_clone.lateral_link_new_temperature_vector_avg_conductance(
+                tube_side_to_tube_wall_conductance,
+                tube_side_temp_vector_top_to_bottom
+            ).unwrap();
+
+            tube_side_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+                tube_side_to_tube_wall_conductance,
+                tube_wall_temp_vector_bottom_to_top
+            ).unwrap();
+
+            self.shell_side_therminol_array.set(
+                shell_side_array_clone.into()).unwrap();
+
+            self.tube_side_therminol_array.set(
+                tube_side_array_clone.into()).unwrap();
+
+            self.tube_wall.set(tube_wall_clone.into()).unwrap();
+        }
+
+        // axial connections 
+        self.zero_power_bc_connection();
+    }
+
+    /// the end of each node should have a zero power boundary condition 
+    /// connected to each of them at the bare minimum
+    ///
+    /// the therminol array inlets and outlets are then linked to 
+    /// the rest of the loops with advection interactions
+    #[inline]
+    fn zero_power_bc_connection(&mut self){
+
+        let zero_power: Power = Power::ZERO;
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(zero_power).into();
+
+        let interaction: HeatTransferInteractionType = 
+        HeatTransferInteractionType::UserSpecifiedHeatAddition;
+
+        self.shell_side_therminol_array.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.shell_side_therminol_array.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_side_therminol_array.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_side_therminol_array.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_wall.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.tube_wall.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+    }
+
+    /// obtains shell side therminol to tube wall conductance 
+    /// per node
+    ///
+    /// the shell side is treated as a channel of the shell side
+    /// hydraulic diameter, the steel wall conduction 
+    /// resistance is neglected
+    #[inline]
+    pub fn get_shell_side_node_tube_wall_conductance(&mut self) 
+        -> ThermalConductance {
+
+        let mut shell_side_array_clone: FluidArray = 
+        self.shell_side_therminol_array.clone().try_into().unwrap();
+
+        let mut tube_wall_clone: SolidColumn = 
+        self.tube_wall.clone().try_into().unwrap();
+
+        let tube_wall_temperature: ThermodynamicTemperature 
+        = tube_wall_clone.try_get_bulk_temperature().unwrap();
+
+        let h_to_therminol: HeatTransfer = 
+        get_therminol_tube_heat_transfer_coeff(
+            &mut shell_side_array_clone,
+            tube_wall_temperature);
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let heat_transfer_area_per_node: Area = 
+        self.shell_side_heat_transfer_area / number_of_temperature_nodes as f64;
+
+        return h_to_therminol * heat_transfer_area_per_node;
+    }
+
+    /// obtains tube side therminol to tube wall conductance 
+    /// per node
+    #[inline]
+    pub fn get_tube_side_node_tube_wall_conductance(&mut self) 
+        -> ThermalConductance {
+
+        let mut tube_side_array_clone: FluidArray = 
+        self.tube_side_therminol_array.clone().try_into().unwrap();
+
+        let mut tube_wall_clone: SolidColumn = 
+        self.tube_wall.clone().try_into().unwrap();
+
+        let tube_wall_temperature: ThermodynamicTemperature 
+        = tube_wall_clone.try_get_bulk_temperature().unwrap();
+
+        let h_to_therminol: HeatTransfer = 
+        get_therminol_tube_heat_transfer_coeff(
+            &mut tube_side_array_clone,
+            tube_wall_temperature);
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let heat_transfer_area_per_node: Area = 
+        self.tube_side_heat_transfer_area / number_of_temperature_nodes as f64;
+
+        return h_to_therminol * heat_transfer_area_per_node;
+    }
+}
@@ -0,0 +1,304 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::reciprocal_length::reciprocal_meter;
+
+use crate::{CIETComponent, DRACSLoopDefinition, LoopDefinitionError,
+    FlowSolverError, find_root_with_bracket_expansion};
+
+/// fluid temperatures around the DRACS loop, used for its
+/// natural circulation hydraulics
+///
+/// the hot leg is everything downstream of the DHX and upstream
+/// of the TCHX, the cold leg is everything downstream of the TCHX
+/// and upstream of the DHX
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DRACSLoopTemperatures {
+    /// DHX tube side bulk temperature
+    pub dhx_tube_side: ThermodynamicTemperature,
+    /// DHX tube side outlet temperature
+    pub hot_leg: ThermodynamicTemperature,
+    /// TCHX bulk temperature
+    pub tchx: ThermodynamicTemperature,
+    /// TCHX outlet temperature
+    pub cold_leg: ThermodynamicTemperature,
+}
+
+impl DRACSLoopTemperatures {
+
+    /// every component is at the same temperature, there is
+    /// then no natural circulation
+    pub fn new_uniform(fluid_temp: ThermodynamicTemperature) -> Self {
+        return Self {
+            dhx_tube_side: fluid_temp,
+            hot_leg: fluid_temp,
+            tchx: fluid_temp,
+            cold_leg: fluid_temp,
+        };
+    }
+}
+
+impl Default for DRACSLoopTemperatures {
+    /// the DRACS loop at room temperature (21 C), which is what
+    /// the isothermal components are constructed at
+    fn default() -> Self {
+        return Self::new_uniform(
+            ThermodynamicTemperature::new::<degree_celsius>(21.0));
+    }
+}
+
+/// the part of the DRACS loop a component is in, this decides
+/// which of the DRACSLoopTemperatures it takes
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DRACSLoopSection {
+    DHXTubeSide,
+    HotLeg,
+    TCHX,
+    ColdLeg,
+}
+
+/// the CIET DRACS loop hydraulics
+///
+/// the DRACS loop is a single closed loop with no pump, so
+/// the flowrate comes from the balance between the buoyancy
+/// (hydrostatic) pressure around the loop and the losses,
+/// the momentum equation for the loop is
+///
+/// (sum of L/A) dm/dt = loop pressure change(m)
+///
+/// where loop pressure change(m) is the sum of the pressure
+/// changes (losses and hydrostatics) of every component at
+/// mass flowrate m, which must vanish at steady state
+///
+/// the loop owns its components, so it only needs to be
+/// constructed once, and can then be calculated as many
+/// times as needed, eg. once every timestep
+pub struct DRACSLoop {
+
+    // listed in the direction of positive flow
+    components: Vec<CIETComponent>,
+
+    // which part of the loop each component is in
+    component_sections: Vec<DRACSLoopSection>,
+
+    // flowrate from the last calculation
+    mass_flowrate: MassRate,
+}
+
+impl DRACSLoop {
+
+    /// constructor, returns the DRACS loop with all its
+    /// components at 21C and no flow
+    ///
+    /// the components are those of the default DRACS loop
+    /// definition
+    pub fn new() -> Self {
+
+        return Self::from_definition(&DRACSLoopDefinition::default())
+            .expect("default DRACS loop definition should be valid");
+    }
+
+    /// constructs the DRACS loop from a loop definition
+    pub fn from_definition(
+        loop_definition: &DRACSLoopDefinition) -> Result<Self, LoopDefinitionError> {
+
+        loop_definition.validate()?;
+
+        let components: Vec<CIETComponent> = loop_definition.components.iter()
+            .map(|component_definition| component_definition.build())
+            .collect();
+
+        let component_count = components.len();
+
+        let dhx_index = components.iter()
+            .position(|component|
+                component.get_name() == loop_definition.dhx_tube_side_name)
+            .ok_or(LoopDefinitionError::MissingComponent(
+                    loop_definition.dhx_tube_side_name.clone()))?;
+
+        // going round the loop from the DHX, components are in the
+        // hot leg until the TCHX, and in the cold leg after it
+        let mut component_sections =
+            vec![DRACSLoopSection::DHXTubeSide; component_count];
+        let mut tchx_reached = false;
+
+        for offset in 1..component_count {
+            let index = (dhx_index + offset) % component_count;
+            let component_name = components[index].get_name();
+
+            let is_tchx = loop_definition.tchx_names.iter()
+                .any(|tchx_name| tchx_name == component_name);
+
+            if is_tchx {
+                tchx_reached = true;
+                component_sections[index] = DRACSLoopSection::TCHX;
+            } else if tchx_reached {
+                component_sections[index] = DRACSLoopSection::ColdLeg;
+            } else {
+                component_sections[index] = DRACSLoopSection::HotLeg;
+            }
+        }
+
+        return Ok(Self {
+            components,
+            component_sections,
+            mass_flowrate: MassRate::new::<kilogram_per_second>(0.0),
+        });
+    }
+
+    /// returns the flowrate from the last calculation, positive
+    /// going up through the DHX tube side
+    pub fn get_mass_flowrate(&self) -> MassRate {
+        return self.mass_flowrate;
+    }
+
+    /// puts the flowrate back to zero, this is used when the
+    /// simulation is reset
+    pub fn reset_mass_flowrate(&mut self){
+        self.mass_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+    }
+
+    /// returns the sum of L/A over every component in the loop
+    pub fn get_length_to_area_sum(&self) -> ReciprocalLength {
+        let mut length_to_area_sum = ReciprocalLength::new::<reciprocal_meter>(0.0);
+
+        for component in self.components.iter() {
+            length_to_area_sum += component.get_length_to_area_ratio();
+        }
+
+        return length_to_area_sum;
+    }
+
+    /// returns references to the components in the loop,
+    /// listed in the direction of positive flow
+    pub fn get_fluid_component_vector(&self) -> Vec<&dyn FluidComponent> {
+        return self.components.iter()
+            .map(|component| component.as_fluid_component())
+            .collect();
+    }
+
+    /// sets the fluid temperature of each component according
+    /// to which part of the loop it is in
+    pub fn set_loop_temperatures(&mut self,
+        loop_temperatures: &DRACSLoopTemperatures){
+
+        for (component, section) in self.components.iter_mut()
+            .zip(self.component_sections.iter()) {
+
+            let fluid_temp = match section {
+                DRACSLoopSection::DHXTubeSide => loop_temperatures.dhx_tube_side,
+                DRACSLoopSection::HotLeg => loop_temperatures.hot_leg,
+                DRACSLoopSection::TCHX => loop_temperatures.tchx,
+                DRACSLoopSection::ColdLeg => loop_temperatures.cold_leg,
+            };
+
+            component.set_fluid_temperature(fluid_temp);
+        }
+    }
+
+    /// returns the steady state natural circulation flowrate at
+    /// the fluid temperatures last set on the loop, ie. where the
+    /// pressure change around the loop vanishes
+    ///
+    /// returns an error rather than panicking if the
+    /// flowrate cannot be solved for
+    pub fn try_get_steady_state_mass_flowrate(&self) -> Result<MassRate, FlowSolverError> {
+
+        let loop_pressure_change_root =
+            |mass_flow_kg_per_s: f64| -> f64 {
+
+            let mass_rate =
+                MassRate::new::<kilogram_per_second>(
+                    mass_flow_kg_per_s);
+
+            return self.get_pressure_change(mass_rate).value;
+        };
+
+        let mass_flowrate_kg_per_s =
+            find_root_with_bracket_expansion(
+                loop_pressure_change_root,
+                0.0,
+                1.0)?;
+
+        return Ok(MassRate::new::<kilogram_per_second>(
+                mass_flowrate_kg_per_s));
+    }
+
+    /// advances the loop flowrate by one timestep, accounting
+    /// for the inertia of the fluid
+    ///
+    /// the momentum equation is discretised implicitly (backward
+    /// euler), ie.
+    ///
+    /// (sum of L/A) (m - m_previous)/dt = loop pressure change(m)
+    ///
+    /// so that it is stable for any timestep, as the timestep grows
+    /// this tends to the steady state flowrate
+    ///
+    /// if the solve fails, the flowrate from the last successful
+    /// calculation is kept
+    pub fn calculate_with_inertia(&mut self,
+        timestep: Time) -> Result<MassRate, FlowSolverError> {
+
+        let previous_mass_flowrate = self.mass_flowrate;
+        let length_to_area_sum = self.get_length_to_area_sum();
+
+        let mass_flow_with_inertia_root =
+            |mass_flow_kg_per_s: f64| -> f64 {
+
+            let mass_rate =
+                MassRate::new::<kilogram_per_second>(
+                    mass_flow_kg_per_s);
+
+            let inertial_pressure: Pressure = length_to_area_sum
+                * (mass_rate - previous_mass_flowrate) / timestep;
+
+            return inertial_pressure.value
+                - self.get_pressure_change(mass_rate).value;
+        };
+
+        // the flowrate should not have moved far from the
+        // previous one, so we bracket the root around it
+        let mass_flowrate_kg_per_s =
+            find_root_with_bracket_expansion(
+                mass_flow_with_inertia_root,
+                previous_mass_flowrate.get::<kilogram_per_second>(),
+                1.0)?;
+
+        self.mass_flowrate =
+            MassRate::new::<kilogram_per_second>(mass_flowrate_kg_per_s);
+
+        return Ok(self.mass_flowrate);
+    }
+}
+
+impl FluidComponentCollectionMethods for DRACSLoop {
+
+    /// calculates pressure change around the loop when given
+    /// a mass flowrate
+    fn get_pressure_change(
+        &self,
+        fluid_mass_flowrate: MassRate) -> Pressure{
+        let fluid_component_collection_vector =
+            self.get_fluid_component_vector();
+
+        return <Self as FluidComponentCollectionSeriesAssociatedFunctions>
+            ::calculate_pressure_change_from_mass_flowrate(
+                fluid_mass_flowrate,
+                &fluid_component_collection_vector);
+    }
+
+    /// the pressure change around a closed loop is zero
+    /// at steady state, so this returns the steady state
+    /// flowrate whatever the pressure change given
+    ///
+    /// panics if the flowrate cannot be solved for, use
+    /// try_get_steady_state_mass_flowrate to handle the
+    /// error instead
+    fn get_mass_flowrate_from_pressure_change(
+        &self,
+        _pressure_change: Pressure) -> MassRate{
+
+        return self.try_get_steady_state_mass_flowrate().unwrap();
+    }
+}
+
+impl FluidComponentCollectionSeriesAssociatedFunctions for DRACSLoop {}
@@ -0,0 +1,264 @@
+use std::fmt;
+
+extern crate roots;
+use roots::find_root_brent;
+use roots::Convergency;
+use roots::SearchError;
+
+/// convergence tolerance used for the flow root solvers
+pub const FLOW_SOLVER_TOLERANCE: f64 = 1e-9;
+
+/// maximum number of brent iterations for the flow root solvers
+pub const FLOW_SOLVER_MAX_ITERATIONS: usize = 30;
+
+/// the bracket is doubled in width at most this many times
+/// before the solver gives up
+///
+/// for a ±1 kg/s starting bracket, this allows up to ±1024 kg/s
+/// and for a ±50 kPa starting bracket, up to ±51.2 MPa
+pub const FLOW_SOLVER_MAX_BRACKET_EXPANSIONS: usize = 10;
+
+/// the ways in which a flow root solve can fail
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlowSolverErrorKind {
+    /// no sign change was found even after expanding the bracket
+    NoBracket,
+    /// the root was bracketed but brent's method did not converge
+    /// within the iteration limit
+    NoConvergence,
+    /// the function returned NaN or infinity somewhere
+    /// during the solve
+    NonFiniteResidual,
+}
+
+/// error returned by the flow root solvers
+///
+/// it carries the residual (function value) closest to zero
+/// that the solver saw as well as the number of iterations
+/// used, so that the server can report how badly the solve failed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlowSolverError {
+    pub kind: FlowSolverErrorKind,
+    pub residual: f64,
+    pub iterations: usize,
+}
+
+impl fmt::Display for FlowSolverError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let description = match self.kind {
+            FlowSolverErrorKind::NoBracket =>
+                "root could not be bracketed",
+            FlowSolverErrorKind::NoConvergence =>
+                "root finding did not converge",
+            FlowSolverErrorKind::NonFiniteResidual =>
+                "residual was not finite",
+        };
+        write!(f, "{} (residual: {:e}, iterations: {})",
+            description, self.residual, self.iterations)
+    }
+}
+
+impl std::error::Error for FlowSolverError {}
+
+/// convergency criteria for brent's method which also keeps
+/// track of the residuals and iterations so that a failed
+/// solve can be reported
+struct RecordingConvergency {
+    eps: f64,
+    max_iter: usize,
+    smallest_residual: f64,
+    iterations: usize,
+    non_finite_residual: bool,
+}
+
+impl Convergency<f64> for RecordingConvergency {
+    fn is_root_found(&mut self, y: f64) -> bool {
+
+        // stop straightaway if the function misbehaves,
+        // the caller turns this into an error
+        if !y.is_finite() {
+            self.non_finite_residual = true;
+            return true;
+        }
+
+        if y.abs() < self.smallest_residual.abs() {
+            self.smallest_residual = y;
+        }
+
+        return y.abs() < self.eps;
+    }
+
+    fn is_converged(&mut self, x1: f64, x2: f64) -> bool {
+        return (x1 - x2).abs() < self.eps;
+    }
+
+    fn is_iteration_limit_reached(&mut self, iter: usize) -> bool {
+        self.iterations = iter;
+        return iter >= self.max_iter;
+    }
+}
+
+/// finds the root of a function using brent's method,
+/// starting from the bracket
+/// [centre - initial_half_width, centre + initial_half_width]
+///
+/// if the function does not change sign within the bracket,
+/// the bracket width is doubled until it does (up to
+/// FLOW_SOLVER_MAX_BRACKET_EXPANSIONS times)
+///
+/// this replaces the fixed ±1 kg/s and ±50 kPa brackets which
+/// panicked whenever a large pump pressure was used
+pub fn find_root_with_bracket_expansion<Func>(
+    mut function: Func,
+    centre: f64,
+    initial_half_width: f64) -> Result<f64, FlowSolverError>
+where Func: FnMut(f64) -> f64 {
+
+    let mut half_width = initial_half_width;
+    let mut lower_bound = centre - half_width;
+    let mut upper_bound = centre + half_width;
+    let mut lower_residual = function(lower_bound);
+    let mut upper_residual = function(upper_bound);
+    let mut expansions: usize = 0;
+
+    loop {
+
+        if !lower_residual.is_finite() || !upper_residual.is_finite() {
+            return Err(FlowSolverError {
+                kind: FlowSolverErrorKind::NonFiniteResidual,
+                residual: f64::NAN,
+                iterations: expansions,
+            });
+        }
+
+        // a sign change (or a zero) means the root is bracketed
+        if lower_residual * upper_residual <= 0.0 {
+            break;
+        }
+
+        if expansions >= FLOW_SOLVER_MAX_BRACKET_EXPANSIONS {
+            let residual = if lower_residual.abs() < upper_residual.abs() {
+                lower_residual
+            } else {
+                upper_residual
+            };
+
+            return Err(FlowSolverError {
+                kind: FlowSolverErrorKind::NoBracket,
+                residual,
+                iterations: expansions,
+            });
+        }
+
+        // expand the bracket on both sides
+        half_width = 2.0 * half_width;
+        lower_bound = centre - half_width;
+        upper_bound = centre + half_width;
+        lower_residual = function(lower_bound);
+        upper_residual = function(upper_bound);
+        expansions += 1;
+    }
+
+    let mut convergency = RecordingConvergency {
+        eps: FLOW_SOLVER_TOLERANCE,
+        max_iter: FLOW_SOLVER_MAX_ITERATIONS,
+        smallest_residual: f64::INFINITY,
+        iterations: 0,
+        non_finite_residual: false,
+    };
+
+    let root_result = find_root_brent(
+        upper_bound,
+        lower_bound,
+        &mut function,
+        &mut convergency);
+
+    if convergency.non_finite_residual {
+        return Err(FlowSolverError {
+            kind: FlowSolverErrorKind::NonFiniteResidual,
+            residual: f64::NAN,
+            iterations: convergency.iterations,
+        });
+    }
+
+    match root_result {
+        Ok(root) => return Ok(root),
+        Err(SearchError::NoBracketing) => {
+            return Err(FlowSolverError {
+                kind: FlowSolverErrorKind::NoBracket,
+                residual: convergency.smallest_residual,
+                iterations: convergency.iterations,
+            });
+        },
+        Err(_) => {
+            return Err(FlowSolverError {
+                kind: FlowSolverErrorKind::NoConvergence,
+                residual: convergency.smallest_residual,
+                iterations: convergency.iterations,
+            });
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_outside_the_initial_bracket_is_found_by_expansion() {
+        // ±1 has to be doubled seven times to reach 100
+        let root = find_root_with_bracket_expansion(
+            |x| x - 100.0, 0.0, 1.0).unwrap();
+
+        assert!((root - 100.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn root_inside_the_initial_bracket_is_found() {
+        let root = find_root_with_bracket_expansion(
+            |x| x * x * x - 0.125, 0.0, 1.0).unwrap();
+
+        assert!((root - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn no_sign_change_gives_no_bracket() {
+        let error = find_root_with_bracket_expansion(
+            |x| x * x + 1.0, 0.0, 1.0).unwrap_err();
+
+        assert_eq!(error.kind, FlowSolverErrorKind::NoBracket);
+        assert_eq!(error.iterations, FLOW_SOLVER_MAX_BRACKET_EXPANSIONS);
+        // closest residual to zero is at the bracket ends
+        assert!(error.residual >= 1.0);
+    }
+
+    #[test]
+    fn nan_gives_non_finite_residual() {
+        let error = find_root_with_bracket_expansion(
+            |_| f64::NAN, 0.0, 1.0).unwrap_err();
+
+        assert_eq!(error.kind, FlowSolverErrorKind::NonFiniteResidual);
+    }
+
+    #[test]
+    fn nan_within_the_bracket_gives_non_finite_residual() {
+        // finite at the bracket ends, NaN in between
+        let error = find_root_with_bracket_expansion(
+            |x| if x.abs() < 0.9 { f64::NAN } else { x },
+            0.0, 1.0).unwrap_err();
+
+        assert_eq!(error.kind, FlowSolverErrorKind::NonFiniteResidual);
+    }
+
+    #[test]
+    fn discontinuity_gives_no_convergence() {
+        // a sign change with no root, the residual never gets
+        // below the tolerance and the bracket shrinks too slowly
+        // for the iteration limit
+        let error = find_root_with_bracket_expansion(
+            |x| if x < 0.3 { -1.0 } else { 1.0 }, 0.0, 1.0).unwrap_err();
+
+        assert_eq!(error.kind, FlowSolverErrorKind::NoConvergence);
+        assert_eq!(error.residual.abs(), 1.0);
+    }
+}
@@ -0,0 +1,204 @@
+//! heat transfer coefficients from bare (or insulated) component
+//! surfaces to ambient air
+//!
+//! the heater and structural supports use a single callibrated
+//! heat transfer coefficient to air by default
+//! (20 W/(m^2 K) for the heater, 6 W/(m^2 K) for supports)
+//!
+//! Alternatively, the heat transfer coefficient can be
+//! calculated from the surface temperature every timestep using
+//! the Churchill-Chu natural convection correlations plus a
+//! linearised radiation heat transfer coefficient
+//!
+//! h = h_natural_convection + h_radiation
+//!
+//! h_radiation = emissivity * sigma * (T_s^2 + T_amb^2) (T_s + T_amb)
+//!
+//! Air properties are evaluated at the film temperature
+//! (average of surface and ambient temperature) at 1 atm
+use std::fmt;
+use std::str::FromStr;
+
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::heat_transfer::watt_per_square_meter_kelvin;
+use uom::si::ratio::ratio;
+use uom::si::thermodynamic_temperature::kelvin;
+
+/// Stefan-Boltzmann constant in W/(m^2 K^4)
+const STEFAN_BOLTZMANN_CONSTANT: f64 = 5.670374419e-8;
+
+/// default emissivity of oxidised stainless steel surfaces,
+/// used if the user does not specify one
+pub const DEFAULT_SURFACE_EMISSIVITY: f64 = 0.6;
+
+/// how the heat transfer coefficient to air is obtained
+#[derive(Debug,Clone,Copy,PartialEq,Default)]
+pub enum AirHeatTransferModel {
+    /// constant callibrated heat transfer coefficient as given
+    /// in the component constructors
+    #[default]
+    Callibrated,
+    /// Churchill-Chu natural convection plus linearised
+    /// radiation, recalculated from the surface temperature
+    /// every timestep
+    NaturalConvectionAndRadiation {
+        emissivity: Ratio,
+    },
+}
+
+impl FromStr for AirHeatTransferModel {
+    type Err = String;
+
+    /// parses either "callibrated" or "natural_convection",
+    /// the latter uses the default surface emissivity
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "callibrated" => Ok(Self::Callibrated),
+            "natural_convection" => Ok(Self::NaturalConvectionAndRadiation {
+                emissivity: Ratio::new::<ratio>(DEFAULT_SURFACE_EMISSIVITY)
+            }),
+            other => Err(format!(
+                "unknown air heat transfer model {:?}, expected \
+                callibrated or natural_convection", other)),
+        }
+    }
+}
+
+impl fmt::Display for AirHeatTransferModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Callibrated => write!(f, "callibrated"),
+            Self::NaturalConvectionAndRadiation { emissivity } =>
+                write!(f, "natural_convection (emissivity {})",
+                    emissivity.get::<ratio>()),
+        }
+    }
+}
+
+/// orientation of a cylindrical surface losing heat to air,
+/// this determines which Churchill-Chu correlation is used
+#[derive(Debug,Clone,Copy,PartialEq,Eq)]
+pub enum CylinderOrientation {
+    /// vertical cylinder, treated as a vertical plate
+    /// with the cylinder height as the characteristic length
+    Vertical,
+    /// horizontal cylinder with the diameter as the
+    /// characteristic length
+    Horizontal,
+}
+
+/// returns the natural convection plus radiation heat transfer
+/// coefficient from a cylindrical surface to air
+///
+/// characteristic length is the height for vertical cylinders
+/// and the outer diameter for horizontal cylinders
+pub fn get_natural_convection_and_radiation_htc(
+    surface_temperature: ThermodynamicTemperature,
+    ambient_temperature: ThermodynamicTemperature,
+    orientation: CylinderOrientation,
+    characteristic_length: Length,
+    emissivity: Ratio) -> HeatTransfer {
+
+    get_churchill_chu_natural_convection_htc(
+        surface_temperature,
+        ambient_temperature,
+        orientation,
+        characteristic_length)
+    + get_linearised_radiation_htc(
+        surface_temperature,
+        ambient_temperature,
+        emissivity)
+}
+
+/// Churchill-Chu natural convection heat transfer coefficient
+///
+/// vertical plate (and vertical cylinder):
+/// Nu_L = {0.825 + 0.387 Ra_L^(1/6) / [1 + (0.492/Pr)^(9/16)]^(8/27)}^2
+///
+/// horizontal cylinder:
+/// Nu_D = {0.60 + 0.387 Ra_D^(1/6) / [1 + (0.559/Pr)^(9/16)]^(8/27)}^2
+pub fn get_churchill_chu_natural_convection_htc(
+    surface_temperature: ThermodynamicTemperature,
+    ambient_temperature: ThermodynamicTemperature,
+    orientation: CylinderOrientation,
+    characteristic_length: Length) -> HeatTransfer {
+
+    let surface_temp_kelvin = surface_temperature.get::<kelvin>();
+    let ambient_temp_kelvin = ambient_temperature.get::<kelvin>();
+    let film_temp_kelvin = 0.5 * (surface_temp_kelvin + ambient_temp_kelvin);
+    let length_meters = characteristic_length.get::<meter>();
+
+    let (thermal_conductivity, kinematic_viscosity, prandtl) =
+    get_air_properties(film_temp_kelvin);
+
+    let thermal_diffusivity = kinematic_viscosity / prandtl;
+
+    // ideal gas, so the thermal expansion coefficient is 1/T
+    let expansion_coefficient = 1.0 / film_temp_kelvin;
+    let gravity = 9.81;
+
+    let rayleigh: f64 = gravity * expansion_coefficient
+        * (surface_temp_kelvin - ambient_temp_kelvin).abs()
+        * length_meters.powi(3)
+        / (kinematic_viscosity * thermal_diffusivity);
+
+    let (leading_constant, prandtl_constant) = match orientation {
+        CylinderOrientation::Vertical => (0.825, 0.492),
+        CylinderOrientation::Horizontal => (0.60, 0.559),
+    };
+
+    let prandtl_function: f64 = (1.0 + (prandtl_constant/prandtl)
+        .powf(9.0/16.0)).powf(8.0/27.0);
+
+    let nusselt: f64 = (leading_constant
+        + 0.387 * rayleigh.powf(1.0/6.0) / prandtl_function).powi(2);
+
+    HeatTransfer::new::<watt_per_square_meter_kelvin>(
+        nusselt * thermal_conductivity / length_meters)
+}
+
+/// linearised radiation heat transfer coefficient from a
+/// surface to large surroundings at ambient temperature
+///
+/// h_radiation = emissivity * sigma * (T_s^2 + T_amb^2) (T_s + T_amb)
+pub fn get_linearised_radiation_htc(
+    surface_temperature: ThermodynamicTemperature,
+    ambient_temperature: ThermodynamicTemperature,
+    emissivity: Ratio) -> HeatTransfer {
+
+    let surface_temp_kelvin = surface_temperature.get::<kelvin>();
+    let ambient_temp_kelvin = ambient_temperature.get::<kelvin>();
+
+    let h_radiation: f64 = emissivity.get::<ratio>()
+        * STEFAN_BOLTZMANN_CONSTANT
+        * (surface_temp_kelvin.powi(2) + ambient_temp_kelvin.powi(2))
+        * (surface_temp_kelvin + ambient_temp_kelvin);
+
+    HeatTransfer::new::<watt_per_square_meter_kelvin>(h_radiation)
+}
+
+/// dry air properties at 1 atm,
+/// returns thermal conductivity (W/(m K)), kinematic viscosity (m^2/s)
+/// and prandtl number
+///
+/// viscosity is from Sutherland's law, thermal conductivity
+/// is a polynomial fit valid from about 250 K to 1000 K,
+/// and density is from the ideal gas law
+fn get_air_properties(temperature_kelvin: f64) -> (f64, f64, f64) {
+
+    let t = temperature_kelvin;
+
+    let dynamic_viscosity: f64 = 1.716e-5 * (t/273.15).powf(1.5)
+        * (273.15 + 110.4) / (t + 110.4);
+
+    let thermal_conductivity: f64 = 1.5207e-11 * t.powi(3)
+        - 4.8574e-8 * t.powi(2) + 1.0184e-4 * t - 3.9333e-4;
+
+    let density: f64 = 101325.0 / (287.05 * t);
+    let heat_capacity: f64 = 1007.0;
+
+    let kinematic_viscosity = dynamic_viscosity / density;
+    let prandtl = heat_capacity * dynamic_viscosity / thermal_conductivity;
+
+    (thermal_conductivity, kinematic_viscosity, prandtl)
+}
@@ -0,0 +1,243 @@
+//! axial power shapes for the heated section
+//!
+//! by default, the heater power is spread evenly over the
+//! temperature nodes of the heated section. For FHR prototypic
+//! studies, the power can instead follow a cosine, chopped
+//! cosine or arbitrary (tabulated) axial profile
+//!
+//! the power shape is turned into power fractions for each
+//! node, which always sum to one, so the total heater power
+//! is unchanged
+use std::fmt;
+use std::str::FromStr;
+
+use ndarray::*;
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::ratio::ratio;
+
+/// default ratio of the extrapolated length to the heated
+/// length for the chopped cosine shape
+pub const DEFAULT_CHOPPED_COSINE_EXTRAPOLATION_RATIO: f64 = 1.2;
+
+/// the axial power shape over the heated section,
+/// from the heater inlet (bottom) to the outlet (top)
+#[derive(Debug,Clone,PartialEq,Default)]
+pub enum AxialPowerShape {
+    /// power is spread evenly over each node
+    #[default]
+    Uniform,
+    /// cosine shape which goes to zero at both ends of the
+    /// heated length
+    Cosine,
+    /// cosine shape over an extrapolated length longer than
+    /// the heated length, so the power is non zero at both ends
+    ChoppedCosine {
+        extrapolated_length_ratio: Ratio,
+    },
+    /// relative powers spaced evenly from the inlet to the outlet,
+    /// these are linearly interpolated onto the nodes if the
+    /// number of values differs from the number of nodes
+    Table(Vec<f64>),
+}
+
+impl AxialPowerShape {
+
+    /// constructs a tabulated power shape,
+    /// the relative powers must not be negative and must not
+    /// all be zero
+    pub fn new_table(relative_powers: Vec<f64>) -> Result<Self, String> {
+
+        if relative_powers.is_empty() {
+            return Err("axial power table is empty".to_string());
+        }
+
+        if relative_powers.iter().any(|value| !value.is_finite() || *value < 0.0) {
+            return Err(format!(
+                "axial power table values must be finite and non negative, got {:?}",
+                relative_powers));
+        }
+
+        if relative_powers.iter().sum::<f64>() <= 0.0 {
+            return Err("axial power table values are all zero".to_string());
+        }
+
+        Ok(Self::Table(relative_powers))
+    }
+
+    /// returns the fraction of heater power going into each
+    /// node, these sum to one
+    pub fn get_power_fractions(&self,
+        number_of_temperature_nodes: usize) -> Array1<f64> {
+
+        let n = number_of_temperature_nodes;
+
+        // node midpoints as a fraction of the heated length
+        let node_midpoints: Vec<f64> = (0..n).map(
+            |index| (index as f64 + 0.5) / n as f64
+        ).collect();
+
+        let relative_powers: Vec<f64> = match self {
+            Self::Uniform => vec![1.0; n],
+            Self::Cosine => node_midpoints.iter().map(
+                |z| (PI * (z - 0.5)).cos()
+            ).collect(),
+            Self::ChoppedCosine { extrapolated_length_ratio } => {
+                let extrapolation = extrapolated_length_ratio.get::<ratio>();
+                node_midpoints.iter().map(
+                    |z| (PI * (z - 0.5) / extrapolation).cos().max(0.0)
+                ).collect()
+            },
+            Self::Table(table) => node_midpoints.iter().map(
+                |z| interpolate_table(table, *z)
+            ).collect(),
+        };
+
+        let total: f64 = relative_powers.iter().sum();
+
+        // if the shape has no power in any node (eg. a table
+        // coarser than the nodes with zeros), fall back to uniform
+        if total <= 0.0 {
+            return Array1::from_elem(n, 1.0 / n as f64);
+        }
+
+        Array1::from_vec(relative_powers) / total
+    }
+}
+
+/// linearly interpolates a table of values spaced evenly over
+/// the heated length, the values are at the midpoints of
+/// each table segment
+fn interpolate_table(table: &[f64], z: f64) -> f64 {
+
+    let table_length = table.len();
+
+    if table_length == 1 {
+        return table[0];
+    }
+
+    // position in units of table segments,
+    // measured from the first midpoint
+    let position: f64 = (z * table_length as f64 - 0.5)
+        .clamp(0.0, (table_length - 1) as f64);
+
+    let lower_index = position.floor() as usize;
+    let upper_index = (lower_index + 1).min(table_length - 1);
+    let weight = position - lower_index as f64;
+
+    table[lower_index] * (1.0 - weight) + table[upper_index] * weight
+}
+
+impl FromStr for AxialPowerShape {
+    type Err = String;
+
+    /// parses uniform, cosine or chopped_cosine,
+    /// the chopped cosine uses the default extrapolation ratio
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "uniform" => Ok(Self::Uniform),
+            "cosine" => Ok(Self::Cosine),
+            "chopped_cosine" => Ok(Self::ChoppedCosine {
+                extrapolated_length_ratio: Ratio::new::<ratio>(
+                    DEFAULT_CHOPPED_COSINE_EXTRAPOLATION_RATIO)
+            }),
+            other => Err(format!(
+                "unknown axial power shape {:?}, expected \
+                uniform, cosine or chopped_cosine", other)),
+        }
+    }
+}
+
+impl fmt::Display for AxialPowerShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Uniform => write!(f, "uniform"),
+            Self::Cosine => write!(f, "cosine"),
+            Self::ChoppedCosine { extrapolated_length_ratio } =>
+                write!(f, "chopped_cosine (extrapolated length ratio {})",
+                    extrapolated_length_ratio.get::<ratio>()),
+            Self::Table(table) => write!(f, "table {:?}", table),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn all_shapes() -> Vec<AxialPowerShape> {
+        vec![
+            AxialPowerShape::Uniform,
+            AxialPowerShape::Cosine,
+            "chopped_cosine".parse().unwrap(),
+            AxialPowerShape::new_table(vec![1.0, 3.0, 2.0]).unwrap(),
+        ]
+    }
+
+    #[test]
+    fn power_fractions_sum_to_one() {
+        for shape in all_shapes() {
+            for number_of_temperature_nodes in [1, 2, 5, 8] {
+                let power_fractions = shape.get_power_fractions(
+                    number_of_temperature_nodes);
+
+                assert_eq!(power_fractions.len(), number_of_temperature_nodes);
+                assert!((power_fractions.sum() - 1.0).abs() < 1e-12,
+                    "{} over {} nodes", shape, number_of_temperature_nodes);
+                assert!(power_fractions.iter().all(|fraction| *fraction >= 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn uniform_shape_spreads_power_evenly() {
+        let power_fractions = AxialPowerShape::Uniform.get_power_fractions(4);
+
+        assert!(power_fractions.iter().all(|fraction| (fraction - 0.25).abs() < 1e-12));
+    }
+
+    #[test]
+    fn cosine_shapes_peak_in_the_middle() {
+        let cosine_fractions = AxialPowerShape::Cosine.get_power_fractions(8);
+        let chopped_cosine_fractions = AxialPowerShape::ChoppedCosine {
+            extrapolated_length_ratio: Ratio::new::<ratio>(
+                DEFAULT_CHOPPED_COSINE_EXTRAPOLATION_RATIO)
+        }.get_power_fractions(8);
+
+        for power_fractions in [&cosine_fractions, &chopped_cosine_fractions] {
+            // symmetric about the middle, highest in the middle
+            assert!((power_fractions[0] - power_fractions[7]).abs() < 1e-12);
+            assert!((power_fractions[3] - power_fractions[4]).abs() < 1e-12);
+            assert!(power_fractions[3] > power_fractions[0]);
+        }
+
+        // the chopped cosine is flatter
+        assert!(chopped_cosine_fractions[0] > cosine_fractions[0]);
+    }
+
+    #[test]
+    fn table_with_one_value_per_node_is_used_directly() {
+        let table = AxialPowerShape::new_table(vec![1.0, 3.0, 2.0, 2.0]).unwrap();
+        let power_fractions = table.get_power_fractions(4);
+
+        assert!((power_fractions[0] - 0.125).abs() < 1e-12);
+        assert!((power_fractions[1] - 0.375).abs() < 1e-12);
+        assert!((power_fractions[2] - 0.25).abs() < 1e-12);
+    }
+
+    #[test]
+    fn unusable_tables_are_rejected() {
+        assert!(AxialPowerShape::new_table(vec![]).is_err());
+        assert!(AxialPowerShape::new_table(vec![1.0, -1.0]).is_err());
+        assert!(AxialPowerShape::new_table(vec![1.0, f64::NAN]).is_err());
+        assert!(AxialPowerShape::new_table(vec![0.0, 0.0]).is_err());
+    }
+
+    #[test]
+    fn shapes_are_parsed_by_name() {
+        assert_eq!(" Cosine ".parse::<AxialPowerShape>(),
+            Ok(AxialPowerShape::Cosine));
+        assert_eq!("uniform".parse::<AxialPowerShape>(),
+            Ok(AxialPowerShape::Uniform));
+        assert!("flat".parse::<AxialPowerShape>().is_err());
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+use std::str::FromStr;
+
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use super::{HeaterVersion2Bare, HeaterVersion2Insulated, 
+    HeaterVersion1Bare, HeaterVersion1Insulated, AxialPowerShape};
+
+/// selects which heated section model the server runs with,
+/// this is chosen once at server startup
+#[derive(Debug,Clone,Copy,PartialEq,Eq,Default)]
+pub enum HeaterVersionSelection {
+    /// heater version 2 (twisted tape) without insulation,
+    /// this is the default
+    #[default]
+    Version2Bare,
+    /// heater version 2 (twisted tape) with fiberglass insulation,
+    /// as it was before the insulation burnt off
+    Version2Insulated,
+    /// heater version 1 (annular) without insulation
+    Version1Bare,
+    /// heater version 1 (annular) with fiberglass insulation
+    Version1Insulated,
+}
+
+impl FromStr for HeaterVersionSelection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "v2_bare" => Ok(Self::Version2Bare),
+            "v2_insulated" => Ok(Self::Version2Insulated),
+            "v1_bare" => Ok(Self::Version1Bare),
+            "v1_insulated" => Ok(Self::Version1Insulated),
+            other => Err(format!(
+                "unknown heater version {:?}, expected one of \
+                v2_bare, v2_insulated, v1_bare or v1_insulated", other)),
+        }
+    }
+}
+
+impl fmt::Display for HeaterVersionSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Version2Bare => "v2_bare",
+            Self::Version2Insulated => "v2_insulated",
+            Self::Version1Bare => "v1_bare",
+            Self::Version1Insulated => "v1_insulated",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// the heated section of the CIET heater (between the heater
+/// top and bottom heads), which may be any of the heater
+/// thermal models
+///
+/// all of them share the same
+/// lateral_and_miscellaneous_connections and advance_timestep
+/// API, so the server does not need to know which is running
+#[derive(Debug,Clone,PartialEq)]
+pub enum HeatedSection {
+    Version2Bare(HeaterVersion2Bare),
+    Version2Insulated(HeaterVersion2Insulated),
+    Version1Bare(HeaterVersion1Bare),
+    Version1Insulated(HeaterVersion1Insulated),
+}
+
+impl HeatedSection {
+
+    /// constructs the heated section for the selected heater version
+    /// using the default heat transfer coefficients to air for
+    /// each model
+    pub fn new(heater_version: HeaterVersionSelection,
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        match heater_version {
+            HeaterVersionSelection::Version2Bare =>
+                Self::Version2Bare(HeaterVersion2Bare::new_dewet_model(
+                    initial_temperature,
+                    ambient_temperature,
+                    user_specified_inner_nodes)),
+            HeaterVersionSelection::Version2Insulated =>
+                Self::Version2Insulated(
+                    HeaterVersion2Insulated::new_insulated_heater(
+                        initial_temperature,
+                        ambient_temperature,
+                        user_specified_inner_nodes)),
+            HeaterVersionSelection::Version1Bare =>
+                Self::Version1Bare(HeaterVersion1Bare::new_bare_heater(
+                    initial_temperature,
+                    ambient_temperature,
+                    user_specified_inner_nodes)),
+            HeaterVersionSelection::Version1Insulated =>
+                Self::Version1Insulated(
+                    HeaterVersion1Insulated::new_insulated_heater(
+                        initial_temperature,
+                        ambient_temperature,
+                        user_specified_inner_nodes)),
+        }
+    }
+
+    /// returns the therminol array of the heated section
+    pub fn therminol_array(&mut self) -> &mut HeatTransferEntity {
+        match self {
+            Self::Version2Bare(heater) => &mut heater.therminol_array,
+            Self::Version2Insulated(heater) => &mut heater.heater_v2.therminol_array,
+            Self::Version1Bare(heater) => &mut heater.therminol_array,
+            Self::Version1Insulated(heater) => &mut heater.therminol_array,
+        }
+    }
+
+    /// returns the heated steel shell of the heated section
+    pub fn steel_shell(&mut self) -> &mut HeatTransferEntity {
+        match self {
+            Self::Version2Bare(heater) => &mut heater.steel_shell,
+            Self::Version2Insulated(heater) => &mut heater.heater_v2.steel_shell,
+            Self::Version1Bare(heater) => &mut heater.steel_shell,
+            Self::Version1Insulated(heater) => &mut heater.steel_shell,
+        }
+    }
+
+    /// returns the twisted tape for heater version 2 (bare or insulated)
+    ///
+    /// heater version 1 has no twisted tape, its inner tube
+    /// is not connected to the heater heads
+    pub fn twisted_tape_interior(&mut self) -> Option<&mut HeatTransferEntity> {
+        match self {
+            Self::Version2Bare(heater) => Some(&mut heater.twisted_tape_interior),
+            Self::Version2Insulated(heater) => 
+                Some(&mut heater.heater_v2.twisted_tape_interior),
+            Self::Version1Bare(_) => None,
+            Self::Version1Insulated(_) => None,
+        }
+    }
+
+    /// used to connect the arrays laterally
+    /// you'll need to set the mass flowrate and heater power
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate,
+        heater_steady_state_power: Power){
+        match self {
+            Self::Version2Bare(heater) => heater
+                .lateral_and_miscellaneous_connections(
+                    mass_flowrate, heater_steady_state_power),
+            Self::Version2Insulated(heater) => heater
+                .lateral_and_miscellaneous_connections(
+                    mass_flowrate, heater_steady_state_power),
+            Self::Version1Bare(heater) => heater
+                .lateral_and_miscellaneous_connections(
+                    mass_flowrate, heater_steady_state_power),
+            Self::Version1Insulated(heater) => heater
+                .lateral_and_miscellaneous_connections(
+                    mass_flowrate, heater_steady_state_power),
+        }
+    }
+
+    /// returns the axial power shape of the heated section
+    pub fn axial_power_shape(&self) -> &AxialPowerShape {
+        match self {
+            Self::Version2Bare(heater) => &heater.axial_power_shape,
+            Self::Version2Insulated(heater) => &heater.heater_v2.axial_power_shape,
+            Self::Version1Bare(heater) => &heater.axial_power_shape,
+            Self::Version1Insulated(heater) => &heater.axial_power_shape,
+        }
+    }
+
+    /// sets the axial power shape of the heated section
+    pub fn set_axial_power_shape(&mut self, axial_power_shape: AxialPowerShape) {
+        match self {
+            Self::Version2Bare(heater) => 
+                heater.axial_power_shape = axial_power_shape,
+            Self::Version2Insulated(heater) => 
+                heater.heater_v2.axial_power_shape = axial_power_shape,
+            Self::Version1Bare(heater) => 
+                heater.axial_power_shape = axial_power_shape,
+            Self::Version1Insulated(heater) => 
+                heater.axial_power_shape = axial_power_shape,
+        }
+    }
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// outer surface temperature using natural convection and 
+    /// radiation
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+        match self {
+            Self::Version2Bare(heater) => 
+                heater.update_heat_transfer_to_air(emissivity),
+            Self::Version2Insulated(heater) => 
+                heater.update_heat_transfer_to_air(emissivity),
+            Self::Version1Bare(heater) => 
+                heater.update_heat_transfer_to_air(emissivity),
+            Self::Version1Insulated(heater) => 
+                heater.update_heat_transfer_to_air(emissivity),
+        }
+    }
+
+    /// advances timestep for each HeatTransferEntity within the
+    /// heated section
+    #[inline]
+    pub fn advance_timestep(&mut self, timestep: Time) {
+        match self {
+            Self::Version2Bare(heater) => heater.advance_timestep(timestep),
+            Self::Version2Insulated(heater) => heater.advance_timestep(timestep),
+            Self::Version1Bare(heater) => heater.advance_timestep(timestep),
+            Self::Version1Insulated(heater) => heater.advance_timestep(timestep),
+        }
+    }
+}
@@ -6,9 +6,11 @@ use thermal_hydraulics_rs::heat_transfer_lib::control_volume_calculations::commo
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::input_structs::NusseltPrandtlReynoldsData;
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::enums::NusseltCorrelation;
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use crate::therminol_convection::{floor_at_laminar_nusselt, get_heat_transfer_reynolds};
 use uom::ConstZero;
 use uom::si::pressure::atmosphere;
 use ndarray::*;
+use crate::heater::air_heat_transfer::{get_natural_convection_and_radiation_htc, CylinderOrientation};
 
 impl HeaterTopBottomHead {
 
@@ -323,9 +325,11 @@ impl HeaterTopBottomHead {
             heater_prandtl_reynolds_data
         );
 
-        let nusselt_estimate: Ratio = 
+        let mut nusselt_estimate: Ratio = 
         heater_nusselt_correlation.try_get().unwrap();
 
+        nusselt_estimate = floor_at_laminar_nusselt(nusselt_estimate);
+
         // now we can get the heat transfer coeff, 
 
         let h: HeatTransfer;
@@ -417,9 +421,8 @@ impl HeaterTopBottomHead {
         //
         // but for now, I'm going to use Re and Nu using hydraulic diameter 
         // and live with it for the time being
-        //
-        let reynolds: Ratio = 
-        mass_flowrate/flow_area*hydraulic_diameter / viscosity;
+        let reynolds: Ratio = get_heat_transfer_reynolds(
+            mass_flowrate, flow_area, hydraulic_diameter, viscosity);
 
         reynolds
 
@@ -541,9 +544,8 @@ impl HeaterTopBottomHead {
         //
         // but for now, I'm going to use Re and Nu using hydraulic diameter 
         // and live with it for the time being
-        //
-        let reynolds: Ratio = 
-        mass_flowrate/flow_area*hydraulic_diameter / viscosity;
+        let reynolds: Ratio = get_heat_transfer_reynolds(
+            mass_flowrate, flow_area, hydraulic_diameter, viscosity);
 
         // need to get prandtl number of fluid 
         // so I need fluid temperature 
@@ -570,13 +572,15 @@ impl HeaterTopBottomHead {
         // with Pr and Re, get nusselt estimate 
         //
 
-        let nusselt_estimate: Ratio = 
+        let mut nusselt_estimate: Ratio = 
         therminol_fluid_array_clone.get_nusselt(
             reynolds,
             fluid_prandtl,
             twisted_tape_wall_prandtl,
         ).unwrap();
 
+        nusselt_estimate = floor_at_laminar_nusselt(nusselt_estimate);
+
         // with nusselt estimate done, (I didn't convert the 
         // hydraulic diameter to an equivalent particle diameter)
         // Now I can get a heat transfer coeff 
@@ -633,6 +637,31 @@ impl HeaterTopBottomHead {
         return join_handle;
 
     }
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// steel shell bulk temperature using Churchill-Chu natural convection 
+    /// (vertical cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        steel_shell_clone.try_get_bulk_temperature().unwrap();
+
+        let head_height: Length = steel_shell_clone.get_component_length();
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Vertical,
+            head_height,
+            emissivity);
+    }
 }
 
 
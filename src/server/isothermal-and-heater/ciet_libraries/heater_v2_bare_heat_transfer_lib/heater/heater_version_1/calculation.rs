@@ -0,0 +1,29 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use super::{HeaterVersion1Bare, HeaterVersion1Insulated};
+
+impl HeaterVersion1Bare {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// HeaterVersion1Bare
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        self.steel_shell.advance_timestep_mut_self(timestep).unwrap();
+        self.inner_tube.advance_timestep_mut_self(timestep).unwrap();
+    }
+}
+
+impl HeaterVersion1Insulated {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// HeaterVersion1Insulated
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        self.steel_shell.advance_timestep_mut_self(timestep).unwrap();
+        self.inner_tube.advance_timestep_mut_self(timestep).unwrap();
+        self.insulation_array.advance_timestep_mut_self(timestep).unwrap();
+    }
+}
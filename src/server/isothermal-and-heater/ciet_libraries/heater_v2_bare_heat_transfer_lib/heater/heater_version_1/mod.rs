@@ -0,0 +1,276 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use uom::si::area::square_meter;
+use uom::si::heat_transfer::watt_per_square_meter_kelvin;
+use uom::si::ratio::ratio;
+use uom::si::pressure::atmosphere;
+
+use super::AxialPowerShape;
+
+/// heated length of heater version 1, the same as heater version 2
+pub const HEATER_V1_HEATED_LENGTH_METERS: f64 = 1.6383;
+
+/// flow area of the heater version 1 annulus
+pub const HEATER_V1_FLOW_AREA_SQUARE_METERS: f64 = 3.64e-4;
+
+/// hydraulic diameter of the heater version 1 annulus
+pub const HEATER_V1_HYDRAULIC_DIAMETER_METERS: f64 = 6.60e-3;
+
+/// inner diameter of the heated outer tube, the same as 
+/// heater version 2
+pub const HEATER_V1_SHELL_ID_METERS: f64 = 0.0381;
+
+/// outer diameter of the heated outer tube
+pub const HEATER_V1_SHELL_OD_METERS: f64 = 0.04;
+
+/// outer diameter of the unheated inner tube, 
+/// this is the shell inner diameter less the hydraulic 
+/// diameter (for an annulus, D_h = D_o - D_i)
+pub const HEATER_V1_INNER_TUBE_OD_METERS: f64 = 
+    HEATER_V1_SHELL_ID_METERS - HEATER_V1_HYDRAULIC_DIAMETER_METERS;
+
+/// inner diameter of the unheated inner tube, 
+/// taken as a 16 gauge (1.65 mm) wall
+pub const HEATER_V1_INNER_TUBE_ID_METERS: f64 = 
+    HEATER_V1_INNER_TUBE_OD_METERS - 2.0 * 1.65e-3;
+
+/// thickness of fiberglass insulation around the insulated heater
+pub const HEATER_V1_INSULATION_THICKNESS_METERS: f64 = 0.0508;
+
+/// represents heater version 1 without insulation 
+///
+/// Heater version 1 is the original annular heater of CIET,
+/// therminol flows up the annulus between the heated outer 
+/// tube (steel shell) and an unheated inner tube
+///
+/// It was replaced by heater version 2 (with the twisted tape 
+/// insert), but a lot of the legacy CIET data was taken with 
+/// this heater
+///
+/// note that it only contains the heated section, not the top nor 
+/// bottom heads
+#[derive(Debug,Clone,PartialEq)]
+pub struct HeaterVersion1Bare {
+
+    inner_nodes: usize,
+
+    pub inner_tube: HeatTransferEntity,
+
+    pub steel_shell: HeatTransferEntity,
+
+    pub therminol_array: HeatTransferEntity,
+
+    pub ambient_temperature: ThermodynamicTemperature,
+
+    pub heat_transfer_to_air: HeatTransfer,
+
+    /// axial shape of the heater power, uniform by default
+    pub axial_power_shape: AxialPowerShape,
+}
+
+/// represents heater version 1 with its fiberglass insulation,
+/// which is how it was run for most of the legacy CIET data 
+///
+/// the heat transfer to air is from the insulation outer 
+/// surface rather than the steel shell
+///
+/// note that it only contains the heated section, not the top nor 
+/// bottom heads
+#[derive(Debug,Clone,PartialEq)]
+pub struct HeaterVersion1Insulated {
+
+    inner_nodes: usize,
+
+    pub inner_tube: HeatTransferEntity,
+
+    pub steel_shell: HeatTransferEntity,
+
+    pub insulation_array: HeatTransferEntity,
+
+    pub therminol_array: HeatTransferEntity,
+
+    pub ambient_temperature: ThermodynamicTemperature,
+
+    pub heat_transfer_to_air: HeatTransfer,
+
+    /// axial shape of the heater power, uniform by default
+    pub axial_power_shape: AxialPowerShape,
+}
+
+impl HeaterVersion1Bare {
+
+    /// heater version 1 constructor 
+    /// with 20 W/(m^2 K) of heat loss to air, as callibrated 
+    /// for heater version 2 bare
+    ///
+    /// annulus flow area and hydraulic diameter are from the 
+    /// RELAP and SAM model 
+    pub fn new_bare_heater(initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        let h_to_air: HeatTransfer = 
+        HeatTransfer::new::<watt_per_square_meter_kelvin>(20.0);
+
+        return Self::_user_callibrated_htc_to_air_model(
+            initial_temperature, 
+            ambient_temperature, 
+            user_specified_inner_nodes, 
+            h_to_air);
+    }
+
+    /// heater version 1 constructor with a user specified 
+    /// heat transfer coefficient to air
+    pub fn _user_callibrated_htc_to_air_model(
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize,
+        h_to_air: HeatTransfer) -> Self {
+
+        let (therminol_array, steel_shell_array, inner_tube) = 
+        new_annular_heated_section(initial_temperature, 
+            user_specified_inner_nodes);
+
+        return Self { inner_nodes: user_specified_inner_nodes,
+            inner_tube: inner_tube.into(),
+            steel_shell: steel_shell_array.into(),
+            therminol_array: therminol_array.into(),
+            ambient_temperature,
+            heat_transfer_to_air: h_to_air,
+            axial_power_shape: AxialPowerShape::default(),
+        };
+    }
+}
+
+impl HeaterVersion1Insulated {
+
+    /// heater version 1 constructor 
+    /// with 6 W/(m^2 K) of heat loss to air (natural convection
+    /// only) from 5.08 cm of fiberglass insulation
+    pub fn new_insulated_heater(initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        let h_to_air: HeatTransfer = 
+        HeatTransfer::new::<watt_per_square_meter_kelvin>(6.0);
+
+        return Self::_user_callibrated_htc_to_air_model(
+            initial_temperature, 
+            ambient_temperature, 
+            user_specified_inner_nodes, 
+            h_to_air);
+    }
+
+    /// insulated heater version 1 constructor with a user 
+    /// specified heat transfer coefficient to air
+    pub fn _user_callibrated_htc_to_air_model(
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize,
+        h_to_air: HeatTransfer) -> Self {
+
+        let (therminol_array, steel_shell_array, inner_tube) = 
+        new_annular_heated_section(initial_temperature, 
+            user_specified_inner_nodes);
+
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+        let heated_length = Length::new::<meter>(
+            HEATER_V1_HEATED_LENGTH_METERS);
+        let fiberglass_id = Length::new::<meter>(HEATER_V1_SHELL_OD_METERS);
+        let fiberglass_od = fiberglass_id + 2.0 * Length::new::<meter>(
+            HEATER_V1_INSULATION_THICKNESS_METERS);
+
+        let insulation = 
+        SolidColumn::new_cylindrical_shell(
+            heated_length,
+            fiberglass_id,
+            fiberglass_od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Fiberglass,
+            user_specified_inner_nodes 
+        );
+
+        return Self { inner_nodes: user_specified_inner_nodes,
+            inner_tube: inner_tube.into(),
+            steel_shell: steel_shell_array.into(),
+            insulation_array: insulation.into(),
+            therminol_array: therminol_array.into(),
+            ambient_temperature,
+            heat_transfer_to_air: h_to_air,
+            axial_power_shape: AxialPowerShape::default(),
+        };
+    }
+}
+
+/// builds the therminol annulus, heated outer tube and 
+/// unheated inner tube common to both heater version 1 models
+fn new_annular_heated_section(initial_temperature: ThermodynamicTemperature,
+    user_specified_inner_nodes: usize) -> (FluidArray, SolidColumn, SolidColumn) {
+
+    let flow_area = Area::new::<square_meter>(
+        HEATER_V1_FLOW_AREA_SQUARE_METERS);
+    let heated_length = Length::new::<meter>(
+        HEATER_V1_HEATED_LENGTH_METERS);
+    let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+    let hydraulic_diameter = Length::new::<meter>(
+        HEATER_V1_HYDRAULIC_DIAMETER_METERS);
+
+    // the form losses are taken care of in the hydraulics,
+    // so a dummy form loss is used as in heater version 2
+    let dummy_pipe_form_loss = Ratio::new::<ratio>(0.1);
+
+    // heater is inclined 90 degrees upwards, not that this is 
+    // particularly important for this scenario
+    let pipe_incline_angle = Angle::new::<uom::si::angle::degree>(90.0);
+
+    // therminol in the annulus
+    let therminol_array: FluidArray = 
+    FluidArray::new_odd_shaped_pipe(
+        heated_length,
+        hydraulic_diameter,
+        flow_area,
+        initial_temperature,
+        atmospheric_pressure,
+        SolidMaterial::SteelSS304L,
+        LiquidMaterial::TherminolVP1,
+        dummy_pipe_form_loss,
+        user_specified_inner_nodes,
+        pipe_incline_angle
+    );
+
+    // heated outer tube
+    let steel_shell_array = 
+    SolidColumn::new_cylindrical_shell(
+        heated_length,
+        Length::new::<meter>(HEATER_V1_SHELL_ID_METERS),
+        Length::new::<meter>(HEATER_V1_SHELL_OD_METERS),
+        initial_temperature,
+        atmospheric_pressure,
+        SolidMaterial::SteelSS304L,
+        user_specified_inner_nodes 
+    );
+
+    // unheated inner tube
+    let inner_tube = 
+    SolidColumn::new_cylindrical_shell(
+        heated_length,
+        Length::new::<meter>(HEATER_V1_INNER_TUBE_ID_METERS),
+        Length::new::<meter>(HEATER_V1_INNER_TUBE_OD_METERS),
+        initial_temperature,
+        atmospheric_pressure,
+        SolidMaterial::SteelSS304L,
+        user_specified_inner_nodes 
+    );
+
+    return (therminol_array, steel_shell_array, inner_tube);
+}
+
+// methods for HeaterVersion1Bare and HeaterVersion1Insulated only,
+// kept private as with the DHX
+mod preprocessing;
+pub use preprocessing::get_annulus_heat_transfer_coeffs;
+
+mod calculation;
+
+mod postprocessing;
@@ -0,0 +1,35 @@
+use uom::si::f64::*;
+
+use super::{HeaterVersion1Bare, HeaterVersion1Insulated};
+
+impl HeaterVersion1Bare {
+    pub fn steel_shell_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.steel_shell.get_temperature_vector().unwrap()
+    }
+
+    pub fn _therminol_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.therminol_array.get_temperature_vector().unwrap()
+    }
+
+    pub fn _inner_tube_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.inner_tube.get_temperature_vector().unwrap()
+    }
+}
+
+impl HeaterVersion1Insulated {
+    pub fn steel_shell_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.steel_shell.get_temperature_vector().unwrap()
+    }
+
+    pub fn _therminol_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.therminol_array.get_temperature_vector().unwrap()
+    }
+
+    pub fn _inner_tube_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.inner_tube.get_temperature_vector().unwrap()
+    }
+
+    pub fn _insulation_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.insulation_array.get_temperature_vector().unwrap()
+    }
+}
@@ -0,0 +1,522 @@
+use super::*;
+use crate::ctah_heat_transfer_lib::get_therminol_tube_heat_transfer_coeff;
+use thermal_hydraulics_rs::heat_transfer_lib::control_volume_calculations::common_functions::try_get_thermal_conductance_annular_cylinder;
+use uom::ConstZero;
+use ndarray::*;
+use crate::heater::air_heat_transfer::{get_natural_convection_and_radiation_htc, CylinderOrientation};
+
+impl HeaterVersion1Bare {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrate and heater power
+    ///
+    /// the heater power goes into the steel shell (outer tube),
+    /// which loses heat to the air and the therminol, the 
+    /// therminol also exchanges heat with the inner tube
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate,
+        heater_steady_state_power: Power){
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let steel_to_air_nodal_conductance: ThermalConductance = 
+        get_steel_shell_to_air_nodal_conductance(
+            &self.steel_shell,
+            self.heat_transfer_to_air,
+            number_of_temperature_nodes);
+
+        let ambient_temperature_vector: Vec<ThermodynamicTemperature> 
+        = vec![self.ambient_temperature; number_of_temperature_nodes];
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+            steel_to_air_nodal_conductance,
+            ambient_temperature_vector
+        ).unwrap();
+
+        self.steel_shell.set(steel_shell_clone.into()).unwrap();
+
+        connect_annulus_laterally(
+            &mut self.therminol_array,
+            &mut self.steel_shell,
+            &mut self.inner_tube,
+            mass_flowrate,
+            heater_steady_state_power,
+            &self.axial_power_shape,
+            number_of_temperature_nodes);
+
+        // axial connections 
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(Power::ZERO).into();
+
+        for heat_transfer_entity in [
+            &mut self.therminol_array,
+            &mut self.steel_shell,
+            &mut self.inner_tube] {
+            link_zero_power_bc(heat_transfer_entity, &mut zero_power_bc);
+        }
+    }
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// steel shell bulk temperature using Churchill-Chu natural convection 
+    /// (vertical cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        steel_shell_clone.try_get_bulk_temperature().unwrap();
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Vertical,
+            Length::new::<meter>(HEATER_V1_HEATED_LENGTH_METERS),
+            emissivity);
+    }
+}
+
+impl HeaterVersion1Insulated {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrate and heater power
+    ///
+    /// the heater power goes into the steel shell (outer tube),
+    /// which loses heat through the insulation to the air,
+    /// and to the therminol, the therminol also exchanges heat 
+    /// with the inner tube
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate,
+        heater_steady_state_power: Power){
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let insulation_to_air_conductance: ThermalConductance = 
+        self.get_air_insulation_nodal_conductance();
+
+        let steel_to_insulation_conductance: ThermalConductance = 
+        self.get_steel_to_fiberglass_nodal_conductance();
+
+        let ambient_temperature_vector: Vec<ThermodynamicTemperature> 
+        = vec![self.ambient_temperature; number_of_temperature_nodes];
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let mut insulation_array_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let steel_temp_vector: Vec<ThermodynamicTemperature> 
+        = steel_shell_clone.get_temperature_vector().unwrap();
+
+        let insulation_temp_vector: Vec<ThermodynamicTemperature> 
+        = insulation_array_clone.get_temperature_vector().unwrap();
+
+        // insulation to air interaction
+
+        insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+            insulation_to_air_conductance,
+            ambient_temperature_vector
+        ).unwrap();
+
+        // insulation to steel shell interaction 
+
+        steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+            steel_to_insulation_conductance,
+            insulation_temp_vector
+        ).unwrap();
+
+        insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+            steel_to_insulation_conductance,
+            steel_temp_vector
+        ).unwrap();
+
+        self.steel_shell.set(steel_shell_clone.into()).unwrap();
+
+        self.insulation_array.set(insulation_array_clone.into()).unwrap();
+
+        connect_annulus_laterally(
+            &mut self.therminol_array,
+            &mut self.steel_shell,
+            &mut self.inner_tube,
+            mass_flowrate,
+            heater_steady_state_power,
+            &self.axial_power_shape,
+            number_of_temperature_nodes);
+
+        // axial connections 
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(Power::ZERO).into();
+
+        for heat_transfer_entity in [
+            &mut self.therminol_array,
+            &mut self.steel_shell,
+            &mut self.inner_tube,
+            &mut self.insulation_array] {
+            link_zero_power_bc(heat_transfer_entity, &mut zero_power_bc);
+        }
+    }
+
+    /// obtains air to insulation conductance per node, 
+    /// including conduction through the outer half of the 
+    /// insulation
+    #[inline]
+    pub fn get_air_insulation_nodal_conductance(&self) 
+        -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+        let node_length: Length = Length::new::<meter>(
+            HEATER_V1_HEATED_LENGTH_METERS) / number_of_temperature_nodes as f64;
+
+        let id = Length::new::<meter>(HEATER_V1_SHELL_OD_METERS);
+        let od = id + 2.0 * Length::new::<meter>(
+            HEATER_V1_INSULATION_THICKNESS_METERS);
+        let insulation_mid_diameter: Length = 0.5 * (id + od);
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            insulation_mid_diameter,
+            od,
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+
+        let air_convection_conductance: ThermalConductance
+        = od * PI * node_length * self.heat_transfer_to_air;
+
+        let total_resistance = 
+        1.0/air_convection_conductance + 
+        1.0/fiberglass_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    /// obtains steel to fiberglass conductance per node, 
+    /// from the middle of the steel shell to the middle of 
+    /// the insulation
+    #[inline]
+    pub fn get_steel_to_fiberglass_nodal_conductance(&self) 
+        -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let mut steel_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+        let node_length: Length = Length::new::<meter>(
+            HEATER_V1_HEATED_LENGTH_METERS) / number_of_temperature_nodes as f64;
+
+        let steel_id = Length::new::<meter>(HEATER_V1_SHELL_ID_METERS);
+        let steel_od = Length::new::<meter>(HEATER_V1_SHELL_OD_METERS);
+        let fiberglass_od = steel_od + 2.0 * Length::new::<meter>(
+            HEATER_V1_INSULATION_THICKNESS_METERS);
+
+        let steel_conductivity: ThermalConductivity 
+        = SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+            steel_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let steel_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            0.5 * (steel_id + steel_od),
+            steel_od,
+            node_length,
+            steel_conductivity
+        ).unwrap();
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            steel_od,
+            0.5 * (steel_od + fiberglass_od),
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+
+        let total_resistance = 1.0/steel_layer_conductance 
+            + 1.0/fiberglass_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// insulation bulk temperature (approximating the 
+    /// insulation outer surface temperature) using Churchill-Chu natural convection 
+    /// (vertical cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut insulation_array_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        insulation_array_clone.try_get_bulk_temperature().unwrap();
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Vertical,
+            Length::new::<meter>(HEATER_V1_HEATED_LENGTH_METERS),
+            emissivity);
+    }
+}
+
+/// returns the heat transfer coefficients from the therminol 
+/// in the annulus to the outer tube (steel shell) and the inner 
+/// tube respectively
+///
+/// the tube correlation (gnielinski, floored at the laminar 
+/// value) is evaluated with the annulus hydraulic diameter, 
+/// and then corrected for each wall of the annulus using 
+/// the Petukhov and Roizen factors, where a is the ratio of the 
+/// inner to the outer diameter of the annulus
+///
+/// outer wall: Nu_o = Nu_tube (1 - 0.14 a^0.6)
+/// inner wall: Nu_i = Nu_tube 0.86 a^(-0.16)
+pub fn get_annulus_heat_transfer_coeffs(
+    therminol_array: &mut FluidArray,
+    steel_shell_temperature: ThermodynamicTemperature,
+    inner_tube_temperature: ThermodynamicTemperature) 
+    -> (HeatTransfer, HeatTransfer) {
+
+    let diameter_ratio: f64 = HEATER_V1_INNER_TUBE_OD_METERS 
+        / HEATER_V1_SHELL_ID_METERS;
+
+    let outer_wall_correction: f64 = 1.0 - 0.14 * diameter_ratio.powf(0.6);
+    let inner_wall_correction: f64 = 0.86 * diameter_ratio.powf(-0.16);
+
+    let h_outer_wall: HeatTransfer = outer_wall_correction * 
+        get_therminol_tube_heat_transfer_coeff(
+            therminol_array, steel_shell_temperature);
+
+    let h_inner_wall: HeatTransfer = inner_wall_correction * 
+        get_therminol_tube_heat_transfer_coeff(
+            therminol_array, inner_tube_temperature);
+
+    return (h_outer_wall, h_inner_wall);
+}
+
+/// obtains the bare steel shell to air conductance per node,
+/// including conduction through the outer half of the shell
+fn get_steel_shell_to_air_nodal_conductance(
+    steel_shell: &HeatTransferEntity,
+    h_air_to_steel_surf: HeatTransfer,
+    number_of_temperature_nodes: usize) -> ThermalConductance {
+
+    let mut steel_shell_clone: SolidColumn = 
+    steel_shell.clone().try_into().unwrap();
+
+    let node_length: Length = Length::new::<meter>(
+        HEATER_V1_HEATED_LENGTH_METERS) / number_of_temperature_nodes as f64;
+    let id = Length::new::<meter>(HEATER_V1_SHELL_ID_METERS);
+    let od = Length::new::<meter>(HEATER_V1_SHELL_OD_METERS);
+
+    let steel_conductivity: ThermalConductivity = 
+    SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+        steel_shell_clone.try_get_bulk_temperature().unwrap()
+    ).unwrap();
+
+    let steel_layer_conductance: ThermalConductance = 
+    try_get_thermal_conductance_annular_cylinder(
+        0.5 * (id + od),
+        od,
+        node_length,
+        steel_conductivity
+    ).unwrap();
+
+    let air_convection_conductance: ThermalConductance = 
+    od * PI * node_length * h_air_to_steel_surf;
+
+    let total_resistance = 1.0/steel_layer_conductance 
+        + 1.0/air_convection_conductance;
+
+    return 1.0/total_resistance;
+}
+
+/// connects the therminol in the annulus to the steel shell 
+/// (outer tube) and the inner tube, and adds the heater power 
+/// to the steel shell according to the axial power shape
+///
+/// each wall conductance is the convection resistance in series 
+/// with conduction through half the wall
+fn connect_annulus_laterally(
+    therminol_array: &mut HeatTransferEntity,
+    steel_shell: &mut HeatTransferEntity,
+    inner_tube: &mut HeatTransferEntity,
+    mass_flowrate: MassRate,
+    heater_steady_state_power: Power,
+    axial_power_shape: &AxialPowerShape,
+    number_of_temperature_nodes: usize){
+
+    let mut therminol_array_clone: FluidArray = 
+    therminol_array.clone().try_into().unwrap();
+
+    let mut steel_shell_clone: SolidColumn = 
+    steel_shell.clone().try_into().unwrap();
+
+    let mut inner_tube_clone: SolidColumn = 
+    inner_tube.clone().try_into().unwrap();
+
+    // note, must set mass flowrate first 
+    // otherwise there is by default zero flow through 
+    // the array
+
+    therminol_array_clone.set_mass_flowrate(mass_flowrate);
+
+    let steel_shell_temperature: ThermodynamicTemperature = 
+    steel_shell_clone.try_get_bulk_temperature().unwrap();
+
+    let inner_tube_temperature: ThermodynamicTemperature = 
+    inner_tube_clone.try_get_bulk_temperature().unwrap();
+
+    let (h_outer_wall, h_inner_wall) = get_annulus_heat_transfer_coeffs(
+        &mut therminol_array_clone,
+        steel_shell_temperature,
+        inner_tube_temperature);
+
+    let node_length: Length = Length::new::<meter>(
+        HEATER_V1_HEATED_LENGTH_METERS) / number_of_temperature_nodes as f64;
+
+    let shell_id = Length::new::<meter>(HEATER_V1_SHELL_ID_METERS);
+    let shell_od = Length::new::<meter>(HEATER_V1_SHELL_OD_METERS);
+    let inner_tube_id = Length::new::<meter>(HEATER_V1_INNER_TUBE_ID_METERS);
+    let inner_tube_od = Length::new::<meter>(HEATER_V1_INNER_TUBE_OD_METERS);
+
+    let steel_shell_conductivity: ThermalConductivity = 
+    SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+        steel_shell_temperature).unwrap();
+
+    let inner_tube_conductivity: ThermalConductivity = 
+    SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+        inner_tube_temperature).unwrap();
+
+    // outer wall, the therminol wets the inside of the shell
+    let steel_shell_to_therminol_conductance: ThermalConductance = {
+        let convection_conductance: ThermalConductance = 
+        h_outer_wall * shell_id * PI * node_length;
+
+        let half_wall_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            shell_id,
+            0.5 * (shell_id + shell_od),
+            node_length,
+            steel_shell_conductivity
+        ).unwrap();
+
+        1.0/(1.0/convection_conductance + 1.0/half_wall_conductance)
+    };
+
+    // inner wall, the therminol wets the outside of the inner tube
+    let inner_tube_to_therminol_conductance: ThermalConductance = {
+        let convection_conductance: ThermalConductance = 
+        h_inner_wall * inner_tube_od * PI * node_length;
+
+        let half_wall_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            0.5 * (inner_tube_id + inner_tube_od),
+            inner_tube_od,
+            node_length,
+            inner_tube_conductivity
+        ).unwrap();
+
+        1.0/(1.0/convection_conductance + 1.0/half_wall_conductance)
+    };
+
+    let steel_temp_vector: Vec<ThermodynamicTemperature> 
+    = steel_shell_clone.get_temperature_vector().unwrap();
+
+    let inner_tube_temp_vector: Vec<ThermodynamicTemperature> 
+    = inner_tube_clone.get_temperature_vector().unwrap();
+
+    let fluid_temp_vector: Vec<ThermodynamicTemperature> 
+    = therminol_array_clone.get_temperature_vector().unwrap();
+
+    // steel shell to therminol interaction
+
+    steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+        steel_shell_to_therminol_conductance,
+        fluid_temp_vector.clone()
+    ).unwrap();
+
+    therminol_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+        steel_shell_to_therminol_conductance,
+        steel_temp_vector
+    ).unwrap();
+
+    // inner tube to therminol interaction
+
+    inner_tube_clone.lateral_link_new_temperature_vector_avg_conductance(
+        inner_tube_to_therminol_conductance,
+        fluid_temp_vector
+    ).unwrap();
+
+    therminol_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+        inner_tube_to_therminol_conductance,
+        inner_tube_temp_vector
+    ).unwrap();
+
+    // heat source in the steel shell, following the axial power shape
+
+    let q_frac_arr: Array1<f64> = axial_power_shape
+        .get_power_fractions(number_of_temperature_nodes);
+
+    steel_shell_clone.lateral_link_new_power_vector(
+        heater_steady_state_power,
+        q_frac_arr
+    ).unwrap();
+
+    // now that lateral connections are done, 
+    // modify the heat transfer entity 
+
+    therminol_array.set(therminol_array_clone.into()).unwrap();
+    steel_shell.set(steel_shell_clone.into()).unwrap();
+    inner_tube.set(inner_tube_clone.into()).unwrap();
+}
+
+/// the end of each node should have a zero power boundary condition 
+/// connected to each of them at the bare minimum
+///
+/// to connect the rest of the heat transfer entities, 
+/// use the link to front or back methods within the 
+/// FluidArray or SolidColumn
+#[inline]
+fn link_zero_power_bc(heat_transfer_entity: &mut HeatTransferEntity,
+    zero_power_bc: &mut HeatTransferEntity){
+
+    let interaction: HeatTransferInteractionType = 
+    HeatTransferInteractionType::UserSpecifiedHeatAddition;
+
+    heat_transfer_entity.link_to_front(zero_power_bc,
+        interaction).unwrap();
+
+    heat_transfer_entity.link_to_back(zero_power_bc,
+        interaction).unwrap();
+}
@@ -5,6 +5,8 @@ use uom::si::area::square_meter;
 use uom::si::heat_transfer::watt_per_square_meter_kelvin;
 use uom::si::ratio::ratio;
 use uom::si::pressure::atmosphere;
+
+use super::AxialPowerShape;
 /// represents heater version 2 without insulation 
 /// This is because during 2018-ish, the heater insulation 
 /// got burnt off and a lot of frequency response tests were done 
@@ -35,6 +37,8 @@ pub struct HeaterVersion2Bare {
 
     pub heat_transfer_to_air: HeatTransfer,
 
+    /// axial shape of the heater power, uniform by default
+    pub axial_power_shape: AxialPowerShape,
 
 }
 
@@ -130,6 +134,7 @@ impl HeaterVersion2Bare {
             therminol_array: therminol_array.into(),
             ambient_temperature,
             heat_transfer_to_air: h_to_air,
+            axial_power_shape: AxialPowerShape::default(),
         };
     }
     /// traditional uncallibrated heater constructor 
@@ -216,6 +221,7 @@ impl HeaterVersion2Bare {
             therminol_array: therminol_array.into(),
             ambient_temperature,
             heat_transfer_to_air: h_to_air,
+            axial_power_shape: AxialPowerShape::default(),
         };
     }
 
@@ -302,6 +308,7 @@ impl HeaterVersion2Bare {
             therminol_array: therminol_array.into(),
             ambient_temperature,
             heat_transfer_to_air: h_to_air,
+            axial_power_shape: AxialPowerShape::default(),
         };
     }
 }
@@ -5,8 +5,10 @@ use super::HeaterVersion2Bare;
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::input_structs::NusseltPrandtlReynoldsData;
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::enums::NusseltCorrelation;
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use crate::therminol_convection::{floor_at_laminar_nusselt, get_heat_transfer_reynolds};
 use uom::{si::{area::square_inch, pressure::atmosphere}, ConstZero};
 use ndarray::*;
+use crate::heater::air_heat_transfer::{get_natural_convection_and_radiation_htc, CylinderOrientation};
 
 impl HeaterVersion2Bare {
 
@@ -24,7 +26,7 @@ impl HeaterVersion2Bare {
         heater_steady_state_power: Power){
 
 
-        // first let's get all the conductances 
+        // first let's get the steel to air conductance 
         let heat_transfer_to_air = self.heat_transfer_to_air;
 
         let steel_to_air_nodal_conductance: ThermalConductance 
@@ -32,28 +34,11 @@ impl HeaterVersion2Bare {
             heat_transfer_to_air
         );
 
-        self.set_mass_flowrate(mass_flowrate);
-
-        let steel_surf_to_therminol_conductance: ThermalConductance 
-        = self.get_therminol_node_steel_shell_conductance();
-
-        let twisted_tape_to_therminol_conductance: ThermalConductance 
-        = self.get_therminol_node_twisted_tape_conductance();
-
-        // other stuff 
         let number_of_temperature_nodes = self.inner_nodes + 2;
-        let q_fraction_per_node: f64 = 1.0/ number_of_temperature_nodes as f64;
-        let mut q_frac_arr: Array1<f64> = Array::default(number_of_temperature_nodes);
-        q_frac_arr.fill(q_fraction_per_node);
-
-        // then get the ambient temperature 
-
         let ambient_air_temp = self.ambient_temperature;
 
-        // lateral connections 
+        // steel to air interaction
         {
-            // first i will need to create temperature vectors 
-
             let mut ambient_temperature_vector: Vec<ThermodynamicTemperature> 
             = Array1::default(number_of_temperature_nodes)
                 .iter().map( |&temp| {
@@ -63,7 +48,50 @@ impl HeaterVersion2Bare {
 
             ambient_temperature_vector.fill(ambient_air_temp);
 
+            let mut steel_shell_clone: SolidColumn = 
+            self.steel_shell.clone().try_into().unwrap();
+
+            steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+                steel_to_air_nodal_conductance,
+                ambient_temperature_vector
+            ).unwrap();
+
+            self.steel_shell.set(steel_shell_clone.into()).unwrap();
+        }
+
+        self.lateral_connections_within_steel_shell(
+            mass_flowrate,
+            heater_steady_state_power);
+    }
+
+    /// connects the therminol to the steel shell and twisted tape,
+    /// adds the heater power to the steel shell and 
+    /// connects zero power BCs to the ends of each array
+    ///
+    /// this excludes the heat loss from the steel shell outer 
+    /// surface, which is to air for the bare heater, 
+    /// and to the insulation for the insulated heater
+    #[inline]
+    pub fn lateral_connections_within_steel_shell(&mut self,
+        mass_flowrate: MassRate,
+        heater_steady_state_power: Power){
+
+        // first let's get all the conductances 
+        self.set_mass_flowrate(mass_flowrate);
+
+        let steel_surf_to_therminol_conductance: ThermalConductance 
+        = self.get_therminol_node_steel_shell_conductance();
 
+        let twisted_tape_to_therminol_conductance: ThermalConductance 
+        = self.get_therminol_node_twisted_tape_conductance();
+
+        // other stuff 
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+        let q_frac_arr: Array1<f64> = self.axial_power_shape
+            .get_power_fractions(number_of_temperature_nodes);
+
+        // lateral connections 
+        {
             // clone each array and set them later
 
             let mut steel_shell_clone: SolidColumn = 
@@ -89,13 +117,6 @@ impl HeaterVersion2Bare {
             = therminol_array_clone.get_temperature_vector().unwrap();
 
             // second, fill them into the each array 
-            
-            // steel to air interaction
-
-            steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
-                steel_to_air_nodal_conductance,
-                ambient_temperature_vector
-            ).unwrap();
 
             // steel shell to therminol interaction
 
@@ -336,9 +357,11 @@ impl HeaterVersion2Bare {
             heater_prandtl_reynolds_data
         );
 
-        let nusselt_estimate: Ratio = 
+        let mut nusselt_estimate: Ratio = 
         heater_nusselt_correlation.try_get().unwrap();
 
+        nusselt_estimate = floor_at_laminar_nusselt(nusselt_estimate);
+
 
 
         // now we can get the heat transfer coeff, 
@@ -418,9 +441,8 @@ impl HeaterVersion2Bare {
         //
         // but for now, I'm going to use Re and Nu using hydraulic diameter 
         // and live with it for the time being
-        //
-        let reynolds: Ratio = 
-        mass_flowrate/flow_area*hydraulic_diameter / viscosity;
+        let reynolds: Ratio = get_heat_transfer_reynolds(
+            mass_flowrate, flow_area, hydraulic_diameter, viscosity);
 
         reynolds
 
@@ -544,9 +566,8 @@ impl HeaterVersion2Bare {
         //
         // but for now, I'm going to use Re and Nu using hydraulic diameter 
         // and live with it for the time being
-        //
-        let reynolds: Ratio = 
-        mass_flowrate/flow_area*hydraulic_diameter / viscosity;
+        let reynolds: Ratio = get_heat_transfer_reynolds(
+            mass_flowrate, flow_area, hydraulic_diameter, viscosity);
 
         // need to get prandtl number of fluid 
         // so I need fluid temperature 
@@ -573,13 +594,15 @@ impl HeaterVersion2Bare {
         // with Pr and Re, get nusselt estimate 
         //
 
-        let nusselt_estimate: Ratio = 
+        let mut nusselt_estimate: Ratio = 
         therminol_fluid_array_clone.get_nusselt(
             reynolds,
             fluid_prandtl,
             twisted_tape_wall_prandtl,
         ).unwrap();
 
+        nusselt_estimate = floor_at_laminar_nusselt(nusselt_estimate);
+
         // with nusselt estimate done, (I didn't convert the 
         // hydraulic diameter to an equivalent particle diameter)
         // Now I can get a heat transfer coeff 
@@ -638,6 +661,29 @@ impl HeaterVersion2Bare {
 
     }
 
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// steel shell bulk temperature using Churchill-Chu natural convection 
+    /// (vertical cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        steel_shell_clone.try_get_bulk_temperature().unwrap();
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Vertical,
+            Length::new::<meter>(1.6383),
+            emissivity);
+    }
 }
 
 
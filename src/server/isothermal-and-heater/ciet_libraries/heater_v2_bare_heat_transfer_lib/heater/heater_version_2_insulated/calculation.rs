@@ -0,0 +1,14 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use super::HeaterVersion2Insulated;
+
+impl HeaterVersion2Insulated {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// HeaterVersion2Insulated
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.heater_v2.advance_timestep(timestep);
+        self.insulation_array.advance_timestep_mut_self(timestep).unwrap();
+    }
+}
@@ -0,0 +1,117 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use uom::si::heat_transfer::watt_per_square_meter_kelvin;
+use uom::si::pressure::atmosphere;
+
+use super::HeaterVersion2Bare;
+
+/// heated length of heater version 2
+pub const HEATER_V2_HEATED_LENGTH_METERS: f64 = 1.6383;
+
+/// outer diameter of the heater version 2 steel shell
+pub const HEATER_V2_SHELL_OD_METERS: f64 = 0.04;
+
+/// inner diameter of the heater version 2 steel shell
+pub const HEATER_V2_SHELL_ID_METERS: f64 = 0.0381;
+
+/// thickness of fiberglass insulation around heater version 2
+/// before it burnt off, taken to be the same 2 in 
+/// as the insulated pipes
+pub const HEATER_V2_INSULATION_THICKNESS_METERS: f64 = 0.0508;
+
+/// represents heater version 2 with its fiberglass insulation 
+///
+/// before 2018-ish, heater version 2 was insulated, so the 
+/// older frequency response tests need the insulation 
+/// for the heat loss and thermal inertia
+///
+/// the steel shell, therminol and twisted tape are the 
+/// same as in heater version 2 bare, 
+/// but the steel shell loses heat to the insulation and the 
+/// insulation loses heat to the air
+///
+/// note that it only contains the heated section, not the top nor 
+/// bottom heads
+#[derive(Debug,Clone,PartialEq)]
+pub struct HeaterVersion2Insulated {
+
+    inner_nodes: usize,
+
+    /// the steel shell, therminol and twisted tape,
+    /// its heat_transfer_to_air is not used as the steel 
+    /// shell is not exposed to air
+    pub heater_v2: HeaterVersion2Bare,
+
+    pub insulation_array: HeatTransferEntity,
+
+    pub ambient_temperature: ThermodynamicTemperature,
+
+    pub heat_transfer_to_air: HeatTransfer,
+}
+
+impl HeaterVersion2Insulated {
+
+    /// insulated heater constructor 
+    /// with 6 W/(m^2 K) of heat loss to air (natural convection
+    /// only) from the insulation outer surface
+    pub fn new_insulated_heater(initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize) -> Self {
+
+        let h_to_air: HeatTransfer = 
+        HeatTransfer::new::<watt_per_square_meter_kelvin>(6.0);
+
+        return Self::_user_callibrated_htc_to_air_model(
+            initial_temperature, 
+            ambient_temperature, 
+            user_specified_inner_nodes, 
+            h_to_air);
+    }
+
+    /// insulated heater constructor with a user specified 
+    /// heat transfer coefficient to air
+    pub fn _user_callibrated_htc_to_air_model(
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature,
+        user_specified_inner_nodes: usize,
+        h_to_air: HeatTransfer) -> Self {
+
+        let heater_v2 = HeaterVersion2Bare::new_dewet_model(
+            initial_temperature,
+            ambient_temperature,
+            user_specified_inner_nodes);
+
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+        let heated_length = Length::new::<meter>(
+            HEATER_V2_HEATED_LENGTH_METERS);
+        let fiberglass_id = Length::new::<meter>(HEATER_V2_SHELL_OD_METERS);
+        let fiberglass_od = fiberglass_id + 2.0 * Length::new::<meter>(
+            HEATER_V2_INSULATION_THICKNESS_METERS);
+
+        let insulation = 
+        SolidColumn::new_cylindrical_shell(
+            heated_length,
+            fiberglass_id,
+            fiberglass_od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Fiberglass,
+            user_specified_inner_nodes 
+        );
+
+        return Self { inner_nodes: user_specified_inner_nodes,
+            heater_v2,
+            insulation_array: insulation.into(),
+            ambient_temperature,
+            heat_transfer_to_air: h_to_air,
+        };
+    }
+}
+
+// methods for HeaterVersion2Insulated only,
+// kept private as with the DHX
+mod preprocessing;
+
+mod calculation;
+
+mod postprocessing;
@@ -0,0 +1,13 @@
+use uom::si::f64::*;
+
+use super::HeaterVersion2Insulated;
+
+impl HeaterVersion2Insulated {
+    pub fn steel_shell_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.heater_v2.steel_shell_temperature()
+    }
+
+    pub fn _insulation_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.insulation_array.get_temperature_vector().unwrap()
+    }
+}
@@ -0,0 +1,219 @@
+use super::*;
+use thermal_hydraulics_rs::heat_transfer_lib::control_volume_calculations::common_functions::try_get_thermal_conductance_annular_cylinder;
+use uom::ConstZero;
+use crate::heater::air_heat_transfer::{get_natural_convection_and_radiation_htc, CylinderOrientation};
+
+impl HeaterVersion2Insulated {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrate and heater power
+    ///
+    /// the steel shell loses heat through the insulation to 
+    /// the air, everything within the steel shell is connected 
+    /// as in heater version 2 bare
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate,
+        heater_steady_state_power: Power){
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let insulation_to_air_conductance: ThermalConductance = 
+        self.get_air_insulation_nodal_conductance();
+
+        let steel_to_insulation_conductance: ThermalConductance = 
+        self.get_steel_to_fiberglass_nodal_conductance();
+
+        let ambient_temperature_vector: Vec<ThermodynamicTemperature> 
+        = vec![self.ambient_temperature; number_of_temperature_nodes];
+
+        // lateral connections 
+        {
+            let mut steel_shell_clone: SolidColumn = 
+            self.heater_v2.steel_shell.clone().try_into().unwrap();
+
+            let mut insulation_array_clone: SolidColumn = 
+            self.insulation_array.clone().try_into().unwrap();
+
+            let steel_temp_vector: Vec<ThermodynamicTemperature> 
+            = steel_shell_clone.get_temperature_vector().unwrap();
+
+            let insulation_temp_vector: Vec<ThermodynamicTemperature> 
+            = insulation_array_clone.get_temperature_vector().unwrap();
+
+            // insulation to air interaction
+
+            insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+                insulation_to_air_conductance,
+                ambient_temperature_vector
+            ).unwrap();
+
+            // insulation to steel shell interaction 
+
+            steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+                steel_to_insulation_conductance,
+                insulation_temp_vector
+            ).unwrap();
+
+            insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+                steel_to_insulation_conductance,
+                steel_temp_vector
+            ).unwrap();
+
+            self.heater_v2.steel_shell.set(steel_shell_clone.into()).unwrap();
+
+            self.insulation_array.set(insulation_array_clone.into()).unwrap();
+        }
+
+        // therminol, twisted tape and heater power
+        self.heater_v2.lateral_connections_within_steel_shell(
+            mass_flowrate,
+            heater_steady_state_power);
+
+        // axial connections 
+
+        self.zero_power_bc_connection();
+    }
+
+    /// the insulation ends have zero power boundary conditions, 
+    /// the rest of the heater is done in 
+    /// lateral_connections_within_steel_shell
+    #[inline]
+    fn zero_power_bc_connection(&mut self){
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(Power::ZERO).into();
+
+        let interaction: HeatTransferInteractionType = 
+        HeatTransferInteractionType::UserSpecifiedHeatAddition;
+
+        self.insulation_array.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.insulation_array.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+    }
+
+    /// obtains air to insulation conductance per node, 
+    /// including conduction through the outer half of the 
+    /// insulation
+    #[inline]
+    pub fn get_air_insulation_nodal_conductance(&self) 
+        -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let node_length: Length = self.get_node_length();
+
+        let id = Length::new::<meter>(HEATER_V2_SHELL_OD_METERS);
+        let od = id + 2.0 * Length::new::<meter>(
+            HEATER_V2_INSULATION_THICKNESS_METERS);
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            0.5 * (id + od),
+            od,
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+
+        let air_convection_conductance: ThermalConductance
+        = od * PI * node_length * self.heat_transfer_to_air;
+
+        let total_resistance = 
+        1.0/air_convection_conductance + 
+        1.0/fiberglass_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    /// obtains steel to fiberglass conductance per node, 
+    /// from the middle of the steel shell to the middle of 
+    /// the insulation
+    #[inline]
+    pub fn get_steel_to_fiberglass_nodal_conductance(&self) 
+        -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let mut steel_clone: SolidColumn = 
+        self.heater_v2.steel_shell.clone().try_into().unwrap();
+
+        let node_length: Length = self.get_node_length();
+
+        let steel_id = Length::new::<meter>(HEATER_V2_SHELL_ID_METERS);
+        let steel_od = Length::new::<meter>(HEATER_V2_SHELL_OD_METERS);
+        let fiberglass_od = steel_od + 2.0 * Length::new::<meter>(
+            HEATER_V2_INSULATION_THICKNESS_METERS);
+
+        let steel_conductivity: ThermalConductivity 
+        = SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+            steel_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let steel_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            0.5 * (steel_id + steel_od),
+            steel_od,
+            node_length,
+            steel_conductivity
+        ).unwrap();
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            steel_od,
+            0.5 * (steel_od + fiberglass_od),
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+
+        let total_resistance = 1.0/steel_layer_conductance 
+            + 1.0/fiberglass_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    #[inline]
+    fn get_node_length(&self) -> Length {
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        Length::new::<meter>(HEATER_V2_HEATED_LENGTH_METERS) 
+            / number_of_temperature_nodes as f64
+    }
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// insulation bulk temperature (approximating the 
+    /// insulation outer surface temperature) using Churchill-Chu natural convection 
+    /// (vertical cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut insulation_array_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        insulation_array_clone.try_get_bulk_temperature().unwrap();
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Vertical,
+            Length::new::<meter>(HEATER_V2_HEATED_LENGTH_METERS),
+            emissivity);
+    }
+}
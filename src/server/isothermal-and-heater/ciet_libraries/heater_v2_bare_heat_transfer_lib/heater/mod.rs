@@ -37,11 +37,17 @@
 /// represents heater version 1, 
 ///
 /// it allows fluid to flow through it in an annular tube
+///
+/// the thermal models are HeaterVersion1Bare and 
+/// HeaterVersion1Insulated
 pub struct HeaterVersion1;
 
 /// represents heater version 2, 
 ///
 /// it has twisted tape
+///
+/// the thermal models are HeaterVersion2Bare and 
+/// HeaterVersion2Insulated
 pub struct HeaterVersion2;
 
 pub mod heater_version_2_bare;
@@ -53,6 +59,21 @@ use std::time::SystemTime;
 use csv::Writer;
 pub use heater_version_2_bare::HeaterVersion2Bare;
 
+pub mod heater_version_2_insulated;
+pub use heater_version_2_insulated::HeaterVersion2Insulated;
+
+pub mod heater_version_1;
+pub use heater_version_1::{HeaterVersion1Bare, HeaterVersion1Insulated};
+
+pub mod heated_section;
+pub use heated_section::{HeatedSection, HeaterVersionSelection};
+
+pub mod air_heat_transfer;
+pub use air_heat_transfer::{AirHeatTransferModel, CylinderOrientation};
+
+pub mod axial_power_shape;
+pub use axial_power_shape::AxialPowerShape;
+
 pub mod heater_top_and_bottom_head_bare;
 pub use heater_top_and_bottom_head_bare::HeaterTopBottomHead;
 
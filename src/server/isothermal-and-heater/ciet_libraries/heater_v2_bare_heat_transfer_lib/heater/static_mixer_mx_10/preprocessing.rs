@@ -5,6 +5,7 @@ use thermal_hydraulics_rs::heat_transfer_lib::control_volume_calculations::commo
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::input_structs::GnielinskiData;
 use thermal_hydraulics_rs::heat_transfer_lib::nusselt_correlations::enums::NusseltCorrelation;
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use crate::therminol_convection::get_heat_transfer_reynolds;
 use uom::ConstZero;
 use uom::si::pressure::atmosphere;
 use ndarray::*;
@@ -430,9 +431,8 @@ impl StaticMixerMX10 {
         //
         // but for now, I'm going to use Re and Nu using hydraulic diameter 
         // and live with it for the time being
-        //
-        let reynolds: Ratio = 
-        mass_flowrate/flow_area*hydraulic_diameter / viscosity;
+        let reynolds: Ratio = get_heat_transfer_reynolds(
+            mass_flowrate, flow_area, hydraulic_diameter, viscosity);
 
         reynolds
 
@@ -5,6 +5,7 @@ use super::StructuralSupport;
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
 use uom::ConstZero;
 use ndarray::*;
+use crate::heater::air_heat_transfer::{get_natural_convection_and_radiation_htc, CylinderOrientation};
 
 impl StructuralSupport {
 
@@ -217,6 +218,35 @@ impl StructuralSupport {
 
     }
 
+
+    /// recalculates the heat transfer coefficient to air from the 
+    /// support bulk temperature using Churchill-Chu natural convection 
+    /// (horizontal cylinder) plus linearised radiation
+    ///
+    /// call this every timestep before the lateral connections 
+    /// to replace the callibrated heat transfer coefficient
+    #[inline]
+    pub fn update_heat_transfer_to_air(&mut self, emissivity: Ratio){
+
+        let mut support_array_clone: SolidColumn = 
+        self.support_array.clone().try_into().unwrap();
+
+        let surface_temperature: ThermodynamicTemperature = 
+        support_array_clone.try_get_bulk_temperature().unwrap();
+
+        // the support diameter is obtained from its lateral 
+        // surface area, A = pi D L
+        let support_length: Length = support_array_clone.get_component_length();
+        let support_diameter: Length = self.total_lateral_surface_area
+            / (PI * support_length);
+
+        self.heat_transfer_to_air = get_natural_convection_and_radiation_htc(
+            surface_temperature,
+            self.ambient_temperature,
+            CylinderOrientation::Horizontal,
+            support_diameter,
+            emissivity);
+    }
 }
 
 
@@ -1,82 +1,217 @@
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::frequency::hertz;
 use std::time::{Instant, Duration};
-use crate::{ctah_branch::*, therminol_component::TherminolCustomComponent, HeaterBranch, 
-    DHXBranch};
-
-extern crate roots;
-use roots::find_root_brent;
-use roots::SimpleConvergency;
+use std::cell::Cell;
+use std::path::Path;
+use crate::{CIETBranch, PumpRotor,
+    CIETLoopDefinition, CIETThermalComponentNames,
+    LoopDefinitionError, CIETPressureBreakdown,
+    ManometerBoard, ManometerReading,
+    loop_temperatures::CIETLoopTemperatures,
+    FlowSolverError, FlowSolverErrorKind, FLOW_SOLVER_TOLERANCE,
+    find_root_with_bracket_expansion};
+
+/// maximum number of iterations for the warm started newton solver
+/// before it falls back to brent's method
+pub const NEWTON_MAX_ITERATIONS: usize = 20;
+
+/// the newton solver is converged once every branch pressure change
+/// is within this tolerance of the common branch pressure change,
+/// or within NEWTON_RELATIVE_PRESSURE_TOLERANCE of its magnitude
+/// if that is larger
+///
+/// manometers read to about 1 Pa, so there is little point going 
+/// much below this
+pub const NEWTON_PRESSURE_TOLERANCE_PASCALS: f64 = 1e-3;
+
+/// relative tolerance on the branch pressure change for the
+/// newton solver, for pressure changes large enough that 
+/// round off makes NEWTON_PRESSURE_TOLERANCE_PASCALS unreachable
+pub const NEWTON_RELATIVE_PRESSURE_TOLERANCE: f64 = 1e-8;
+
+/// step used to obtain the gradient of each branch pressure change
+/// curve by finite differencing
+pub const NEWTON_FINITE_DIFFERENCE_STEP_KG_PER_S: f64 = 1e-6;
+
+/// positions of the branches in the newton solver arrays
+const CTAH_BRANCH_INDEX: usize = 0;
+const HEATER_BRANCH_INDEX: usize = 1;
+const DHX_BRANCH_INDEX: usize = 2;
+
+/// the algorithms available to solve for the branch flowrates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HydraulicsSolverMode {
+    /// brent's method on the common branch pressure change,
+    /// with nested brent solves for the flowrate of each branch,
+    /// started from scratch every calculation
+    Brent,
+    /// newton's method on all branch flowrates and the branch
+    /// pressure change at once, started from the previous
+    /// calculation, falls back to brent's method if it fails
+    ///
+    /// this is much quicker when conditions change little
+    /// between calculations, eg. every timestep
+    WarmStartedNewton,
+}
 
-/// This is a struct representing the 
-/// CIET facility in isothermal operation
+/// This is a struct representing the
+/// CIET facility primary loop
 ///
-/// temperature is assumed to be 21C all round
+/// temperature is assumed to be 21C all round unless the
+/// user sets the loop temperatures, in which case the
+/// hydraulics are solved in a non-isothermal manner
 ///
 /// no heat transfer equations are solved
-pub struct CIETIsothermalFacility<'ciet_collection_lifetime> {
+///
+/// the facility owns all of its branches and components, so
+/// it is meant to be constructed once and then calculated
+/// repeatedly (eg. every timestep) after changing the pump
+/// pressure, valve states or loop temperatures
+///
+/// calculate gives the quasi-static flowrates, where the flowrates
+/// jump straight to their steady values, while calculate_with_inertia
+/// advances the flowrates through a timestep with the fluid
+/// inertia of each branch and the coast-down of a tripped pump
+/// accounted for
+pub struct CIETIsothermalFacility {
 
     pub ctah_pump_pressure: Pressure,
     pub ctah_branch_mass_flowrate: MassRate,
     pub dhx_branch_mass_flowrate: MassRate,
     pub heater_branch_mass_flowrate: MassRate,
 
-    super_collection_vector_immutable: 
-        Vec<&'ciet_collection_lifetime dyn FluidComponentCollectionMethods>,
+    // pressure change across each branch (they are in parallel)
+    // from the last calculation
+    branch_pressure_change: Pressure,
 
-    ctah_branch: CTAHBranch<'ciet_collection_lifetime>,
-    heater_branch: HeaterBranch<'ciet_collection_lifetime>,
-    dhx_branch: DHXBranch<'ciet_collection_lifetime>
+    solver_mode: HydraulicsSolverMode,
 
+    // number of calculations where the warm started newton
+    // solver failed and brent's method was used instead
+    brent_fallback_count: usize,
 
+    // pump set points from the user, the pump delivers these
+    // unless it is tripped and coasting down
+    ctah_pump_set_pressure: Pressure,
+    ctah_pump_set_speed: Option<Frequency>,
+    ctah_pump_rotor: PumpRotor,
 
+    ctah_branch: CIETBranch,
+    heater_branch: CIETBranch,
+    dhx_branch: CIETBranch,
 
-}
-
+    // components modelled by the heater, CTAH and DHX thermal
+    // models, passed on to the loop temperatures
+    thermal_components: CIETThermalComponentNames,
 
-/// i also "inherit" traits from my supercollection from
-/// fluid_mechanics_rust
-///
-/// basically i need CIETIsothermalFacility to act a as a super
-/// collection of components
-/// the reason why this is here is so that i can get the 
-/// pressure change of the branch given a mass flowrate
-pub trait ParallelSuperCollection<'trait_lifetime> : 
-FluidComponentSuperCollection<'trait_lifetime> +
-FluidComponentSuperCollectionParallelAssociatedFunctions {}
+}
 
 
 /// for this object,
 ///
-/// i want to be able to 
+/// i want to be able to
 /// (1) set the ctah pump pressure
 /// (2) obtain flowrate readings for all flowmeters
 /// (3) obtain calculation time for the calculations as a function
 /// (4) the user should be able to execute value calculation via a function is called
+/// (5) open, close or partially open the branch valves
+/// (6) set the fluid temperatures of every component
+/// (7) attain manometer readings
+/// (8) trip the ctah pump and follow the flowrates as it coasts down
 ///
 ///
-/// i would have liked to, but an not doing:
-/// (1) attain manometer readings if possible
-/// This is not quite critical for dissertation purposes
-/// and would take quite some effort
-///
-///
-impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime> {
+impl CIETIsothermalFacility {
 
 
+    /// returns the ctah pump pressure, for a speed driven pump
+    /// this is the pump curve pressure at the ctah branch flowrate
+    /// of the last calculation
     pub fn get_ctah_pump_pressure(&self) -> Pressure {
         return self.ctah_pump_pressure;
     }
 
     #[inline]
     pub fn set_ctah_pump_pressure(
-        &mut self, 
-        user_specified_pressure: Pressure,
-        mutable_ctah_pump: &'ciet_collection_lifetime mut TherminolCustomComponent){
+        &mut self,
+        user_specified_pressure: Pressure){
 
         self.ctah_pump_pressure = user_specified_pressure;
-        self.ctah_branch.set_ctah_pump_pressure(
-            user_specified_pressure, mutable_ctah_pump);
+        self.ctah_pump_set_pressure = user_specified_pressure;
+        self.ctah_pump_set_speed = None;
+        self.apply_ctah_pump_set_point();
+
+    }
+
+    /// drives the ctah pump by speed (VFD frequency) instead
+    /// of a set pressure, the next calculation then finds the
+    /// operating point where the pump curve meets the loop
+    pub fn set_ctah_pump_speed(&mut self, pump_speed: Frequency){
+        self.ctah_pump_set_speed = Some(pump_speed);
+        self.apply_ctah_pump_set_point();
+    }
+
+    /// returns the ctah pump speed, or None if the pump is
+    /// a set pressure source
+    ///
+    /// for a tripped pump, this is the speed it has coasted
+    /// down to
+    pub fn get_ctah_pump_speed(&self) -> Option<Frequency> {
+        return self.ctah_branch.get_pump_speed();
+    }
+
+    /// trips the ctah pump, from the next calculate_with_inertia
+    /// onwards it coasts down from its set speed (or set pressure)
+    /// and the loop goes into natural circulation
+    ///
+    /// the pump stays tripped until reset_ctah_pump_trip is called,
+    /// set points written in the meantime take effect on reset
+    pub fn trip_ctah_pump(&mut self){
+        self.ctah_pump_rotor.trip();
+    }
 
+    /// restarts a tripped ctah pump at its set point
+    pub fn reset_ctah_pump_trip(&mut self){
+        self.ctah_pump_rotor.reset();
+        self.apply_ctah_pump_set_point();
+    }
+
+    /// puts the flowrates and branch pressure change back to
+    /// zero and restarts a tripped ctah pump, this is used when
+    /// the simulation is reset, set points and valve states
+    /// are left as they are
+    pub fn reset_flowrates(&mut self){
+        self.ctah_branch_mass_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+        self.dhx_branch_mass_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+        self.heater_branch_mass_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+        self.branch_pressure_change = Pressure::new::<pascal>(0.0);
+        self.reset_ctah_pump_trip();
+    }
+
+    pub fn is_ctah_pump_tripped(&self) -> bool {
+        return self.ctah_pump_rotor.is_tripped();
+    }
+
+    /// returns the ctah pump speed over its speed at trip,
+    /// which is 1.0 unless the pump is coasting down
+    pub fn get_ctah_pump_speed_ratio(&self) -> f64 {
+        return self.ctah_pump_rotor.get_speed_ratio();
+    }
+
+    /// passes the pump set point, scaled down by the coast-down
+    /// of a tripped pump, to the ctah branch
+    ///
+    /// by the affinity laws, the pump speed scales with the speed 
+    /// ratio and a set pressure scales with its square
+    fn apply_ctah_pump_set_point(&mut self){
+
+        let speed_ratio = self.ctah_pump_rotor.get_speed_ratio();
+
+        match self.ctah_pump_set_speed {
+            Some(pump_speed) => self.ctah_branch.set_pump_speed(
+                pump_speed * speed_ratio),
+            None => self.ctah_branch.set_pump_pressure(
+                self.ctah_pump_set_pressure * speed_ratio * speed_ratio),
+        }
     }
 
     pub fn get_ctah_branch_mass_flowrate(&self) -> MassRate {
@@ -91,183 +226,811 @@ impl<'ciet_collection_lifetime> CIETIsothermalFacility<'ciet_collection_lifetime
         return self.heater_branch_mass_flowrate;
     }
 
+    /// returns the pressure change across each branch
+    /// from the last calculation, the branches are in parallel
+    /// so they all share the same pressure change
+    pub fn get_branch_pressure_change(&self) -> Pressure {
+        return self.branch_pressure_change;
+    }
+
+    /// opens or closes the ctah branch valve
+    pub fn set_ctah_branch_valve_open(&mut self, valve_open: bool){
+        self.ctah_branch.set_valve_open(valve_open);
+    }
+
+    /// opens or closes the heater branch valve
+    pub fn set_heater_branch_valve_open(&mut self, valve_open: bool){
+        self.heater_branch.set_valve_open(valve_open);
+    }
+
+    /// opens or closes the dhx branch valve
+    pub fn set_dhx_branch_valve_open(&mut self, valve_open: bool){
+        self.dhx_branch.set_valve_open(valve_open);
+    }
+
+    /// sets the ctah branch valve opening (0.0 closed, 1.0 fully open)
+    pub fn set_ctah_branch_valve_opening(&mut self, opening: f64){
+        self.ctah_branch.set_valve_opening(opening);
+    }
 
-    pub fn calculate(&'ciet_collection_lifetime mut self) -> 
-        (Duration,MassRate,MassRate,MassRate)
+    /// sets the heater branch valve opening (0.0 closed, 1.0 fully open)
+    pub fn set_heater_branch_valve_opening(&mut self, opening: f64){
+        self.heater_branch.set_valve_opening(opening);
+    }
+
+    /// sets the dhx branch valve opening (0.0 closed, 1.0 fully open)
+    pub fn set_dhx_branch_valve_opening(&mut self, opening: f64){
+        self.dhx_branch.set_valve_opening(opening);
+    }
+
+    /// sets the fluid temperature of every component in the loop
+    /// so that buoyancy is accounted for in the next calculation
+    ///
+    /// the loop temperatures must have been made for this facility
+    /// (see get_uniform_loop_temperatures), otherwise an error is
+    /// returned and the temperatures are left unchanged
+    pub fn set_loop_temperatures(&mut self,
+        loop_temperatures: &CIETLoopTemperatures)
+        -> Result<(), LoopDefinitionError> {
+
+        let branches = [
+            ("heater_branch", self.heater_branch.get_component_names(),
+             loop_temperatures.get_heater_branch_component_names()),
+            ("ctah_branch", self.ctah_branch.get_component_names(),
+             loop_temperatures.get_ctah_branch_component_names()),
+            ("dhx_branch", self.dhx_branch.get_component_names(),
+             loop_temperatures.get_dhx_branch_component_names()),
+        ];
+
+        for (branch_name, component_names, temperature_names) in branches {
+            if component_names != temperature_names {
+                return Err(LoopDefinitionError::MismatchedTemperatures(
+                        branch_name.to_string()));
+            }
+        }
+
+        self.ctah_branch.set_fluid_temperatures(
+            &loop_temperatures.ctah_branch)?;
+        self.heater_branch.set_fluid_temperatures(
+            &loop_temperatures.heater_branch)?;
+        self.dhx_branch.set_fluid_temperatures(
+            &loop_temperatures.dhx_branch)?;
+
+        return Ok(());
+    }
+
+    /// sets every component in the loop to the same fluid
+    /// temperature
+    pub fn set_uniform_fluid_temperature(&mut self,
+        fluid_temp: ThermodynamicTemperature){
+
+        self.ctah_branch.set_uniform_fluid_temperature(fluid_temp);
+        self.heater_branch.set_uniform_fluid_temperature(fluid_temp);
+        self.dhx_branch.set_uniform_fluid_temperature(fluid_temp);
+    }
+
+
+    /// returns the friction, form loss, hydrostatic and pump
+    /// contributions of every component at the flowrates and
+    /// fluid temperatures of the last calculation
+    pub fn get_pressure_breakdown(&self) -> CIETPressureBreakdown {
+        return CIETPressureBreakdown {
+            heater_branch: self.heater_branch.get_pressure_breakdown(
+                self.heater_branch_mass_flowrate),
+            ctah_branch: self.ctah_branch.get_pressure_breakdown(
+                self.ctah_branch_mass_flowrate),
+            dhx_branch: self.dhx_branch.get_pressure_breakdown(
+                self.dhx_branch_mass_flowrate),
+        };
+    }
+
+    /// returns the readings of each manometer on the board
+    /// at the flowrates and fluid temperatures of the last
+    /// calculation
+    pub fn get_manometer_readings(&self,
+        manometer_board: &ManometerBoard)
+        -> Result<Vec<ManometerReading>, LoopDefinitionError> {
+
+        return manometer_board.get_readings(
+            &self.get_pressure_breakdown());
+    }
+
+    /// sets the algorithm used to solve for the branch flowrates
+    pub fn set_solver_mode(&mut self, solver_mode: HydraulicsSolverMode){
+        self.solver_mode = solver_mode;
+    }
+
+    pub fn get_solver_mode(&self) -> HydraulicsSolverMode {
+        return self.solver_mode;
+    }
+
+    /// returns the number of calculations so far where the warm 
+    /// started newton solver failed and brent's method was used 
+    /// instead, if this climbs steadily, the newton solver is 
+    /// not doing its job
+    pub fn get_brent_fallback_count(&self) -> usize {
+        return self.brent_fallback_count;
+    }
+
+    /// solves for the branch flowrates given the current pump
+    /// pressure, valve states and loop temperatures
+    ///
+    /// returns the calculation time as well as the
+    /// ctah, heater and dhx branch flowrates in that order
+    ///
+    /// if the flowrates cannot be solved for, an error is returned
+    /// and the flowrates from the previous calculation are kept
+    pub fn calculate(&mut self) ->
+        Result<(Duration,MassRate,MassRate,MassRate), FlowSolverError>
         {
 
             // start the timer
             let start = Instant::now();
 
-            // i'm using the same algorithm from ciet digital twin v1
+            // the warm started newton solver falls back to brent's
+            // method if it does not converge
+            let solution = match self.solver_mode {
+                HydraulicsSolverMode::Brent => self.solve_with_brent(),
+                HydraulicsSolverMode::WarmStartedNewton => {
+                    match self.solve_with_warm_started_newton(None) {
+                        Ok(solution) => Ok(solution),
+                        Err(_) => {
+                            self.brent_fallback_count += 1;
+                            self.solve_with_brent()
+                        },
+                    }
+                },
+            };
+
+            let (ctah_branch_flowrate,
+                heater_branch_flowrate,
+                dhx_branch_flowrate,
+                pressure_change) = solution?;
 
-            let pressure_change_root = 
-                |pressure_change_pascals: f64| -> f64 {
+            self.ctah_branch_mass_flowrate = ctah_branch_flowrate;
+            self.heater_branch_mass_flowrate = heater_branch_flowrate;
+            self.dhx_branch_mass_flowrate = dhx_branch_flowrate;
+            self.branch_pressure_change = pressure_change;
+            self.ctah_pump_pressure = self.ctah_branch
+                .get_pump_pressure_at(ctah_branch_flowrate);
 
-                    // let's get heater branch mass flowrate
-                    // given the iterated pressure change
-                    //
-                    let test_pressure_change = 
-                        Pressure::new::<pascal>(pressure_change_pascals);
 
-                    let heater_branch_mass_flowrate = 
-                        self.heater_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
 
+            // now that i've gotten all the calculations, i can return the
+            // elapsed time to the environment
 
 
-                    let dhx_branch_mass_flowrate = 
-                        self.dhx_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
+            let elapsed_time: Duration= start.elapsed();
 
+            return Ok((elapsed_time,
+                    ctah_branch_flowrate,
+                    heater_branch_flowrate,
+                    dhx_branch_flowrate));
 
+        }
 
+    /// advances the branch flowrates through one timestep, 
+    /// accounting for the fluid inertia of each branch and the 
+    /// coast-down of the ctah pump if it is tripped
+    ///
+    /// the momentum equation of each branch is
+    ///
+    /// (sum of L/A) dm/dt = branch pressure change(m) - pressure change
+    ///
+    /// and since the branches are in parallel, they share the
+    /// same pressure change, which is whatever keeps the sum of 
+    /// the branch flowrates at zero
+    ///
+    /// the solver mode applies here as well, the warm started 
+    /// newton solver starts from the flowrates at the start of the
+    /// timestep and falls back to brent's method if it fails
+    ///
+    /// returns the calculation time as well as the
+    /// ctah, heater and dhx branch flowrates in that order
+    ///
+    /// if the flowrates cannot be solved for, an error is returned
+    /// and the flowrates from the previous timestep are kept
+    pub fn calculate_with_inertia(&mut self, timestep: Time) ->
+        Result<(Duration,MassRate,MassRate,MassRate), FlowSolverError>
+        {
 
-                    
-                    let ctah_branch_mass_flowrate = 
-                        self.ctah_branch.
-                        get_mass_flowrate_from_pressure_change(
-                            test_pressure_change);
+            let start = Instant::now();
 
-                    let total_mass_flowrate = 
-                        heater_branch_mass_flowrate 
-                        + dhx_branch_mass_flowrate
-                        + ctah_branch_mass_flowrate;
+            // a pump driven by pressure is taken to coast down
+            // from its rated speed
+            let speed_at_trip: Frequency = match self.ctah_pump_set_speed {
+                Some(pump_speed) => pump_speed,
+                None => self.ctah_branch.get_pump_curve()
+                    .map(|pump_curve| pump_curve.get_rated_speed())
+                    .unwrap_or(Frequency::new::<hertz>(0.0)),
+            };
+
+            self.ctah_pump_rotor.advance_timestep(timestep, speed_at_trip);
+            self.apply_ctah_pump_set_point();
+
+            let solution = match self.solver_mode {
+                HydraulicsSolverMode::Brent => 
+                    self.solve_with_brent_and_inertia(timestep),
+                HydraulicsSolverMode::WarmStartedNewton => {
+                    match self.solve_with_warm_started_newton(Some(timestep)) {
+                        Ok(solution) => Ok(solution),
+                        Err(_) => {
+                            self.brent_fallback_count += 1;
+                            self.solve_with_brent_and_inertia(timestep)
+                        },
+                    }
+                },
+            };
+
+            let (ctah_branch_flowrate,
+                heater_branch_flowrate,
+                dhx_branch_flowrate,
+                pressure_change) = solution?;
 
-                    return total_mass_flowrate.value;
+            self.ctah_branch_mass_flowrate = ctah_branch_flowrate;
+            self.heater_branch_mass_flowrate = heater_branch_flowrate;
+            self.dhx_branch_mass_flowrate = dhx_branch_flowrate;
+            self.branch_pressure_change = pressure_change;
+            self.ctah_pump_pressure = self.ctah_branch
+                .get_pump_pressure_at(ctah_branch_flowrate);
+
+            let elapsed_time: Duration= start.elapsed();
+
+            return Ok((elapsed_time,
+                    ctah_branch_flowrate,
+                    heater_branch_flowrate,
+                    dhx_branch_flowrate));
+        }
 
+    /// solves the implicitly discretised branch momentum equations
+    /// for the common branch pressure change with brent's method,
+    /// each evaluation solves for the branch flowrates at the end
+    /// of the timestep
+    ///
+    /// returns the ctah, heater and dhx branch flowrates and
+    /// the branch pressure change
+    fn solve_with_brent_and_inertia(&self, timestep: Time) ->
+        Result<(MassRate,MassRate,MassRate,Pressure), FlowSolverError> {
 
+            let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
+
+            let branch_solver_error: Cell<Option<FlowSolverError>> = 
+                Cell::new(None);
+
+            let branch_flowrates_at = |pressure_change: Pressure| 
+                -> Result<(MassRate,MassRate,MassRate), FlowSolverError> {
+
+                    return Ok((
+                        self.ctah_branch.try_get_mass_flowrate_with_inertia(
+                            pressure_change,
+                            self.ctah_branch_mass_flowrate,
+                            timestep)?,
+                        self.heater_branch.try_get_mass_flowrate_with_inertia(
+                            pressure_change,
+                            self.heater_branch_mass_flowrate,
+                            timestep)?,
+                        self.dhx_branch.try_get_mass_flowrate_with_inertia(
+                            pressure_change,
+                            self.dhx_branch_mass_flowrate,
+                            timestep)?,
+                    ));
                 };
 
+            let pressure_change_root =
+                |pressure_change_pascals: f64| -> f64 {
+
+                    let test_pressure_change =
+                        Pressure::new::<pascal>(pressure_change_pascals);
+
+                    match branch_flowrates_at(test_pressure_change) {
+                        Ok((ctah_flowrate, heater_flowrate, dhx_flowrate)) => {
+                            let total_mass_flowrate: MassRate = zero_flowrate
+                                + ctah_flowrate + heater_flowrate + dhx_flowrate;
+                            return total_mass_flowrate.value;
+                        },
+                        Err(error) => {
+                            branch_solver_error.set(Some(error));
+                            // stops the root finder
+                            return f64::NAN;
+                        },
+                    }
+                };
+
+            // the pressure change should not have moved far
+            // within a timestep
+            let pressure_change_result
+                = find_root_with_bracket_expansion(
+                    pressure_change_root,
+                    self.branch_pressure_change.value,
+                    50000_f64);
+
+            if let Some(branch_error) = branch_solver_error.get() {
+                return Err(branch_error);
+            }
+
+            let pressure_change =
+                Pressure::new::<pascal>(pressure_change_result?);
+
+            let (ctah_branch_flowrate,
+                heater_branch_flowrate,
+                dhx_branch_flowrate) = branch_flowrates_at(pressure_change)?;
+
+            return Ok((ctah_branch_flowrate,
+                heater_branch_flowrate,
+                dhx_branch_flowrate,
+                pressure_change));
+        }
+
+    /// solves for the common branch pressure change with a cold
+    /// started brent's method, each evaluation solves for the
+    /// branch flowrates (also with brent's method)
+    ///
+    /// returns the ctah, heater and dhx branch flowrates and
+    /// the branch pressure change
+    fn solve_with_brent(&self) ->
+        Result<(MassRate,MassRate,MassRate,Pressure), FlowSolverError> {
+
             let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
 
-            let upper_bound = self.heater_branch.
-                get_pressure_change(zero_flowrate) +
-                Pressure::new::<pascal>(50000_f64);
+            // the valves are part of each branch, so closed or 
+            // partially closed valves need no special treatment here,
+            // a closed branch simply contributes zero flow for
+            // any pressure change
+            //
+            // likewise, the dhx check valve is part of the dhx branch
+
+            // i'm using the same algorithm from ciet digital twin v1
+            //
+            // if any of the branch flowrates cannot be solved for,
+            // the error is kept here and returned once the
+            // root finder is done
+            let branch_solver_error: Cell<Option<FlowSolverError>> = 
+                Cell::new(None);
+
+            let pressure_change_root =
+                |pressure_change_pascals: f64| -> f64 {
+
+                    // let's get heater branch mass flowrate
+                    // given the iterated pressure change
+                    //
+                    let test_pressure_change =
+                        Pressure::new::<pascal>(pressure_change_pascals);
+
+                    let branch_flowrates = [
+                        self.heater_branch.
+                            try_get_mass_flowrate_from_pressure_change(
+                                test_pressure_change),
+                        self.dhx_branch.
+                            try_get_mass_flowrate_from_pressure_change(
+                                test_pressure_change),
+                        self.ctah_branch.
+                            try_get_mass_flowrate_from_pressure_change(
+                                test_pressure_change),
+                    ];
+
+                    let mut total_mass_flowrate = zero_flowrate;
+
+                    for branch_flowrate in branch_flowrates {
+                        match branch_flowrate {
+                            Ok(mass_flowrate) => {
+                                total_mass_flowrate += mass_flowrate;
+                            },
+                            Err(error) => {
+                                branch_solver_error.set(Some(error));
+                                // stops the root finder
+                                return f64::NAN;
+                            },
+                        }
+                    }
+
+                    return total_mass_flowrate.value;
 
-            let lower_bound = self.heater_branch.
-                get_pressure_change(zero_flowrate) +
-                Pressure::new::<pascal>(-50000_f64);
 
+                };
 
-            let mut convergency = SimpleConvergency { eps:1e-9_f64, max_iter:30 };
+            // the root is searched for starting at
+            // 50000 pascals plus or minus the 
+            // hydrostatic pressure change of the heater,
+            // this is widened if the pump pressure is large
+            let heater_hydrostatic_pressure = self.heater_branch.
+                get_pressure_change(zero_flowrate);
 
-            let pressure_change_value 
-                = find_root_brent(
-                    upper_bound.value,
-                    lower_bound.value,
-                    &pressure_change_root,
-                    &mut convergency).unwrap();
+            let pressure_change_result
+                = find_root_with_bracket_expansion(
+                    pressure_change_root,
+                    heater_hydrostatic_pressure.value,
+                    50000_f64);
 
-            //let pressure_change_value = 0.0;
+            // errors within the branches take precedence as they
+            // are the cause of the loop solve failing
+            if let Some(branch_error) = branch_solver_error.get() {
+                return Err(branch_error);
+            }
 
-            let pressure_change = 
-                Pressure::new::<pascal>(pressure_change_value);
+            let pressure_change =
+                Pressure::new::<pascal>(pressure_change_result?);
 
             let ctah_branch_flowrate = self.ctah_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
             let heater_branch_flowrate = self.heater_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
             // again, check valve behaviour algorithm
 
             let dhx_branch_flowrate = self.dhx_branch.
-                get_mass_flowrate_from_pressure_change(pressure_change);
+                try_get_mass_flowrate_from_pressure_change(pressure_change)?;
 
+            return Ok((ctah_branch_flowrate,
+                heater_branch_flowrate,
+                dhx_branch_flowrate,
+                pressure_change));
+        }
 
-            self.ctah_branch_mass_flowrate = ctah_branch_flowrate;
-            self.heater_branch_mass_flowrate = heater_branch_flowrate;
-            self.dhx_branch_mass_flowrate = dhx_branch_flowrate;
+    /// solves for the branch flowrates and the common branch
+    /// pressure change simultaneously using newton's method,
+    /// starting from the results of the previous calculation
+    ///
+    /// the unknowns are the flowrate of each flowing branch and the
+    /// branch pressure change, the equations are
+    ///
+    /// branch_pressure_change(m_i) = pressure_change (for each branch)
+    /// sum of m_i = 0
+    ///
+    /// the jacobian of each branch pressure change curve is
+    /// obtained by finite differencing, and since each branch 
+    /// only depends on its own flowrate, the newton step can 
+    /// be solved for directly without a matrix solver
+    ///
+    /// the dhx check valve is not differentiable, so we first 
+    /// guess whether it is open or shut from the previous 
+    /// pressure change and, if the converged solution contradicts 
+    /// the guess, try again with the other guess
+    ///
+    /// with a timestep, the branch momentum equations are 
+    /// discretised implicitly as in solve_with_brent_and_inertia,
+    /// so each branch pressure change curve has the pressure needed 
+    /// to accelerate its fluid from the previous flowrate taken off
+    ///
+    /// returns the ctah, heater and dhx branch flowrates and
+    /// the branch pressure change
+    fn solve_with_warm_started_newton(&self, timestep: Option<Time>) ->
+        Result<(MassRate,MassRate,MassRate,Pressure), FlowSolverError> {
 
+            let zero_flowrate = MassRate::new::<kilogram_per_second>(0.0);
 
+            // the check valve shuts once the pressure change 
+            // exceeds that of the dhx branch at zero flow, without
+            // inertia this is its hydrostatic pressure change
+            let dhx_shut_off_pressure = self.get_branch_pressure_change_at(
+                DHX_BRANCH_INDEX, zero_flowrate, timestep);
 
-            // now that i've gotten all the calculations, i can return the
-            // elapsed time to the environment
+            let check_valve_open_guess: bool = 
+                self.branch_pressure_change <= dhx_shut_off_pressure;
 
+            let mut newton_result = self.newton_iterations(
+                check_valve_open_guess, dhx_shut_off_pressure, timestep);
 
-            let elapsed_time: Duration= start.elapsed();
+            if newton_result.is_err() {
+                newton_result = self.newton_iterations(
+                    !check_valve_open_guess, dhx_shut_off_pressure, timestep);
+            }
 
-            return (elapsed_time,
-                    ctah_branch_flowrate,
-                    heater_branch_flowrate,
-                    dhx_branch_flowrate);
+            return newton_result;
+        }
+
+    /// pressure change across a branch (ctah, heater and dhx in 
+    /// that order) at a given flowrate, less the pressure needed to 
+    /// accelerate the fluid from the previous flowrate within the 
+    /// timestep, if there is one
+    fn get_branch_pressure_change_at(&self,
+        branch_index: usize,
+        mass_flowrate: MassRate,
+        timestep: Option<Time>) -> Pressure {
+
+            let (branch, previous_mass_flowrate): (&CIETBranch, MassRate) =
+                match branch_index {
+                    CTAH_BRANCH_INDEX => (&self.ctah_branch, 
+                        self.ctah_branch_mass_flowrate),
+                    HEATER_BRANCH_INDEX => (&self.heater_branch, 
+                        self.heater_branch_mass_flowrate),
+                    _ => (&self.dhx_branch, 
+                        self.dhx_branch_mass_flowrate),
+                };
+
+            let pressure_change = branch.get_pressure_change(mass_flowrate);
+
+            match timestep {
+                Some(timestep) => {
+                    let inertial_pressure: Pressure = branch.get_length_to_area_sum()
+                        * (mass_flowrate - previous_mass_flowrate) / timestep;
+                    return pressure_change - inertial_pressure;
+                },
+                None => return pressure_change,
+            }
+        }
+
+    /// newton iterations for solve_with_warm_started_newton with
+    /// the dhx check valve assumed to be open or shut
+    fn newton_iterations(&self, 
+        check_valve_open: bool,
+        dhx_shut_off_pressure: Pressure,
+        timestep: Option<Time>) ->
+        Result<(MassRate,MassRate,MassRate,Pressure), FlowSolverError> {
+
+            // branch order is ctah, heater, dhx
+            let branch_pressure_change_at = 
+                |branch_index: usize, mass_flowrate_kg_per_s: f64| -> f64 {
+                    return self.get_branch_pressure_change_at(
+                        branch_index,
+                        MassRate::new::<kilogram_per_second>(
+                            mass_flowrate_kg_per_s),
+                        timestep).value;
+                };
 
+            // branches with shut valves have zero flow and
+            // are left out of the newton iterations
+            let branch_flowing: [bool; 3] = [
+                self.ctah_branch.is_valve_open(),
+                self.heater_branch.is_valve_open(),
+                self.dhx_branch.is_valve_open() && check_valve_open,
+            ];
+
+            if !branch_flowing.contains(&true) {
+                // pressure change is undefined if nothing flows,
+                // leave this to brent's method
+                return Err(FlowSolverError {
+                    kind: FlowSolverErrorKind::NoConvergence,
+                    residual: 0.0,
+                    iterations: 0,
+                });
+            }
+
+            // warm start from the previous calculation
+            let mut mass_flowrates: [f64; 3] = [
+                self.ctah_branch_mass_flowrate.value,
+                self.heater_branch_mass_flowrate.value,
+                self.dhx_branch_mass_flowrate.value,
+            ];
+            let mut pressure_change: f64 = self.branch_pressure_change.value;
+
+            for index in 0..3 {
+                if !branch_flowing[index] {
+                    mass_flowrates[index] = 0.0;
+                }
+            }
+
+            let mut mass_balance_residual: f64 = f64::INFINITY;
+            // number of newton steps taken before convergence
+            let mut iterations_taken: usize = 0;
+
+            for iteration in 0..NEWTON_MAX_ITERATIONS {
+
+                let mut pressure_residuals: [f64; 3] = [0.0; 3];
+                let mut gradients: [f64; 3] = [1.0; 3];
+
+                for index in 0..3 {
+                    if !branch_flowing[index] {
+                        continue;
+                    }
+
+                    let branch_pressure_change = branch_pressure_change_at(
+                        index, mass_flowrates[index]);
+
+                    let perturbed_pressure_change = branch_pressure_change_at(
+                        index, mass_flowrates[index] 
+                        + NEWTON_FINITE_DIFFERENCE_STEP_KG_PER_S);
+
+                    let gradient = (perturbed_pressure_change 
+                        - branch_pressure_change)
+                        /NEWTON_FINITE_DIFFERENCE_STEP_KG_PER_S;
+
+                    if !gradient.is_finite() || gradient == 0.0 
+                        || !branch_pressure_change.is_finite() {
+                        return Err(FlowSolverError {
+                            kind: FlowSolverErrorKind::NonFiniteResidual,
+                            residual: f64::NAN,
+                            iterations: iteration,
+                        });
+                    }
+
+                    pressure_residuals[index] = 
+                        branch_pressure_change - pressure_change;
+                    gradients[index] = gradient;
+                }
+
+                mass_balance_residual = mass_flowrates.iter().sum();
+
+                let largest_pressure_residual = pressure_residuals.iter()
+                    .fold(0.0_f64, |largest, residual| 
+                        largest.max(residual.abs()));
+
+                let pressure_tolerance: f64 = NEWTON_PRESSURE_TOLERANCE_PASCALS
+                    .max(NEWTON_RELATIVE_PRESSURE_TOLERANCE * pressure_change.abs());
+
+                if mass_balance_residual.abs() < FLOW_SOLVER_TOLERANCE 
+                    && largest_pressure_residual < pressure_tolerance {
+                    iterations_taken = iteration;
+                    break;
+                }
+
+                // each branch is linearised as
+                // dp_i + g_i * dm_i = pressure_change + d_pressure_change
+                //
+                // so dm_i = (d_pressure_change - r_i)/g_i
+                // substituting into the mass balance gives the 
+                // pressure change step
+                let mut sum_of_inverse_gradients: f64 = 0.0;
+                let mut sum_of_scaled_residuals: f64 = 0.0;
+
+                for index in 0..3 {
+                    if !branch_flowing[index] {
+                        continue;
+                    }
+                    sum_of_inverse_gradients += 1.0/gradients[index];
+                    sum_of_scaled_residuals += 
+                        pressure_residuals[index]/gradients[index];
+                }
+
+                let pressure_change_step = 
+                    (sum_of_scaled_residuals - mass_balance_residual)
+                    /sum_of_inverse_gradients;
+
+                for index in 0..3 {
+                    if !branch_flowing[index] {
+                        continue;
+                    }
+                    mass_flowrates[index] += 
+                        (pressure_change_step - pressure_residuals[index])
+                        /gradients[index];
+                }
+
+                pressure_change += pressure_change_step;
+
+                if iteration == NEWTON_MAX_ITERATIONS - 1 {
+                    return Err(FlowSolverError {
+                        kind: FlowSolverErrorKind::NoConvergence,
+                        residual: mass_balance_residual,
+                        iterations: iteration + 1,
+                    });
+                }
+            }
+
+            // now check that the check valve guess was right,
+            // forward flow (top to bottom) means the check valve 
+            // is open, and the check valve shuts if the pressure 
+            // change is larger than that of the dhx branch at 
+            // zero flow
+            let pressure_change = Pressure::new::<pascal>(pressure_change);
+
+            let check_valve_guess_consistent: bool = if check_valve_open {
+                mass_flowrates[2] >= 0.0
+            } else {
+                !self.dhx_branch.is_valve_open() 
+                    || pressure_change >= dhx_shut_off_pressure
+            };
+
+            if !check_valve_guess_consistent {
+                return Err(FlowSolverError {
+                    kind: FlowSolverErrorKind::NoConvergence,
+                    residual: mass_balance_residual,
+                    iterations: iterations_taken,
+                });
+            }
+
+            return Ok((
+                MassRate::new::<kilogram_per_second>(mass_flowrates[0]),
+                MassRate::new::<kilogram_per_second>(mass_flowrates[1]),
+                MassRate::new::<kilogram_per_second>(mass_flowrates[2]),
+                pressure_change));
         }
 
 
     // constructor
 
-    pub fn new(ctah_branch: CTAHBranch<'ciet_collection_lifetime>,
-               heater_branch: HeaterBranch<'ciet_collection_lifetime>,
-               dhx_branch: DHXBranch<'ciet_collection_lifetime>) -> Self {
-
-        // again here we have an empty vector and we move ownership of
-        // the dhx branch to this vector
-        //
-
-        
-        return Self { 
-            ctah_pump_pressure: Pressure::new::<pascal>(0.0), 
-            ctah_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
-            dhx_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
-            heater_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0), 
-            super_collection_vector_immutable: vec![], 
-            ctah_branch: ctah_branch, 
-            heater_branch: heater_branch, 
-            dhx_branch: dhx_branch 
+    pub fn new(ctah_branch: CIETBranch,
+               heater_branch: CIETBranch,
+               dhx_branch: CIETBranch,
+               thermal_components: CIETThermalComponentNames) -> Self {
+
+        // the pump pressure of the facility follows that of
+        // the ctah branch
+        let ctah_pump_pressure = ctah_branch.get_pump_pressure();
+
+        return Self {
+            ctah_pump_pressure,
+            ctah_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0),
+            dhx_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0),
+            heater_branch_mass_flowrate: MassRate::new::<kilogram_per_second>(0.0),
+            branch_pressure_change: Pressure::new::<pascal>(0.0),
+            solver_mode: HydraulicsSolverMode::WarmStartedNewton,
+            brent_fallback_count: 0,
+            ctah_pump_set_pressure: ctah_pump_pressure,
+            ctah_pump_set_speed: None,
+            ctah_pump_rotor: PumpRotor::default(),
+            ctah_branch: ctah_branch,
+            heater_branch: heater_branch,
+            dhx_branch: dhx_branch,
+            thermal_components: thermal_components,
         }
 
     }
 
+    /// constructs the facility from a loop definition, eg. one
+    /// read from a TOML or JSON file
+    pub fn from_definition(loop_definition: &CIETLoopDefinition)
+        -> Result<Self, LoopDefinitionError> {
 
-}
+        loop_definition.validate()?;
 
-/// the CIET isothermal facility must implement the parallel super
-/// collection supertrait i just defined
-impl<'ciet_object_lifetime> 
-ParallelSuperCollection<'ciet_object_lifetime> for CIETIsothermalFacility<'ciet_object_lifetime> {}
+        let ctah_branch = loop_definition.build_ctah_branch()?;
+        let heater_branch = loop_definition.build_heater_branch()?;
+        let dhx_branch = loop_definition.build_dhx_branch()?;
 
-impl<'ciet_object_lifetime> FluidComponentSuperCollectionParallelAssociatedFunctions for 
-CIETIsothermalFacility<'ciet_object_lifetime> {}
+        let mut ciet_facility = Self::new(ctah_branch, heater_branch, 
+            dhx_branch, loop_definition.thermal_components.clone());
+        ciet_facility.ctah_pump_rotor = loop_definition.build_ctah_pump_rotor();
 
-impl<'ciet_object_lifetime> FluidComponentSuperCollection<'ciet_object_lifetime> 
-for CIETIsothermalFacility<'ciet_object_lifetime> {
+        return Ok(ciet_facility);
+    }
 
-    fn get_immutable_vector(&self) 
-        -> &Vec<&'ciet_object_lifetime dyn FluidComponentCollectionMethods>{
+    /// constructs the facility from a .toml or .json loop
+    /// definition file
+    pub fn from_definition_file<P: AsRef<Path>>(path: P)
+        -> Result<Self, LoopDefinitionError> {
 
-            return &self.super_collection_vector_immutable;
-        }
-
-    fn set_vector(
-        &mut self,
-        fluid_component_super_vector: 
-        Vec<&'ciet_object_lifetime dyn FluidComponentCollectionMethods>){
-        self.super_collection_vector_immutable = fluid_component_super_vector;
+        let loop_definition = CIETLoopDefinition::from_file(path)?;
+        return Self::from_definition(&loop_definition);
+    }
 
+    /// returns a CIETLoopTemperatures with every component at
+    /// the same temperature, sized to match the branches of
+    /// this facility (which may differ from the default CIET
+    /// layout if a loop definition file is used)
+    pub fn get_uniform_loop_temperatures(&self,
+        fluid_temp: ThermodynamicTemperature) -> CIETLoopTemperatures {
+
+        return CIETLoopTemperatures::from_component_names(
+            self.heater_branch.get_component_names(),
+            self.ctah_branch.get_component_names(),
+            self.dhx_branch.get_component_names(),
+            self.thermal_components.clone(),
+            fluid_temp);
     }
 
+
+}
+
+impl Default for CIETIsothermalFacility {
+    /// CIET at 21C with all valves open and zero pump pressure
+    fn default() -> Self {
+        return Self::from_definition(&CIETLoopDefinition::default())
+            .expect("default CIET loop definition should be valid");
+    }
 }
 
-impl<'ciet_object_lifetime> 
-FluidComponentCollectionMethods for CIETIsothermalFacility<'ciet_object_lifetime> {
+impl FluidComponentSuperCollectionParallelAssociatedFunctions for
+CIETIsothermalFacility {}
+
+impl FluidComponentCollectionMethods for CIETIsothermalFacility {
 
 
     /// calculates pressure change when given a mass flowrate
     fn get_pressure_change(
-        &self, 
+        &self,
         fluid_mass_flowrate: MassRate) -> Pressure{
-        let fluid_component_collection_vector = 
-            self.get_immutable_vector();
-
-        let pressure_change = 
+        let fluid_component_collection_vector:
+            Vec<&dyn FluidComponentCollectionMethods> = vec![
+                &self.ctah_branch,
+                &self.heater_branch,
+                &self.dhx_branch,
+            ];
+
+        let pressure_change =
             <Self as FluidComponentSuperCollectionParallelAssociatedFunctions>
             ::calculate_pressure_change_from_mass_flowrate(
-                fluid_mass_flowrate, 
-                fluid_component_collection_vector);
+                fluid_mass_flowrate,
+                &fluid_component_collection_vector);
 
         return pressure_change;
     }
@@ -278,22 +1041,21 @@ FluidComponentCollectionMethods for CIETIsothermalFacility<'ciet_object_lifetime
         &self,
         pressure_change: Pressure) -> MassRate{
 
-        let fluid_component_collection_vector = 
-            self.get_immutable_vector();
-        
-        let mass_flowrate = 
+        let fluid_component_collection_vector:
+            Vec<&dyn FluidComponentCollectionMethods> = vec![
+                &self.ctah_branch,
+                &self.heater_branch,
+                &self.dhx_branch,
+            ];
+
+        let mass_flowrate =
             <Self as FluidComponentSuperCollectionParallelAssociatedFunctions>
             ::calculate_mass_flowrate_from_pressure_change(
-                pressure_change, 
-                fluid_component_collection_vector);
+                pressure_change,
+                &fluid_component_collection_vector);
 
         return mass_flowrate;
     }
 
 
 }
-
-
-
-
-
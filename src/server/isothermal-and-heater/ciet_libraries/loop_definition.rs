@@ -0,0 +1,807 @@
+use std::fmt;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use crate::therminol_pipe::TherminolPipe;
+use crate::therminol_component::TherminolCustomComponent;
+use crate::correlation::Correlation;
+use crate::{PumpCurve, PumpRotor, PumpRotorDefinition, CIETBranch};
+
+/// the default CIET primary loop layout, this is what the
+/// branches are built from unless the user supplies their own
+/// loop definition file
+pub const DEFAULT_CIET_LOOP_DEFINITION_TOML: &str =
+    include_str!("ciet_loop_definition.toml");
+
+/// the default CIET DRACS loop layout
+pub const DEFAULT_DRACS_LOOP_DEFINITION_TOML: &str =
+    include_str!("dracs_loop_definition.toml");
+
+/// the elevation changes around a closed loop must sum to
+/// within this much of zero
+pub const LOOP_ELEVATION_TOLERANCE_METERS: f64 = 1e-3;
+
+/// describes the CIET primary loop, ie. the components in each
+/// branch listed from the top of the branch to the bottom
+///
+/// this can be read from (and written to) a TOML or JSON file
+/// so that modified loop configurations can be studied without
+/// recompiling, see ciet_loop_definition.toml for the default
+/// CIET layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CIETLoopDefinition {
+    /// name of the component in the ctah branch whose internal
+    /// pressure source is set by the ctah pump pressure
+    pub ctah_pump_name: String,
+    /// pump curve followed by the ctah pump when it is driven
+    /// by speed, the default CIET pump curve is used if this
+    /// is left out
+    #[serde(default)]
+    pub ctah_pump_curve: PumpCurve,
+    /// rotor of the ctah pump, which sets how fast it coasts
+    /// down after a trip, the provisional default parameters
+    /// are used if this is left out
+    #[serde(default)]
+    pub ctah_pump_rotor: PumpRotorDefinition,
+    /// components modelled by the heater, CTAH and DHX thermal
+    /// models, the default CIET components are used if this is
+    /// left out
+    #[serde(default)]
+    pub thermal_components: CIETThermalComponentNames,
+    /// heater branch components, top to bottom
+    pub heater_branch: Vec<CIETComponentDefinition>,
+    /// ctah branch components, top to bottom
+    pub ctah_branch: Vec<CIETComponentDefinition>,
+    /// dhx branch components, top to bottom
+    pub dhx_branch: Vec<CIETComponentDefinition>,
+}
+
+/// names of the components which are modelled by the heater,
+/// CTAH and DHX thermal models rather than as insulated pipes,
+/// the thermally modelled components of each branch must be 
+/// next to each other, but may be listed in any order
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CIETThermalComponentNames {
+    /// heater branch, static mixer MX-10
+    pub static_mixer_10: String,
+    /// heater branch, the pipe attached to static mixer MX-10
+    pub static_mixer_10_pipe: String,
+    /// heater branch
+    pub heater_top_head: String,
+    /// heater branch, the heated section of the heater
+    pub heated_section: String,
+    /// heater branch
+    pub heater_bottom_head: String,
+    /// ctah branch
+    pub ctah_vertical: String,
+    /// ctah branch
+    pub ctah_horizontal: String,
+    /// dhx branch
+    pub dhx_shell_side: String,
+}
+
+/// describes the CIET DRACS (direct reactor auxiliary cooling
+/// system) loop, a single closed loop driven by natural
+/// circulation
+///
+/// the components are listed in the direction of positive
+/// flow, ie. up through the DHX tube side, along the hot leg
+/// to the TCHX, then down the cold leg back to the DHX
+///
+/// see dracs_loop_definition.toml for the default layout
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DRACSLoopDefinition {
+    /// name of the DHX tube side component, this is where
+    /// heat is picked up from the primary loop
+    pub dhx_tube_side_name: String,
+    /// names of the TCHX components, this is where heat is
+    /// rejected to the air, listed in the direction of flow
+    pub tchx_names: Vec<String>,
+    /// loop components, in the direction of positive flow
+    pub components: Vec<CIETComponentDefinition>,
+}
+
+/// describes a single pipe or component within a branch
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CIETComponentDefinition {
+    /// a pipe with a fixed form loss K, the darcy friction factor
+    /// comes from the churchill correlation
+    Pipe {
+        name: String,
+        incline_angle_degrees: f64,
+        component_length_meters: f64,
+        hydraulic_diameter_meters: f64,
+        form_loss_k: f64,
+        absolute_roughness_millimeters: f64,
+    },
+    /// a component with custom K and darcy friction factor
+    /// correlations, eg. static mixers, flowmeters, the heater
+    /// and the heat exchangers
+    ///
+    /// K and the darcy friction factor take the sign of the
+    /// flow unless the reverse_flow form is used
+    CustomComponent {
+        name: String,
+        incline_angle_degrees: f64,
+        component_length_meters: f64,
+        cross_sectional_area_square_meters: f64,
+        hydraulic_diameter_meters: f64,
+        absolute_roughness_millimeters: f64,
+        custom_k: Correlation,
+        custom_darcy: Correlation,
+    },
+}
+
+/// errors when reading a loop definition or building branches
+/// from it
+#[derive(Debug)]
+pub enum LoopDefinitionError {
+    /// the file could not be read or written
+    Io(std::io::Error),
+    /// the TOML could not be parsed
+    TomlParse(toml::de::Error),
+    /// the loop definition could not be written as TOML
+    TomlWrite(toml::ser::Error),
+    /// the JSON could not be parsed or written
+    Json(serde_json::Error),
+    /// the file extension is neither .toml nor .json
+    UnknownFileFormat(String),
+    /// a branch has no components in it
+    EmptyBranch(String),
+    /// a component which the loop needs (eg. the ctah pump)
+    /// could not be found
+    MissingComponent(String),
+    /// the downstream tap of a manometer is above its
+    /// upstream tap
+    InvalidManometer(String),
+    /// the elevation changes around a closed loop do not
+    /// sum to zero (in meters)
+    UnbalancedElevation(f64),
+    /// the fluid temperatures given for a branch do not match
+    /// its components, either in number or by name
+    MismatchedTemperatures(String),
+    /// the ctah pump curve has fewer than two points, flowrates
+    /// which do not increase or a rated speed which is not
+    /// positive
+    InvalidPumpCurve(String),
+    /// the ctah pump rotor moment of inertia or rated torque
+    /// is not positive
+    InvalidPumpRotor(String),
+    /// the thermally modelled components of a branch are
+    /// repeated or not next to each other
+    InvalidThermalComponents(String),
+    /// a K or darcy friction factor correlation has an empty
+    /// or unordered table, or a negative roughness
+    InvalidCorrelation(String),
+}
+
+impl fmt::Display for LoopDefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoopDefinitionError::Io(error) =>
+                write!(f, "could not read loop definition: {}", error),
+            LoopDefinitionError::TomlParse(error) =>
+                write!(f, "could not parse loop definition TOML: {}", error),
+            LoopDefinitionError::TomlWrite(error) =>
+                write!(f, "could not write loop definition TOML: {}", error),
+            LoopDefinitionError::Json(error) =>
+                write!(f, "could not parse or write loop definition JSON: {}", error),
+            LoopDefinitionError::UnknownFileFormat(path) =>
+                write!(f, "loop definition {} must be a .toml or .json file", path),
+            LoopDefinitionError::EmptyBranch(branch) =>
+                write!(f, "{} has no components", branch),
+            LoopDefinitionError::MissingComponent(name) =>
+                write!(f, "component {} not found in loop definition", name),
+            LoopDefinitionError::InvalidManometer(name) =>
+                write!(f, "manometer {} has its downstream tap above \
+                    its upstream tap", name),
+            LoopDefinitionError::UnbalancedElevation(elevation_change) =>
+                write!(f, "loop elevation changes sum to {} m \
+                    rather than zero", elevation_change),
+            LoopDefinitionError::MismatchedTemperatures(branch) =>
+                write!(f, "fluid temperatures do not match the \
+                    components of {}", branch),
+            LoopDefinitionError::InvalidPumpCurve(message) =>
+                write!(f, "invalid ctah pump curve: {}", message),
+            LoopDefinitionError::InvalidPumpRotor(message) =>
+                write!(f, "invalid ctah pump rotor: {}", message),
+            LoopDefinitionError::InvalidThermalComponents(message) =>
+                write!(f, "invalid thermal components: {}", message),
+            LoopDefinitionError::InvalidCorrelation(message) =>
+                write!(f, "invalid correlation: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for LoopDefinitionError {}
+
+impl From<std::io::Error> for LoopDefinitionError {
+    fn from(error: std::io::Error) -> Self {
+        return LoopDefinitionError::Io(error);
+    }
+}
+
+impl From<toml::de::Error> for LoopDefinitionError {
+    fn from(error: toml::de::Error) -> Self {
+        return LoopDefinitionError::TomlParse(error);
+    }
+}
+
+impl From<toml::ser::Error> for LoopDefinitionError {
+    fn from(error: toml::ser::Error) -> Self {
+        return LoopDefinitionError::TomlWrite(error);
+    }
+}
+
+impl From<serde_json::Error> for LoopDefinitionError {
+    fn from(error: serde_json::Error) -> Self {
+        return LoopDefinitionError::Json(error);
+    }
+}
+
+impl CIETLoopDefinition {
+
+    /// reads a loop definition from a TOML string
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = toml::from_str(toml_str)?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// reads a loop definition from a JSON string
+    pub fn from_json_str(json_str: &str) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = serde_json::from_str(json_str)?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// reads a loop definition from a .toml or .json file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = read_definition_file(path.as_ref())?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// writes the loop definition to a .toml or .json file
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LoopDefinitionError> {
+        return write_definition_file(self, path.as_ref());
+    }
+
+    /// checks that every branch has components with usable
+    /// correlations, that the ctah pump can be found in the ctah
+    /// branch, that its pump curve and rotor are usable and that the
+    /// thermally modelled components of each branch can be found
+    /// next to each other
+    pub fn validate(&self) -> Result<(), LoopDefinitionError> {
+
+        let branches = [
+            ("heater_branch", &self.heater_branch),
+            ("ctah_branch", &self.ctah_branch),
+            ("dhx_branch", &self.dhx_branch),
+        ];
+
+        for (branch_name, branch) in branches {
+            if branch.is_empty() {
+                return Err(LoopDefinitionError::EmptyBranch(
+                        branch_name.to_string()));
+            }
+
+            for component in branch {
+                component.validate_correlations()?;
+            }
+        }
+
+        let ctah_pump_found = self.ctah_branch.iter().any(
+            |component| component.get_name() == self.ctah_pump_name);
+
+        if !ctah_pump_found {
+            return Err(LoopDefinitionError::MissingComponent(
+                    self.ctah_pump_name.clone()));
+        }
+
+        // the pump curve is deserialized directly rather than
+        // through PumpCurve::new, so it is checked here
+        self.ctah_pump_curve.validate()?;
+        self.ctah_pump_rotor.validate()?;
+
+        let thermal_components = [
+            ("heater_branch", &self.heater_branch,
+             self.thermal_components.get_heater_branch_names().to_vec()),
+            ("ctah_branch", &self.ctah_branch,
+             self.thermal_components.get_ctah_branch_names().to_vec()),
+            ("dhx_branch", &self.dhx_branch,
+             self.thermal_components.get_dhx_branch_names().to_vec()),
+        ];
+
+        for (branch_name, branch, component_names) in thermal_components {
+            validate_thermal_components(branch_name, branch, &component_names)?;
+        }
+
+        return Ok(());
+    }
+
+    /// builds the ctah pump rotor, which coasts down from the
+    /// rated speed of the ctah pump curve
+    pub fn build_ctah_pump_rotor(&self) -> PumpRotor {
+        return self.ctah_pump_rotor.build(
+            self.ctah_pump_curve.get_rated_speed());
+    }
+
+    /// builds the heater branch, from pipe 4 at the top of the
+    /// branch to pipe 18 at the bottom for the default CIET layout
+    pub fn build_heater_branch(&self) -> Result<CIETBranch, LoopDefinitionError> {
+        return CIETBranch::from_definition("heater_branch", &self.heater_branch);
+    }
+
+    /// builds the ctah branch, from branch 5 at the top of the
+    /// branch to branch 17 at the bottom for the default CIET layout,
+    /// the ctah pump follows ctah_pump_curve when driven by speed
+    pub fn build_ctah_branch(&self) -> Result<CIETBranch, LoopDefinitionError> {
+        let mut ctah_branch = CIETBranch::from_definition(
+            "ctah_branch", &self.ctah_branch)?;
+        ctah_branch.set_pump(&self.ctah_pump_name,
+            self.ctah_pump_curve.clone())?;
+
+        return Ok(ctah_branch);
+    }
+
+    /// builds the dhx branch, from pipe 26 at the top of the
+    /// branch to pipe 19 at the bottom for the default CIET layout,
+    /// the dhx branch has a check valve so that flow only goes
+    /// from top to bottom
+    pub fn build_dhx_branch(&self) -> Result<CIETBranch, LoopDefinitionError> {
+        let mut dhx_branch = CIETBranch::from_definition(
+            "dhx_branch", &self.dhx_branch)?;
+        dhx_branch.set_check_valve(true);
+
+        return Ok(dhx_branch);
+    }
+}
+
+impl CIETThermalComponentNames {
+
+    /// heater branch components modelled by the heater thermal
+    /// model
+    pub fn get_heater_branch_names(&self) -> [&str; 5] {
+        return [
+            &self.static_mixer_10,
+            &self.static_mixer_10_pipe,
+            &self.heater_top_head,
+            &self.heated_section,
+            &self.heater_bottom_head,
+        ];
+    }
+
+    /// ctah branch components modelled by the CTAH thermal model
+    pub fn get_ctah_branch_names(&self) -> [&str; 2] {
+        return [&self.ctah_vertical, &self.ctah_horizontal];
+    }
+
+    /// dhx branch components modelled by the DHX thermal model
+    pub fn get_dhx_branch_names(&self) -> [&str; 1] {
+        return [&self.dhx_shell_side];
+    }
+}
+
+impl Default for CIETThermalComponentNames {
+    /// the heater, CTAH and DHX components of the CIET primary loop
+    fn default() -> Self {
+        return Self {
+            static_mixer_10: "static_mixer_10_label_2".to_string(),
+            static_mixer_10_pipe: "pipe_2a_static_mixer".to_string(),
+            heater_top_head: "heater_top_head_label_1a".to_string(),
+            heated_section: "heater_version_1_label_1".to_string(),
+            heater_bottom_head: "heater_bottom_head_label_1b".to_string(),
+            ctah_vertical: "ctah_vertical_label_7a".to_string(),
+            ctah_horizontal: "ctah_horizontal_label_7b".to_string(),
+            dhx_shell_side: "dhx_shell_side_label_24".to_string(),
+        };
+    }
+}
+
+/// splits a branch into the components above its thermally
+/// modelled components, the thermally modelled components 
+/// themselves and the components below them, each listed from
+/// top to bottom
+///
+/// the thermally modelled components are taken to be next to
+/// each other (see CIETLoopDefinition::validate), if none of them 
+/// are in the branch, the whole branch is taken to be above them
+pub fn split_at_thermal_components(branch: &[CIETComponentDefinition],
+    thermal_component_names: &[&str]) -> (Vec<CIETComponentDefinition>,
+    Vec<CIETComponentDefinition>, Vec<CIETComponentDefinition>) {
+
+    let is_thermal_component = |component: &CIETComponentDefinition| {
+        thermal_component_names.contains(&component.get_name())
+    };
+
+    let first_position = branch.iter().position(is_thermal_component);
+    let last_position = branch.iter().rposition(is_thermal_component);
+
+    match (first_position, last_position) {
+        (Some(first), Some(last)) => return (
+            branch[..first].to_vec(),
+            branch[first..=last].to_vec(),
+            branch[last + 1..].to_vec()),
+        _ => return (branch.to_vec(), vec![], vec![]),
+    }
+}
+
+// checks that each thermally modelled component of a branch
+// appears in it exactly once and that they are next to each
+// other, with no pipes in between
+fn validate_thermal_components(branch_name: &str,
+    branch: &[CIETComponentDefinition],
+    thermal_component_names: &[&str]) -> Result<(), LoopDefinitionError> {
+
+    for (index, component_name) in thermal_component_names.iter().enumerate() {
+        let occurrences = branch.iter()
+            .filter(|component| component.get_name() == *component_name)
+            .count();
+
+        if occurrences == 0 {
+            return Err(LoopDefinitionError::MissingComponent(
+                    component_name.to_string()));
+        }
+
+        if occurrences > 1 || thermal_component_names[..index].contains(component_name) {
+            return Err(LoopDefinitionError::InvalidThermalComponents(format!(
+                        "{} is repeated in {}", component_name, branch_name)));
+        }
+    }
+
+    let (_, thermal_components, _) = 
+        split_at_thermal_components(branch, thermal_component_names);
+
+    if thermal_components.len() != thermal_component_names.len() {
+        return Err(LoopDefinitionError::InvalidThermalComponents(format!(
+                    "the thermally modelled components of {} are not \
+                    next to each other", branch_name)));
+    }
+
+    return Ok(());
+}
+
+impl Default for CIETLoopDefinition {
+    /// the CIET primary loop as built
+    fn default() -> Self {
+        return Self::from_toml_str(DEFAULT_CIET_LOOP_DEFINITION_TOML)
+            .expect("default CIET loop definition should be valid");
+    }
+}
+
+impl DRACSLoopDefinition {
+
+    /// reads a DRACS loop definition from a TOML string
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = toml::from_str(toml_str)?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// reads a DRACS loop definition from a JSON string
+    pub fn from_json_str(json_str: &str) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = serde_json::from_str(json_str)?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// reads a DRACS loop definition from a .toml or .json file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, LoopDefinitionError> {
+        let loop_definition: Self = read_definition_file(path.as_ref())?;
+        loop_definition.validate()?;
+        return Ok(loop_definition);
+    }
+
+    /// writes the DRACS loop definition to a .toml or .json file
+    pub fn write_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), LoopDefinitionError> {
+        return write_definition_file(self, path.as_ref());
+    }
+
+    /// checks that the loop has components with usable
+    /// correlations, that the DHX and TCHX can be found in it,
+    /// and that it closes on itself (the elevation changes around
+    /// the loop sum to zero)
+    pub fn validate(&self) -> Result<(), LoopDefinitionError> {
+
+        if self.components.is_empty() {
+            return Err(LoopDefinitionError::EmptyBranch(
+                    "dracs_loop".to_string()));
+        }
+
+        for component in &self.components {
+            component.validate_correlations()?;
+        }
+
+        let required_names = std::iter::once(&self.dhx_tube_side_name)
+            .chain(self.tchx_names.iter());
+
+        for required_name in required_names {
+            let component_found = self.components.iter().any(
+                |component| component.get_name() == required_name);
+
+            if !component_found {
+                return Err(LoopDefinitionError::MissingComponent(
+                        required_name.clone()));
+            }
+        }
+
+        let loop_elevation_change: f64 = self.components.iter()
+            .map(|component| component.get_elevation_change_meters())
+            .sum();
+
+        if loop_elevation_change.abs() > LOOP_ELEVATION_TOLERANCE_METERS {
+            return Err(LoopDefinitionError::UnbalancedElevation(
+                    loop_elevation_change));
+        }
+
+        return Ok(());
+    }
+}
+
+impl Default for DRACSLoopDefinition {
+    /// the CIET DRACS loop
+    fn default() -> Self {
+        return Self::from_toml_str(DEFAULT_DRACS_LOOP_DEFINITION_TOML)
+            .expect("default DRACS loop definition should be valid");
+    }
+}
+
+// reads a definition from a .toml or .json file, picking the
+// format from the file extension
+fn read_definition_file<T: DeserializeOwned>(path: &Path) -> Result<T, LoopDefinitionError> {
+    let file_contents = std::fs::read_to_string(path)?;
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => return Ok(toml::from_str(&file_contents)?),
+        Some("json") => return Ok(serde_json::from_str(&file_contents)?),
+        _ => return Err(LoopDefinitionError::UnknownFileFormat(
+                path.display().to_string())),
+    }
+}
+
+// writes a definition to a .toml or .json file, picking the
+// format from the file extension
+fn write_definition_file<T: Serialize>(definition: &T,
+    path: &Path) -> Result<(), LoopDefinitionError> {
+
+    let file_contents = match path.extension()
+        .and_then(|extension| extension.to_str()) {
+        Some("toml") => toml::to_string_pretty(definition)?,
+        Some("json") => serde_json::to_string_pretty(definition)?,
+        _ => return Err(LoopDefinitionError::UnknownFileFormat(
+                path.display().to_string())),
+    };
+
+    std::fs::write(path, file_contents)?;
+    return Ok(());
+}
+
+impl CIETComponentDefinition {
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            CIETComponentDefinition::Pipe { name, .. } => return name,
+            CIETComponentDefinition::CustomComponent { name, .. } => return name,
+        }
+    }
+
+    /// checks the K and darcy friction factor correlations of
+    /// the component, pipes use the churchill correlation so
+    /// only their roughness is checked
+    pub fn validate_correlations(&self) -> Result<(), LoopDefinitionError> {
+
+        let validation = match self {
+            CIETComponentDefinition::Pipe {
+                hydraulic_diameter_meters,
+                absolute_roughness_millimeters, .. } => {
+
+                let roughness_ratio = absolute_roughness_millimeters * 1e-3
+                    / hydraulic_diameter_meters;
+
+                Correlation::Churchill.validate(roughness_ratio)
+            },
+            CIETComponentDefinition::CustomComponent {
+                hydraulic_diameter_meters,
+                absolute_roughness_millimeters,
+                custom_k,
+                custom_darcy, .. } => {
+
+                let roughness_ratio = absolute_roughness_millimeters * 1e-3
+                    / hydraulic_diameter_meters;
+
+                // K has no roughness ratio, see Correlation::evaluate_k
+                custom_k.validate(0.0)
+                    .and_then(|_| custom_darcy.validate(roughness_ratio))
+            },
+        };
+
+        // name the component in the error
+        if let Err(LoopDefinitionError::InvalidCorrelation(message)) = validation {
+            return Err(LoopDefinitionError::InvalidCorrelation(
+                    format!("{}, {}", self.get_name(), message)));
+        }
+
+        return validation;
+    }
+
+    /// returns the rise in elevation from the component inlet
+    /// to its outlet
+    pub fn get_elevation_change_meters(&self) -> f64 {
+        let (incline_angle_degrees, component_length_meters) = match self {
+            CIETComponentDefinition::Pipe {
+                incline_angle_degrees, component_length_meters, .. } =>
+                (incline_angle_degrees, component_length_meters),
+            CIETComponentDefinition::CustomComponent {
+                incline_angle_degrees, component_length_meters, .. } =>
+                (incline_angle_degrees, component_length_meters),
+        };
+
+        return component_length_meters * incline_angle_degrees.to_radians().sin();
+    }
+
+    /// constructs the component at 21C
+    pub fn build(&self) -> CIETComponent {
+
+        let fluid_temp = ThermodynamicTemperature::new::<degree_celsius>(21.0);
+
+        match self {
+            CIETComponentDefinition::Pipe {
+                name,
+                incline_angle_degrees,
+                component_length_meters,
+                hydraulic_diameter_meters,
+                form_loss_k,
+                absolute_roughness_millimeters } => {
+
+                let pipe = TherminolPipe::new(
+                    name,
+                    fluid_temp,
+                    Angle::new::<degree>(*incline_angle_degrees),
+                    Length::new::<meter>(*component_length_meters),
+                    Length::new::<meter>(*hydraulic_diameter_meters),
+                    *form_loss_k,
+                    Length::new::<millimeter>(*absolute_roughness_millimeters),
+                    );
+
+                return CIETComponent::Pipe(pipe);
+            },
+            CIETComponentDefinition::CustomComponent {
+                name,
+                incline_angle_degrees,
+                component_length_meters,
+                cross_sectional_area_square_meters,
+                hydraulic_diameter_meters,
+                absolute_roughness_millimeters,
+                custom_k,
+                custom_darcy } => {
+
+                let component = TherminolCustomComponent::new(
+                    name,
+                    fluid_temp,
+                    Angle::new::<degree>(*incline_angle_degrees),
+                    Length::new::<meter>(*component_length_meters),
+                    Area::new::<square_meter>(*cross_sectional_area_square_meters),
+                    Length::new::<meter>(*hydraulic_diameter_meters),
+                    Length::new::<millimeter>(*absolute_roughness_millimeters),
+                    custom_k.clone(),
+                    custom_darcy.clone());
+
+                return CIETComponent::Custom(component);
+            },
+        }
+    }
+}
+
+/// a pipe or custom component built from a loop definition
+#[derive(Clone)]
+pub enum CIETComponent {
+    Pipe(TherminolPipe),
+    Custom(TherminolCustomComponent),
+}
+
+impl CIETComponent {
+
+    pub fn as_fluid_component(&self) -> &dyn FluidComponent {
+        match self {
+            CIETComponent::Pipe(pipe) => return pipe,
+            CIETComponent::Custom(component) => return component,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        match self {
+            CIETComponent::Pipe(pipe) => return pipe.get_name(),
+            CIETComponent::Custom(component) => return component.get_name(),
+        }
+    }
+
+    pub fn set_fluid_temperature(&mut self,
+        fluid_temp: ThermodynamicTemperature){
+        match self {
+            CIETComponent::Pipe(pipe) =>
+                pipe.set_fluid_temperature(fluid_temp),
+            CIETComponent::Custom(component) =>
+                component.set_fluid_temperature(fluid_temp),
+        }
+    }
+
+    /// sets the internal pressure source (eg. pump pressure)
+    pub fn set_internal_pressure_source(&mut self,
+        internal_pressure: Pressure){
+        match self {
+            CIETComponent::Pipe(pipe) =>
+                pipe.set_internal_pressure_source(internal_pressure),
+            CIETComponent::Custom(component) =>
+                component.set_internal_pressure_source(internal_pressure),
+        }
+    }
+
+    pub fn get_internal_pressure_source(&self) -> Pressure {
+        return self.as_fluid_component()
+            .get_internal_pressure_source_immutable();
+    }
+
+    pub fn get_cross_sectional_area(&self) -> Area {
+        return self.as_fluid_component()
+            .get_cross_sectional_area_immutable();
+    }
+
+    /// returns the component length over its cross sectional
+    /// area, which sets the inertia of the fluid within it
+    pub fn get_length_to_area_ratio(&self) -> ReciprocalLength {
+        let fluid_component = self.as_fluid_component();
+
+        return fluid_component.get_component_length_immutable()
+            / fluid_component.get_cross_sectional_area_immutable();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_loop_definition_is_valid() {
+        assert!(CIETLoopDefinition::default().validate().is_ok());
+    }
+
+    #[test]
+    fn empty_correlation_table_is_rejected() {
+        let mut loop_definition = CIETLoopDefinition::default();
+
+        let custom_k_to_break = loop_definition.heater_branch.iter_mut()
+            .find_map(|component| match component {
+                CIETComponentDefinition::CustomComponent { custom_k, .. } =>
+                    Some(custom_k),
+                CIETComponentDefinition::Pipe { .. } => None,
+            })
+            .expect("heater branch should have a custom component");
+        *custom_k_to_break = Correlation::PiecewiseTable { points: vec![] };
+
+        assert!(matches!(loop_definition.validate(),
+            Err(LoopDefinitionError::InvalidCorrelation(_))));
+    }
+
+    #[test]
+    fn negative_pipe_roughness_is_rejected() {
+        let mut loop_definition = CIETLoopDefinition::default();
+
+        let roughness_to_break = loop_definition.ctah_branch.iter_mut()
+            .find_map(|component| match component {
+                CIETComponentDefinition::Pipe {
+                    absolute_roughness_millimeters, .. } =>
+                    Some(absolute_roughness_millimeters),
+                CIETComponentDefinition::CustomComponent { .. } => None,
+            })
+            .expect("ctah branch should have a pipe");
+        *roughness_to_break = -0.015;
+
+        assert!(matches!(loop_definition.validate(),
+            Err(LoopDefinitionError::InvalidCorrelation(_))));
+    }
+}
@@ -0,0 +1,292 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::{CIETLoopDefinition, CIETComponentDefinition, 
+    CIETThermalComponentNames, LoopDefinitionError};
+
+/// This struct holds the fluid temperatures of every component
+/// in the CIET primary loop so that the hydraulics can be solved
+/// in a non-isothermal manner
+///
+/// each branch is a vector of temperatures, listed in the same
+/// order as the components in the branch, from the top of
+/// the branch to the bottom, for the default loop definition
+/// these are
+///
+/// heater branch:
+/// pipe 4, pipe 3, static mixer 10 (label 2), pipe 2a,
+/// heater top head 1a, heater 1, heater bottom head 1b, pipe 18
+///
+/// ctah branch:
+/// branch 5, pipe 6a, static mixer 41 (label 6), ctah vertical 7a,
+/// ctah horizontal 7b, pipe 8a, static mixer 40 (label 8),
+/// pipe 9, pipe 10, pipe 11, pipe 12, ctah pump, pipe 13, pipe 14,
+/// flowmeter 40 (label 14a), pipe 15, pipe 16, branch 17
+///
+/// dhx branch:
+/// pipe 26, static mixer 21 (label 25), pipe 25a, dhx shell side 24,
+/// static mixer 20 (label 23), pipe 23a, pipe 22,
+/// flowmeter 20 (label 21a), pipe 21, pipe 20, pipe 19
+///
+/// the hydrostatic and friction terms of each component
+/// are then evaluated at that component's temperature,
+/// which is what allows natural circulation to appear
+///
+/// the temperatures are sized from the loop definition (or the
+/// facility built from it) and carry the component names, so
+/// components are looked up by name rather than by position,
+/// the components modelled by the heater, CTAH and DHX thermal
+/// models are also named by the loop definition
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETLoopTemperatures {
+    /// heater branch fluid temperatures, top to bottom
+    pub heater_branch: Vec<ThermodynamicTemperature>,
+    /// ctah branch fluid temperatures, top to bottom
+    pub ctah_branch: Vec<ThermodynamicTemperature>,
+    /// dhx branch fluid temperatures, top to bottom
+    pub dhx_branch: Vec<ThermodynamicTemperature>,
+
+    heater_branch_component_names: Vec<String>,
+    ctah_branch_component_names: Vec<String>,
+    dhx_branch_component_names: Vec<String>,
+
+    thermal_components: CIETThermalComponentNames,
+}
+
+impl CIETLoopTemperatures {
+
+    /// every component is at the same temperature, the branches
+    /// are given by the names of their components, listed from
+    /// top to bottom
+    pub fn from_component_names(
+        heater_branch_component_names: Vec<String>,
+        ctah_branch_component_names: Vec<String>,
+        dhx_branch_component_names: Vec<String>,
+        thermal_components: CIETThermalComponentNames,
+        fluid_temp: ThermodynamicTemperature) -> Self {
+
+        return Self {
+            heater_branch: vec![fluid_temp; heater_branch_component_names.len()],
+            ctah_branch: vec![fluid_temp; ctah_branch_component_names.len()],
+            dhx_branch: vec![fluid_temp; dhx_branch_component_names.len()],
+            heater_branch_component_names,
+            ctah_branch_component_names,
+            dhx_branch_component_names,
+            thermal_components,
+        };
+    }
+
+    /// every component of the loop definition is at the same
+    /// temperature, this reproduces the isothermal calculations
+    pub fn new_uniform(loop_definition: &CIETLoopDefinition,
+        fluid_temp: ThermodynamicTemperature) -> Self {
+
+        let get_names = |branch: &Vec<CIETComponentDefinition>| {
+            branch.iter()
+                .map(|component| component.get_name().to_string())
+                .collect::<Vec<String>>()
+        };
+
+        return Self::from_component_names(
+            get_names(&loop_definition.heater_branch),
+            get_names(&loop_definition.ctah_branch),
+            get_names(&loop_definition.dhx_branch),
+            loop_definition.thermal_components.clone(),
+            fluid_temp);
+    }
+
+    pub fn get_heater_branch_component_names(&self) -> &[String] {
+        return &self.heater_branch_component_names;
+    }
+
+    pub fn get_ctah_branch_component_names(&self) -> &[String] {
+        return &self.ctah_branch_component_names;
+    }
+
+    pub fn get_dhx_branch_component_names(&self) -> &[String] {
+        return &self.dhx_branch_component_names;
+    }
+
+    /// returns the branch temperatures and position of a
+    /// component, searching every branch by name
+    fn find_component(&mut self, component_name: &str)
+        -> Result<(&mut Vec<ThermodynamicTemperature>, usize), LoopDefinitionError> {
+
+        let branches = [
+            (&self.heater_branch_component_names, &mut self.heater_branch),
+            (&self.ctah_branch_component_names, &mut self.ctah_branch),
+            (&self.dhx_branch_component_names, &mut self.dhx_branch),
+        ];
+
+        for (component_names, temperatures) in branches {
+            if let Some(position) = component_names.iter()
+                .position(|name| name == component_name) {
+                return Ok((temperatures, position));
+            }
+        }
+
+        return Err(LoopDefinitionError::MissingComponent(
+                component_name.to_string()));
+    }
+
+    /// sets the fluid temperature of a single component
+    pub fn set_component_temperature(&mut self,
+        component_name: &str,
+        fluid_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let (temperatures, position) = self.find_component(component_name)?;
+        temperatures[position] = fluid_temp;
+
+        return Ok(());
+    }
+
+    /// sets the fluid temperature of a component and of every
+    /// component below it in the same branch
+    fn set_temperatures_from_component_downwards(&mut self,
+        component_name: &str,
+        fluid_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let (temperatures, position) = self.find_component(component_name)?;
+
+        for temperature in temperatures[position..].iter_mut() {
+            *temperature = fluid_temp;
+        }
+
+        return Ok(());
+    }
+
+    /// a simple two temperature approximation of the loop
+    ///
+    /// the hot leg is everything downstream of the heater and
+    /// upstream of the heat exchangers (CTAH and DHX),
+    /// the cold leg is everything downstream of the heat exchangers
+    /// and upstream of the heater
+    ///
+    /// the heater, the vertical part of the CTAH and the DHX
+    /// are taken at the average of both temperatures
+    pub fn set_hot_and_cold_leg(&mut self,
+        hot_leg_temp: ThermodynamicTemperature,
+        cold_leg_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let average_temp: ThermodynamicTemperature =
+        ThermodynamicTemperature::new::<kelvin>(
+            0.5 * (hot_leg_temp.get::<kelvin>()
+                + cold_leg_temp.get::<kelvin>()));
+
+        // in the heater branch, everything above the heater is
+        // hot and everything below it cold, in the ctah and dhx
+        // branches, everything above the heat exchanger is hot
+        // and everything below it cold
+        let heat_exchanger_names = [
+            self.thermal_components.heated_section.clone(),
+            self.thermal_components.ctah_vertical.clone(),
+            self.thermal_components.dhx_shell_side.clone()];
+
+        for component_name in heat_exchanger_names {
+
+            let (temperatures, position) = self.find_component(&component_name)?;
+
+            for temperature in temperatures[..position].iter_mut() {
+                *temperature = hot_leg_temp;
+            }
+            temperatures[position] = average_temp;
+            for temperature in temperatures[position + 1..].iter_mut() {
+                *temperature = cold_leg_temp;
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// sets the temperatures of the heated section of the
+    /// heater branch from the heater thermal model
+    /// (static mixer 10, pipe 2a, heater top head 1a,
+    /// heater 1 and heater bottom head 1b for the default
+    /// loop definition)
+    pub fn set_heater_section_temperatures(
+        &mut self,
+        static_mixer_10_temp: ThermodynamicTemperature,
+        static_mixer_10_pipe_temp: ThermodynamicTemperature,
+        heater_top_head_temp: ThermodynamicTemperature,
+        heated_section_temp: ThermodynamicTemperature,
+        heater_bottom_head_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let heater_section_temperatures = [
+            static_mixer_10_temp,
+            static_mixer_10_pipe_temp,
+            heater_top_head_temp,
+            heated_section_temp,
+            heater_bottom_head_temp];
+
+        let heater_section_names: Vec<String> = self.thermal_components
+            .get_heater_branch_names().iter()
+            .map(|component_name| component_name.to_string())
+            .collect();
+
+        for (component_name, fluid_temp) in heater_section_names.iter()
+            .zip(heater_section_temperatures) {
+            self.set_component_temperature(component_name, fluid_temp)?;
+        }
+
+        return Ok(());
+    }
+
+    /// sets the temperatures of the ctah branch from the CTAH
+    /// thermal model (ctah vertical 7a and ctah horizontal 7b
+    /// for the default loop definition), everything downstream 
+    /// of the CTAH (pipe 8a to branch 17) is taken at the CTAH 
+    /// outlet temperature (BT-43)
+    pub fn set_ctah_section_temperatures(
+        &mut self,
+        ctah_vertical_temp: ThermodynamicTemperature,
+        ctah_horizontal_temp: ThermodynamicTemperature,
+        ctah_outlet_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let ctah_vertical_name = self.thermal_components.ctah_vertical.clone();
+        let ctah_horizontal_name = self.thermal_components.ctah_horizontal.clone();
+
+        self.set_temperatures_from_component_downwards(
+            &ctah_horizontal_name, ctah_outlet_temp)?;
+        self.set_component_temperature(&ctah_vertical_name,
+            ctah_vertical_temp)?;
+        self.set_component_temperature(&ctah_horizontal_name,
+            ctah_horizontal_temp)?;
+
+        return Ok(());
+    }
+
+    /// sets the temperatures of the dhx branch from the DHX
+    /// thermal model (dhx shell side 24 for the default loop 
+    /// definition), everything downstream of the DHX (static 
+    /// mixer 20 to pipe 19) is taken at the DHX shell side 
+    /// outlet temperature
+    pub fn set_dhx_section_temperatures(
+        &mut self,
+        dhx_shell_side_temp: ThermodynamicTemperature,
+        dhx_shell_side_outlet_temp: ThermodynamicTemperature)
+        -> Result<(), LoopDefinitionError> {
+
+        let dhx_shell_side_name = self.thermal_components.dhx_shell_side.clone();
+
+        self.set_temperatures_from_component_downwards(
+            &dhx_shell_side_name, dhx_shell_side_outlet_temp)?;
+        self.set_component_temperature(&dhx_shell_side_name,
+            dhx_shell_side_temp)?;
+
+        return Ok(());
+    }
+}
+
+impl Default for CIETLoopTemperatures {
+    /// CIET at room temperature (21 C), which is what the
+    /// isothermal components are constructed at
+    fn default() -> Self {
+        return Self::new_uniform(&CIETLoopDefinition::default(),
+            ThermodynamicTemperature::new::<degree_celsius>(21.0));
+    }
+}
@@ -0,0 +1,236 @@
+use serde::{Deserialize, Serialize};
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::acceleration::meter_per_second_squared;
+
+use crate::{CIETPressureBreakdown, ComponentPressureBreakdown,
+    LoopDefinitionError};
+
+/// the branches of the CIET primary loop
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CIETBranchName {
+    Heater,
+    CTAH,
+    DHX,
+}
+
+/// a manometer connected to two pressure taps within a branch
+///
+/// the upstream tap is at the inlet of the upstream component
+/// and the downstream tap is at the outlet of the downstream
+/// component, components are named as in the loop definition
+/// (the branch valves are named heater_branch_valve,
+/// ctah_branch_valve and dhx_branch_valve)
+///
+/// upstream and downstream follow the positive flow direction,
+/// which is from the top of the branch to the bottom
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Manometer {
+    pub name: String,
+    pub branch: CIETBranchName,
+    pub upstream_component: String,
+    pub downstream_component: String,
+}
+
+impl Manometer {
+
+    pub fn new(name: &str,
+        branch: CIETBranchName,
+        upstream_component: &str,
+        downstream_component: &str) -> Self {
+
+        return Self {
+            name: name.to_string(),
+            branch,
+            upstream_component: upstream_component.to_string(),
+            downstream_component: downstream_component.to_string(),
+        };
+    }
+}
+
+/// a simulated manometer reading
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManometerReading {
+    pub name: String,
+    /// difference in piezometric pressure between the upstream
+    /// and downstream taps, ie. what the manometer column height
+    /// corresponds to
+    pub pressure_difference: Pressure,
+    /// difference in manometer column height
+    pub column_height_difference: Length,
+    /// reading error from the manometer error model
+    pub reading_error: Pressure,
+}
+
+/// the manometer board of CIET
+///
+/// manometers measure the difference in piezometric pressure
+/// (p + rho_ref g z) between two taps, where rho_ref is the density
+/// of the fluid in the manometer lines at the reference
+/// temperature, for isothermal flow at the reference temperature
+/// this is the sum of the friction and form losses minus any
+/// pump pressure between the taps, for non isothermal flow
+/// the buoyancy between the taps is also picked up
+///
+/// the error model is a reading error of the manometer
+/// resolution (1 mm by default) on each of the two columns,
+/// which is how get_manometer_reading_error_pascals in the
+/// deviation calculations obtains its 14.7 Pa
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManometerBoard {
+    manometers: Vec<Manometer>,
+    reference_temperature: ThermodynamicTemperature,
+    reading_resolution: Length,
+}
+
+impl ManometerBoard {
+
+    /// constructor for a user specified set of manometers
+    pub fn new(manometers: Vec<Manometer>,
+        reference_temperature: ThermodynamicTemperature,
+        reading_resolution: Length) -> Self {
+
+        return Self {
+            manometers,
+            reference_temperature,
+            reading_resolution,
+        };
+    }
+
+    /// the pressure taps across each branch and across the
+    /// heater, CTAH, DHX, static mixers, flowmeters
+    /// and ctah pump of CIET
+    pub fn default_ciet_manometers() -> Vec<Manometer> {
+
+        use CIETBranchName::*;
+
+        return vec![
+            // heater branch
+            Manometer::new("heater_branch", Heater,
+                "pipe_4", "heater_branch_valve"),
+            Manometer::new("static_mixer_10_label_2", Heater,
+                "static_mixer_10_label_2", "pipe_2a_static_mixer"),
+            Manometer::new("heater_label_1", Heater,
+                "heater_top_head_label_1a", "heater_bottom_head_label_1b"),
+            // ctah branch
+            Manometer::new("ctah_branch", CTAH,
+                "branch_5", "ctah_branch_valve"),
+            Manometer::new("static_mixer_41_label_6", CTAH,
+                "static_mixer_41_label_6", "static_mixer_41_label_6"),
+            Manometer::new("ctah_label_7", CTAH,
+                "ctah_vertical_label_7a", "ctah_horizontal_label_7b"),
+            Manometer::new("static_mixer_40_label_8", CTAH,
+                "static_mixer_pipe_8a", "static_mixer_40_label_8"),
+            Manometer::new("ctah_pump", CTAH,
+                "ctah_pump", "ctah_pump"),
+            Manometer::new("flowmeter_40_14a", CTAH,
+                "flowmeter_40_14a", "flowmeter_40_14a"),
+            // dhx branch
+            Manometer::new("dhx_branch", DHX,
+                "pipe_26", "dhx_branch_valve"),
+            Manometer::new("static_mixer_21_label_25", DHX,
+                "static_mixer_21_label_25", "static_mixer_pipe_25a"),
+            Manometer::new("dhx_shell_side_label_24", DHX,
+                "dhx_shell_side_label_24", "dhx_shell_side_label_24"),
+            Manometer::new("static_mixer_20_label_23", DHX,
+                "static_mixer_20_label_23", "static_mixer_pipe_23a"),
+            Manometer::new("flowmeter_20_label_21a", DHX,
+                "flowmeter_20_label_21a", "flowmeter_20_label_21a"),
+        ];
+    }
+
+    pub fn get_manometers(&self) -> &[Manometer] {
+        return &self.manometers;
+    }
+
+    /// returns the error of a single manometer reading,
+    /// ie. the reading resolution on both columns added
+    /// in quadrature
+    pub fn get_reading_error(&self) -> Pressure {
+
+        let reference_density = TherminolVP1Properties::new()
+            .density(self.reference_temperature);
+        let g = Acceleration::new::<meter_per_second_squared>(9.81);
+
+        return 2.0_f64.sqrt() * reference_density * g
+            * self.reading_resolution;
+    }
+
+    /// returns the readings of every manometer given the pressure
+    /// breakdown of the loop
+    ///
+    /// returns an error if a manometer tap refers to a component
+    /// which is not in its branch, or if the downstream component
+    /// is above the upstream one
+    pub fn get_readings(&self,
+        pressure_breakdown: &CIETPressureBreakdown)
+        -> Result<Vec<ManometerReading>, LoopDefinitionError> {
+
+        let reference_density = TherminolVP1Properties::new()
+            .density(self.reference_temperature);
+        let g = Acceleration::new::<meter_per_second_squared>(9.81);
+        let reading_error = self.get_reading_error();
+
+        let mut readings: Vec<ManometerReading> = vec![];
+
+        for manometer in self.manometers.iter() {
+
+            let branch_breakdown: &[ComponentPressureBreakdown] =
+                match manometer.branch {
+                    CIETBranchName::Heater => &pressure_breakdown.heater_branch,
+                    CIETBranchName::CTAH => &pressure_breakdown.ctah_branch,
+                    CIETBranchName::DHX => &pressure_breakdown.dhx_branch,
+                };
+
+            let find_component = |component_name: &str| {
+                branch_breakdown.iter()
+                    .position(|component| component.name == component_name)
+                    .ok_or(LoopDefinitionError::MissingComponent(
+                            component_name.to_string()))
+            };
+
+            let upstream_index = find_component(&manometer.upstream_component)?;
+            let downstream_index = find_component(&manometer.downstream_component)?;
+
+            if downstream_index < upstream_index {
+                return Err(LoopDefinitionError::InvalidManometer(
+                        manometer.name.clone()));
+            }
+
+            // piezometric pressure drop from the upstream tap to the
+            // downstream tap,
+            //
+            // (p + rho_ref g z)_upstream - (p + rho_ref g z)_downstream
+            // = - sum (pressure change) - rho_ref g sum (dz)
+            let mut pressure_difference = Pressure::new::<pascal>(0.0);
+
+            for component in branch_breakdown[upstream_index..=downstream_index].iter() {
+                pressure_difference +=
+                    -component.get_pressure_change()
+                    - reference_density * g * component.elevation_change;
+            }
+
+            let column_height_difference: Length =
+                pressure_difference / reference_density / g;
+
+            readings.push(ManometerReading {
+                name: manometer.name.clone(),
+                pressure_difference,
+                column_height_difference,
+                reading_error,
+            });
+        }
+
+        return Ok(readings);
+    }
+}
+
+impl Default for ManometerBoard {
+    /// the CIET manometers read to the nearest millimeter of
+    /// Dowtherm A at 20C
+    fn default() -> Self {
+        return Self::new(Self::default_ciet_manometers(),
+            ThermodynamicTemperature::new::<degree_celsius>(20.0),
+            Length::new::<millimeter>(1.0));
+    }
+}
@@ -5,11 +5,13 @@ pub mod therminol_pipe;
 /// contains a class or struct for isothermal therminol components
 pub mod therminol_component;
 
-/// contains classes for ciet components within 
-/// ctah branch
-/// heater branch
-/// and 
-/// DHX branch
+/// contains the serialisable K and darcy friction factor
+/// correlations used by custom components
+pub mod correlation;
+pub use correlation::*;
+
+/// contains the branch valves and the ctah pump curve and
+/// rotor used within the ctah, heater and DHX branches
 pub mod component_libraries;
 pub use component_libraries::*;
 
@@ -17,6 +19,38 @@ pub use component_libraries::*;
 /// contains class or struct for isothermal branches in ciet
 /// a branch is a series of pipes in ciet
 pub mod branch;
+pub use branch::*;
+
+/// contains a struct holding the fluid temperatures of each
+/// component in the ciet primary loop, used for 
+/// non-isothermal hydraulics
+pub mod loop_temperatures;
+pub use loop_temperatures::*;
+
+/// contains the loop definition (list of components in each
+/// branch) which can be read from a TOML or JSON file
+pub mod loop_definition;
+pub use loop_definition::*;
+
+/// contains the natural circulation hydraulics of the
+/// DRACS loop
+pub mod dracs_loop;
+pub use dracs_loop::*;
+
+/// contains the per component pressure drop breakdown
+/// (friction, form loss, hydrostatic and pump contributions)
+pub mod pressure_breakdown;
+pub use pressure_breakdown::*;
+
+/// contains the manometer board, which simulates the
+/// differential pressure readings at the CIET pressure taps
+pub mod manometer;
+pub use manometer::*;
+
+/// contains the root finding algorithm and error type
+/// used to solve for branch and loop flowrates
+pub mod flow_solver;
+pub use flow_solver::*;
 
 /// contains the class representing ciet facility in isothermal operation
 /// primary loop only
@@ -24,6 +58,29 @@ pub mod branch;
 pub mod isothermal_ciet_facility;
 pub use isothermal_ciet_facility::*;
 
+/// contains the laminar nusselt floor and reynolds number
+/// shared by the therminol heat transfer models
+pub mod therminol_convection;
+pub use therminol_convection::*;
+
 /// contains code for the ciet heater v2.0 bare
 pub mod heater_v2_bare_heat_transfer_lib;
 pub use heater_v2_bare_heat_transfer_lib::*;
+
+/// contains code for the ciet coiled tube air heat exchanger (CTAH)
+pub mod ctah_heat_transfer_lib;
+pub use ctah_heat_transfer_lib::*;
+
+/// contains code for the ciet DRACS heat exchanger (DHX)
+pub mod dhx_heat_transfer_lib;
+pub use dhx_heat_transfer_lib::*;
+
+/// contains code for the insulated pipes around the ciet 
+/// primary loop
+pub mod thermal_pipe_lib;
+pub use thermal_pipe_lib::*;
+
+/// contains the point reactor kinetics model which can 
+/// optionally drive the heater power
+pub mod point_kinetics_lib;
+pub use point_kinetics_lib::*;
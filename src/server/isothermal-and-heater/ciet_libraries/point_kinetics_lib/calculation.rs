@@ -0,0 +1,126 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::ratio::ratio;
+use uom::si::frequency::hertz;
+use uom::si::time::second;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use super::PointKinetics;
+
+/// most substeps a timestep is split into, at 15 ms timesteps 
+/// this covers reactivities of well over a hundred dollars
+const MAX_SUBSTEPS: usize = 1000;
+
+impl PointKinetics {
+
+    /// returns the reactivity from external reactivity, scram and 
+    /// temperature feedback
+    pub fn get_reactivity(&self,
+        fuel_temperature: ThermodynamicTemperature,
+        coolant_temperature: ThermodynamicTemperature) -> Ratio {
+
+        let fuel_temperature_change_kelvin: f64 = 
+            fuel_temperature.get::<kelvin>() 
+            - self.reference_fuel_temperature.get::<kelvin>();
+
+        let coolant_temperature_change_kelvin: f64 = 
+            coolant_temperature.get::<kelvin>() 
+            - self.reference_coolant_temperature.get::<kelvin>();
+
+        let feedback_reactivity = Ratio::new::<ratio>(
+            self.doppler_coefficient.get::<per_kelvin>() 
+                * fuel_temperature_change_kelvin
+            + self.coolant_temperature_coefficient.get::<per_kelvin>() 
+                * coolant_temperature_change_kelvin);
+
+        let mut reactivity = self.external_reactivity + feedback_reactivity;
+
+        if self.scrammed {
+            reactivity += self.scram_reactivity;
+        }
+
+        reactivity
+    }
+
+    /// advances the neutron population, precursors and decay heat 
+    /// by one timestep using backward Euler
+    ///
+    /// the reactivity is held constant over the timestep, which
+    /// is split into substeps short enough for backward Euler to 
+    /// stay positive when the reactor is well above prompt critical
+    pub fn advance_timestep(&mut self, timestep: Time,
+        fuel_temperature: ThermodynamicTemperature,
+        coolant_temperature: ThermodynamicTemperature) {
+
+        let reactivity = self.get_reactivity(
+            fuel_temperature, coolant_temperature);
+        self.total_reactivity = reactivity;
+
+        let dt: f64 = timestep.get::<second>();
+        let rho: f64 = reactivity.get::<ratio>();
+        let generation_time: f64 = self.neutron_generation_time.get::<second>();
+
+        // the implicit neutron equation coefficient is at least 
+        // 1 - dt rho/Lambda, so keeping dt rho/Lambda at or below 
+        // one half keeps it positive
+        let number_of_substeps: usize = 
+            ((2.0 * dt * rho / generation_time).ceil() as usize)
+            .clamp(1, MAX_SUBSTEPS);
+
+        let substep: f64 = dt / number_of_substeps as f64;
+
+        for _ in 0..number_of_substeps {
+            self.advance_substep(substep, rho);
+        }
+    }
+
+    /// one backward Euler step of dt seconds at reactivity rho
+    fn advance_substep(&mut self, dt: f64, rho: f64) {
+        let beta: f64 = self.get_delayed_neutron_fraction().get::<ratio>();
+        let generation_time: f64 = self.neutron_generation_time.get::<second>();
+
+        // implicit precursors,
+        // C_i' = (C_i + dt beta_i/Lambda n')/(1 + dt lambda_i)
+        //
+        // substituting into the implicit neutron equation 
+        // gives n' directly
+        let mut lhs_coefficient: f64 = 1.0 - dt * (rho - beta) / generation_time;
+        let mut rhs: f64 = self.relative_fission_power;
+
+        for i in 0..6 {
+            let beta_i = self.delayed_neutron_fractions[i].get::<ratio>();
+            let lambda_i = self.precursor_decay_constants[i].get::<hertz>();
+            let denominator = 1.0 + dt * lambda_i;
+
+            lhs_coefficient -= dt * lambda_i * dt * beta_i 
+                / generation_time / denominator;
+            rhs += dt * lambda_i * self.precursor_concentrations[i] / denominator;
+        }
+
+        // the coefficient only goes negative beyond MAX_SUBSTEPS,
+        // clamp as a last guard against negative power
+        let new_relative_fission_power: f64 = (rhs / lhs_coefficient).max(0.0);
+
+        for i in 0..6 {
+            let beta_i = self.delayed_neutron_fractions[i].get::<ratio>();
+            let lambda_i = self.precursor_decay_constants[i].get::<hertz>();
+
+            self.precursor_concentrations[i] = 
+                (self.precursor_concentrations[i] 
+                 + dt * beta_i / generation_time * new_relative_fission_power)
+                / (1.0 + dt * lambda_i);
+        }
+
+        for j in 0..3 {
+            let gamma_j = self.decay_heat_fractions[j].get::<ratio>();
+            let lambda_j = self.decay_heat_decay_constants[j].get::<hertz>();
+
+            self.decay_heat_energies[j] = 
+                (self.decay_heat_energies[j] 
+                 + dt * gamma_j * new_relative_fission_power)
+                / (1.0 + dt * lambda_j);
+        }
+
+        self.relative_fission_power = new_relative_fission_power;
+    }
+}
@@ -0,0 +1,347 @@
+//! point reactor kinetics for driving the heater power
+//!
+//! CIET is a surrogate for the Fluoride Salt cooled High 
+//! Temperature Reactor (FHR), so the heater can optionally 
+//! follow the power of a point kinetics model rather than a 
+//! power set by the operator
+//!
+//! The model has six delayed neutron groups,
+//!
+//! dn/dt = (rho - beta)/Lambda n + sum_i lambda_i C_i
+//!
+//! dC_i/dt = beta_i/Lambda n - lambda_i C_i
+//!
+//! where n is the fission power relative to nominal power, 
+//! and three decay heat groups,
+//!
+//! dH_j/dt = gamma_j n - lambda_j H_j
+//!
+//! The heater power is 
+//!
+//! P = P_nominal [(1 - sum_j gamma_j) n + sum_j lambda_j H_j]
+//!
+//! which equals the nominal power at steady state
+//!
+//! The reactivity is the external (operator) reactivity, 
+//! scram reactivity, plus Doppler and coolant temperature 
+//! feedback. The heater steel shell stands in for the fuel 
+//! (Doppler feedback) and the heater therminol for the 
+//! coolant. Feedback is taken relative to the temperatures 
+//! when the point kinetics model was started
+//!
+//! The equations are stiff, so they are solved implicitly 
+//! (backward Euler), which is stable for the heater timestep
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::ratio::ratio;
+use uom::si::frequency::hertz;
+use uom::si::time::second;
+use uom::si::temperature_coefficient::per_kelvin;
+use uom::si::f64::TemperatureCoefficient;
+
+/// delayed neutron fractions for U-235 thermal fission (Keepin)
+pub const DELAYED_NEUTRON_FRACTIONS: [f64; 6] = 
+    [0.000215, 0.001424, 0.001274, 0.002568, 0.000748, 0.000273];
+
+/// delayed neutron precursor decay constants in 1/s 
+/// for U-235 thermal fission (Keepin)
+pub const PRECURSOR_DECAY_CONSTANTS_PER_SECOND: [f64; 6] = 
+    [0.0124, 0.0305, 0.111, 0.301, 1.14, 3.01];
+
+/// approximate three group fit to decay heat, 
+/// fraction of fission power at equilibrium for each group
+pub const DECAY_HEAT_FRACTIONS: [f64; 3] = [0.025, 0.015, 0.022];
+
+/// decay constants in 1/s for the three decay heat groups
+pub const DECAY_HEAT_DECAY_CONSTANTS_PER_SECOND: [f64; 3] = 
+    [1.0e-1, 5.0e-3, 1.5e-4];
+
+/// prompt neutron generation time in seconds, approximately that 
+/// of a graphite moderated FHR core
+pub const NEUTRON_GENERATION_TIME_SECONDS: f64 = 5.0e-4;
+
+/// Doppler (fuel temperature) reactivity coefficient in 1/K
+pub const DOPPLER_COEFFICIENT_PER_KELVIN: f64 = -3.5e-5;
+
+/// coolant temperature reactivity coefficient in 1/K
+pub const COOLANT_TEMPERATURE_COEFFICIENT_PER_KELVIN: f64 = -1.0e-5;
+
+/// scram reactivity in dollars
+pub const SCRAM_REACTIVITY_DOLLARS: f64 = -8.0;
+
+/// point reactor kinetics with six delayed neutron groups, 
+/// three decay heat groups and temperature feedback
+#[derive(Debug,Clone,PartialEq)]
+pub struct PointKinetics {
+
+    /// power at which the model was started at steady state
+    pub nominal_power: Power,
+
+    pub neutron_generation_time: Time,
+
+    pub delayed_neutron_fractions: [Ratio; 6],
+
+    pub precursor_decay_constants: [Frequency; 6],
+
+    pub decay_heat_fractions: [Ratio; 3],
+
+    pub decay_heat_decay_constants: [Frequency; 3],
+
+    pub doppler_coefficient: TemperatureCoefficient,
+
+    pub coolant_temperature_coefficient: TemperatureCoefficient,
+
+    /// fuel (heater steel shell) temperature at startup
+    pub reference_fuel_temperature: ThermodynamicTemperature,
+
+    /// coolant (heater therminol) temperature at startup
+    pub reference_coolant_temperature: ThermodynamicTemperature,
+
+    /// reactivity inserted by the operator
+    pub external_reactivity: Ratio,
+
+    pub scram_reactivity: Ratio,
+
+    /// once scrammed, the scram reactivity is inserted 
+    /// until the scram is reset
+    pub scrammed: bool,
+
+    // fission power relative to nominal power
+    relative_fission_power: f64,
+
+    // precursor concentrations relative to nominal power
+    precursor_concentrations: [f64; 6],
+
+    // decay heat group energies relative to nominal power
+    decay_heat_energies: [f64; 3],
+
+    // reactivity at the last timestep, for postprocessing
+    total_reactivity: Ratio,
+}
+
+impl PointKinetics {
+
+    /// starts the point kinetics model at steady state 
+    /// (zero reactivity) at the given power, with the 
+    /// current fuel and coolant temperatures as reference 
+    /// temperatures for feedback
+    pub fn new_steady_state(nominal_power: Power,
+        fuel_temperature: ThermodynamicTemperature,
+        coolant_temperature: ThermodynamicTemperature) -> Self {
+
+        let neutron_generation_time = Time::new::<second>(
+            NEUTRON_GENERATION_TIME_SECONDS);
+
+        let delayed_neutron_fractions: [Ratio; 6] = 
+            DELAYED_NEUTRON_FRACTIONS.map(Ratio::new::<ratio>);
+
+        let precursor_decay_constants: [Frequency; 6] = 
+            PRECURSOR_DECAY_CONSTANTS_PER_SECOND.map(Frequency::new::<hertz>);
+
+        let beta: Ratio = delayed_neutron_fractions.iter()
+            .fold(Ratio::new::<ratio>(0.0), |sum, beta_i| sum + *beta_i);
+
+        let mut point_kinetics = Self {
+            nominal_power,
+            neutron_generation_time,
+            delayed_neutron_fractions,
+            precursor_decay_constants,
+            decay_heat_fractions: DECAY_HEAT_FRACTIONS.map(Ratio::new::<ratio>),
+            decay_heat_decay_constants: 
+                DECAY_HEAT_DECAY_CONSTANTS_PER_SECOND.map(Frequency::new::<hertz>),
+            doppler_coefficient: TemperatureCoefficient::new::<per_kelvin>(
+                DOPPLER_COEFFICIENT_PER_KELVIN),
+            coolant_temperature_coefficient: TemperatureCoefficient::new::<per_kelvin>(
+                COOLANT_TEMPERATURE_COEFFICIENT_PER_KELVIN),
+            reference_fuel_temperature: fuel_temperature,
+            reference_coolant_temperature: coolant_temperature,
+            external_reactivity: Ratio::new::<ratio>(0.0),
+            scram_reactivity: SCRAM_REACTIVITY_DOLLARS * beta,
+            scrammed: false,
+            relative_fission_power: 1.0,
+            precursor_concentrations: [0.0; 6],
+            decay_heat_energies: [0.0; 3],
+            total_reactivity: Ratio::new::<ratio>(0.0),
+        };
+
+        point_kinetics.set_steady_state_precursors_and_decay_heat();
+
+        point_kinetics
+    }
+
+    /// sets precursors and decay heat to equilibrium with 
+    /// the current fission power
+    fn set_steady_state_precursors_and_decay_heat(&mut self) {
+
+        let n = self.relative_fission_power;
+        let generation_time = self.neutron_generation_time.get::<second>();
+
+        for i in 0..6 {
+            self.precursor_concentrations[i] = 
+                self.delayed_neutron_fractions[i].get::<ratio>() * n
+                / (generation_time * self.precursor_decay_constants[i].get::<hertz>());
+        }
+
+        for j in 0..3 {
+            self.decay_heat_energies[j] = 
+                self.decay_heat_fractions[j].get::<ratio>() * n
+                / self.decay_heat_decay_constants[j].get::<hertz>();
+        }
+    }
+
+    /// total delayed neutron fraction (beta)
+    pub fn get_delayed_neutron_fraction(&self) -> Ratio {
+        self.delayed_neutron_fractions.iter()
+            .fold(Ratio::new::<ratio>(0.0), |sum, beta_i| sum + *beta_i)
+    }
+
+    /// sets the external reactivity in dollars
+    pub fn set_external_reactivity_dollars(&mut self, reactivity_dollars: f64) {
+        self.external_reactivity = 
+            reactivity_dollars * self.get_delayed_neutron_fraction();
+    }
+
+    /// total reactivity at the last timestep in dollars
+    pub fn get_total_reactivity_dollars(&self) -> f64 {
+        (self.total_reactivity / self.get_delayed_neutron_fraction())
+            .get::<ratio>()
+    }
+
+    /// power from fission alone
+    pub fn get_fission_power(&self) -> Power {
+        let decay_heat_fraction: f64 = self.decay_heat_fractions.iter()
+            .map(|gamma| gamma.get::<ratio>()).sum();
+
+        (1.0 - decay_heat_fraction) * self.relative_fission_power 
+            * self.nominal_power
+    }
+
+    /// power from decay heat alone
+    pub fn get_decay_heat(&self) -> Power {
+        let relative_decay_heat: f64 = self.decay_heat_energies.iter()
+            .zip(self.decay_heat_decay_constants.iter())
+            .map(|(energy, decay_constant)| {
+                decay_constant.get::<hertz>() * energy
+            }).sum();
+
+        relative_decay_heat * self.nominal_power
+    }
+
+    /// total heater power (fission plus decay heat)
+    pub fn get_heater_power(&self) -> Power {
+        self.get_fission_power() + self.get_decay_heat()
+    }
+}
+
+// methods for PointKinetics only
+mod calculation;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uom::si::power::kilowatt;
+    use uom::si::thermodynamic_temperature::degree_celsius;
+
+    fn reference_temperature() -> ThermodynamicTemperature {
+        ThermodynamicTemperature::new::<degree_celsius>(80.0)
+    }
+
+    fn steady_state_point_kinetics() -> PointKinetics {
+        PointKinetics::new_steady_state(Power::new::<kilowatt>(8.0),
+            reference_temperature(), reference_temperature())
+    }
+
+    fn heater_timestep() -> Time {
+        Time::new::<second>(0.015)
+    }
+
+    #[test]
+    fn steady_state_stays_at_nominal_power() {
+        let mut point_kinetics = steady_state_point_kinetics();
+
+        assert!((point_kinetics.get_heater_power().get::<kilowatt>() - 8.0).abs() < 1e-9);
+
+        for _ in 0..1000 {
+            point_kinetics.advance_timestep(heater_timestep(),
+                reference_temperature(), reference_temperature());
+        }
+
+        assert!((point_kinetics.get_heater_power().get::<kilowatt>() - 8.0).abs() < 1e-9);
+        assert_eq!(point_kinetics.get_total_reactivity_dollars(), 0.0);
+    }
+
+    #[test]
+    fn temperature_rise_gives_negative_feedback() {
+        let point_kinetics = steady_state_point_kinetics();
+        let hotter = ThermodynamicTemperature::new::<degree_celsius>(90.0);
+
+        let reactivity = point_kinetics.get_reactivity(hotter, hotter);
+        let expected_reactivity = 10.0 * (DOPPLER_COEFFICIENT_PER_KELVIN 
+            + COOLANT_TEMPERATURE_COEFFICIENT_PER_KELVIN);
+
+        assert!((reactivity.get::<ratio>() - expected_reactivity).abs() < 1e-12);
+    }
+
+    #[test]
+    fn reactivity_sign_sets_the_power_trend() {
+        let mut supercritical = steady_state_point_kinetics();
+        let mut subcritical = steady_state_point_kinetics();
+
+        supercritical.set_external_reactivity_dollars(0.1);
+        subcritical.set_external_reactivity_dollars(-0.1);
+
+        for _ in 0..100 {
+            supercritical.advance_timestep(heater_timestep(),
+                reference_temperature(), reference_temperature());
+            subcritical.advance_timestep(heater_timestep(),
+                reference_temperature(), reference_temperature());
+        }
+
+        let nominal_fission_power = steady_state_point_kinetics().get_fission_power();
+
+        assert!(supercritical.get_fission_power() > nominal_fission_power);
+        assert!(subcritical.get_fission_power() < nominal_fission_power);
+    }
+
+    #[test]
+    fn prompt_supercritical_timestep_is_substepped() {
+        let mut point_kinetics = steady_state_point_kinetics();
+
+        // at 10 dollars, a single backward Euler step over the 
+        // heater timestep would give a negative power
+        point_kinetics.set_external_reactivity_dollars(10.0);
+        let beta: f64 = point_kinetics.get_delayed_neutron_fraction().get::<ratio>();
+        let single_step_coefficient: f64 = 1.0 - heater_timestep().get::<second>()
+            * 9.0 * beta / NEUTRON_GENERATION_TIME_SECONDS;
+        assert!(single_step_coefficient < 0.0);
+
+        let mut previous_power = point_kinetics.get_fission_power();
+        for _ in 0..5 {
+            point_kinetics.advance_timestep(heater_timestep(),
+                reference_temperature(), reference_temperature());
+
+            let fission_power = point_kinetics.get_fission_power();
+            assert!(fission_power.get::<kilowatt>().is_finite());
+            assert!(fission_power > previous_power);
+            previous_power = fission_power;
+        }
+    }
+
+    #[test]
+    fn scram_leaves_decay_heat() {
+        let mut point_kinetics = steady_state_point_kinetics();
+
+        point_kinetics.scrammed = true;
+        // 30 s after the scram
+        for _ in 0..2000 {
+            point_kinetics.advance_timestep(heater_timestep(),
+                reference_temperature(), reference_temperature());
+        }
+
+        let heater_power = point_kinetics.get_heater_power().get::<kilowatt>();
+        let decay_heat = point_kinetics.get_decay_heat().get::<kilowatt>();
+
+        assert!(heater_power < 0.1 * 8.0);
+        assert!(decay_heat > 0.0);
+        assert!(decay_heat > point_kinetics.get_fission_power().get::<kilowatt>());
+    }
+}
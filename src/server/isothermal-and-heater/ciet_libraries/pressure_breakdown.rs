@@ -0,0 +1,132 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use crate::{CIETComponent, BranchValve};
+
+/// the contributions of a single component to the
+/// pressure change across a branch
+///
+/// losses are positive in the direction of flow, so that
+///
+/// pressure change = - friction loss - form loss
+/// + hydrostatic pressure change + internal pressure source
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentPressureBreakdown {
+    /// name of the component, eg. pipe_4
+    pub name: String,
+    /// the darcy friction factor (f L/D) part of the pressure loss
+    pub friction_loss: Pressure,
+    /// the form loss (K) part of the pressure loss
+    pub form_loss: Pressure,
+    /// pressure change due to elevation (rho g dh)
+    pub hydrostatic_pressure_change: Pressure,
+    /// pressure supplied by the component, eg. the ctah pump
+    pub internal_pressure_source: Pressure,
+    /// height of the component outlet above its inlet
+    pub elevation_change: Length,
+}
+
+impl ComponentPressureBreakdown {
+
+    /// returns the total pressure change across the component
+    pub fn get_pressure_change(&self) -> Pressure {
+        return -self.friction_loss
+            - self.form_loss
+            + self.hydrostatic_pressure_change
+            + self.internal_pressure_source;
+    }
+}
+
+/// per component pressure breakdown of every branch in the
+/// CIET primary loop, each listed from top to bottom with the
+/// branch valve last
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETPressureBreakdown {
+    pub heater_branch: Vec<ComponentPressureBreakdown>,
+    pub ctah_branch: Vec<ComponentPressureBreakdown>,
+    pub dhx_branch: Vec<ComponentPressureBreakdown>,
+}
+
+impl CIETComponent {
+
+    /// returns the friction, form loss, hydrostatic and
+    /// internal pressure source contributions of this component
+    /// at a given mass flowrate and the current fluid temperature
+    ///
+    /// the total pressure loss comes from thermal_hydraulics_rs,
+    /// the form loss is K m|m|/(2 rho A^2) and the rest of the
+    /// pressure loss is taken to be friction
+    pub fn get_pressure_breakdown(&self,
+        mass_flowrate: MassRate) -> ComponentPressureBreakdown {
+
+        let fluid_component = self.as_fluid_component();
+
+        let pressure_loss = fluid_component
+            .get_pressure_loss_immutable(mass_flowrate);
+
+        let cross_sectional_area = fluid_component
+            .get_cross_sectional_area_immutable();
+        let fluid_density = fluid_component
+            .get_fluid_density_immutable();
+
+        // K m|m|/(2 rho A^2), where K already takes the sign
+        // of the flow for custom components
+        let form_loss: Pressure = match self {
+            CIETComponent::Pipe(pipe) => {
+                let form_loss_k = pipe.get_pipe_form_loss_k_immutable();
+
+                0.5 * form_loss_k * mass_flowrate * mass_flowrate.abs()
+                    / fluid_density / cross_sectional_area / cross_sectional_area
+            },
+            CIETComponent::Custom(component) => {
+                let reynolds_number: Ratio = mass_flowrate
+                    / cross_sectional_area
+                    * component.get_hydraulic_diameter_immutable()
+                    / component.get_fluid_viscosity_immutable();
+
+                let custom_k = component.get_custom_k_correlation()
+                    .evaluate_k(reynolds_number.value);
+
+                0.5 * custom_k * mass_flowrate * mass_flowrate
+                    / fluid_density / cross_sectional_area / cross_sectional_area
+            },
+        };
+
+        return ComponentPressureBreakdown {
+            name: self.get_name().to_string(),
+            friction_loss: pressure_loss - form_loss,
+            form_loss,
+            hydrostatic_pressure_change: fluid_component
+                .get_hydrostatic_pressure_change_immutable(),
+            internal_pressure_source: fluid_component
+                .get_internal_pressure_source_immutable(),
+            elevation_change: fluid_component.get_component_length_immutable()
+                * fluid_component.get_incline_angle_immutable().sin(),
+        };
+    }
+}
+
+impl BranchValve {
+
+    /// the valve only has a form loss, a closed valve has no
+    /// flow through it and therefore no loss
+    pub fn get_pressure_breakdown(&self,
+        mass_flowrate: MassRate) -> ComponentPressureBreakdown {
+
+        let zero_pressure = Pressure::new::<pascal>(0.0);
+
+        let form_loss = if self.is_closed() {
+            zero_pressure
+        } else {
+            self.get_pressure_loss_immutable(mass_flowrate)
+        };
+
+        return ComponentPressureBreakdown {
+            name: self.get_name().to_string(),
+            friction_loss: zero_pressure,
+            form_loss,
+            hydrostatic_pressure_change: zero_pressure,
+            internal_pressure_source: zero_pressure,
+            elevation_change: Length::new::<meter>(0.0),
+        };
+    }
+}
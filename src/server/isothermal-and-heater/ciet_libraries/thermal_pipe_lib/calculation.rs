@@ -0,0 +1,28 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use super::{ThermalPipe, ThermalPipeChain};
+
+
+impl ThermalPipe {
+    /// advances timestep for each HeatTransferEntity within the 
+    /// ThermalPipe
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        self.therminol_array.advance_timestep_mut_self(timestep).unwrap();
+        self.steel_shell.advance_timestep_mut_self(timestep).unwrap();
+        self.insulation_array.advance_timestep_mut_self(timestep).unwrap();
+    }
+}
+
+impl ThermalPipeChain {
+    /// advances timestep for every pipe in the chain
+    #[inline]
+    pub fn advance_timestep(&mut self, 
+    timestep: Time) {
+
+        for pipe in self.pipes.iter_mut() {
+            pipe.advance_timestep(timestep);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! The thermal pipe module represents the insulated pipes (and 
+//! unheated components such as static mixers, flowmeters and the 
+//! ctah pump) which make up the rest of the CIET primary loop 
+//! between BT-12 and BT-11
+//!
+//! Each pipe has a FluidArray of therminol, a SolidColumn for the 
+//! steel pipe wall and another for the fiberglass insulation, 
+//! which loses heat to the ambient air, as with the static mixer 
+//! MX-10 pipe
+//!
+//! Pipes are built from the same loop definition as the 
+//! hydraulics, so their lengths and flow areas match, and are 
+//! then strung together into chains (ThermalPipeChain) which 
+//! link the heater, CTAH and DHX thermal models to each other.
+//! This closes the loop thermally, so that BT-11 is computed 
+//! rather than set, and the time taken for fluid to travel 
+//! around the loop is accounted for
+//!
+//! Pipe wall thickness and insulation are representative of 
+//! the 1 inch schedule 40 piping of CIET and should be 
+//! callibrated against loop heat loss data
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+
+use uom::si::area::square_meter;
+use uom::si::heat_transfer::watt_per_square_meter_kelvin;
+use uom::si::ratio::ratio;
+use uom::si::pressure::atmosphere;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use crate::CIETComponentDefinition;
+
+/// thickness of the steel pipe walls
+pub const THERMAL_PIPE_WALL_THICKNESS_METERS: f64 = 0.0034;
+
+/// thickness of the fiberglass insulation around the pipes
+pub const THERMAL_PIPE_INSULATION_THICKNESS_METERS: f64 = 0.0508;
+
+/// heat transfer coefficient from the insulation to the 
+/// ambient air
+pub const THERMAL_PIPE_AMBIENT_HTC_WATT_PER_SQUARE_METER_KELVIN: f64 = 6.0;
+
+/// the pipes are split into nodes of about this length, so 
+/// that longer pipes carry more of the transport delay
+pub const THERMAL_PIPE_NODE_LENGTH_METERS: f64 = 0.5;
+
+/// pipes have at most this many inner nodes
+pub const THERMAL_PIPE_MAX_INNER_NODES: usize = 4;
+
+/// represents an insulated pipe (or unheated component) 
+/// of the CIET loop
+#[derive(Debug,Clone,PartialEq)]
+pub struct ThermalPipe {
+
+    name: String,
+
+    inner_nodes: usize,
+
+    pub insulation_array: HeatTransferEntity,
+
+    pub steel_shell: HeatTransferEntity,
+
+    pub therminol_array: HeatTransferEntity,
+
+    pub ambient_temperature: ThermodynamicTemperature,
+
+    pub heat_transfer_to_air: HeatTransfer,
+
+    tube_inner_diameter: Length,
+
+    tube_outer_diameter: Length, 
+
+    insulation_inner_diameter: Length,
+
+    insulation_outer_diameter: Length,
+}
+
+impl ThermalPipe {
+
+    /// constructs the thermal pipe from its loop definition
+    ///
+    /// the pipe inner diameter is that of a circle with the 
+    /// component flow area, the number of nodes depends on 
+    /// the pipe length
+    pub fn new_from_definition(
+        component_definition: &CIETComponentDefinition,
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature) -> Self {
+
+        let (component_length, hydraulic_diameter, flow_area, 
+            pipe_incline_angle) = match component_definition {
+            CIETComponentDefinition::Pipe {
+                incline_angle_degrees,
+                component_length_meters,
+                hydraulic_diameter_meters, .. } => {
+                let flow_area_square_meters = 0.25 * PI 
+                    * hydraulic_diameter_meters * hydraulic_diameter_meters;
+                (*component_length_meters, *hydraulic_diameter_meters,
+                    flow_area_square_meters, *incline_angle_degrees)
+            },
+            CIETComponentDefinition::CustomComponent {
+                incline_angle_degrees,
+                component_length_meters,
+                cross_sectional_area_square_meters,
+                hydraulic_diameter_meters, .. } => {
+                (*component_length_meters, *hydraulic_diameter_meters,
+                    *cross_sectional_area_square_meters, *incline_angle_degrees)
+            },
+        };
+
+        let user_specified_inner_nodes: usize = 
+            ((component_length / THERMAL_PIPE_NODE_LENGTH_METERS).round() as usize)
+            .min(THERMAL_PIPE_MAX_INNER_NODES);
+
+        let component_length = Length::new::<meter>(component_length);
+        let hydraulic_diameter = Length::new::<meter>(hydraulic_diameter);
+        let flow_area = Area::new::<square_meter>(flow_area);
+        let pipe_incline_angle = Angle::new::<uom::si::angle::degree>(
+            pipe_incline_angle);
+        let atmospheric_pressure = Pressure::new::<atmosphere>(1.0);
+
+        // the form losses are taken care of in the hydraulics
+        let dummy_pipe_form_loss = Ratio::new::<ratio>(0.1);
+
+        let h_to_air: HeatTransfer = 
+        HeatTransfer::new::<watt_per_square_meter_kelvin>(
+            THERMAL_PIPE_AMBIENT_HTC_WATT_PER_SQUARE_METER_KELVIN);
+
+        let steel_id: Length = (4.0 * flow_area / PI).sqrt();
+        let steel_od: Length = steel_id + 2.0 * Length::new::<meter>(
+            THERMAL_PIPE_WALL_THICKNESS_METERS);
+        let fiberglass_id = steel_od;
+        let fiberglass_od = fiberglass_id + 2.0 * Length::new::<meter>(
+            THERMAL_PIPE_INSULATION_THICKNESS_METERS);
+
+        // inner therminol array
+        let therminol_array: FluidArray = 
+        FluidArray::new_odd_shaped_pipe(
+            component_length,
+            hydraulic_diameter,
+            flow_area,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::SteelSS304L,
+            LiquidMaterial::TherminolVP1,
+            dummy_pipe_form_loss,
+            user_specified_inner_nodes,
+            pipe_incline_angle
+        );
+        // now the outer steel array
+        let steel_shell_array = 
+        SolidColumn::new_cylindrical_shell(
+            component_length,
+            steel_id,
+            steel_od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::SteelSS304L,
+            user_specified_inner_nodes 
+        );
+        // insulation
+        let insulation = 
+        SolidColumn::new_cylindrical_shell(
+            component_length,
+            fiberglass_id,
+            fiberglass_od,
+            initial_temperature,
+            atmospheric_pressure,
+            SolidMaterial::Fiberglass,
+            user_specified_inner_nodes 
+        );
+
+        return Self { 
+            name: component_definition.get_name().to_string(),
+            inner_nodes: user_specified_inner_nodes,
+            insulation_array: insulation.into(),
+            steel_shell: steel_shell_array.into(),
+            therminol_array: therminol_array.into(),
+            ambient_temperature,
+            heat_transfer_to_air: h_to_air,
+            tube_inner_diameter: steel_id,
+            tube_outer_diameter: steel_od,
+            insulation_inner_diameter: fiberglass_id,
+            insulation_outer_diameter: fiberglass_od,
+        };
+    }
+
+    pub fn get_name(&self) -> &str {
+        return &self.name;
+    }
+}
+
+/// a series of thermal pipes, linked front to back, with the 
+/// same mass flowrate through each of them
+///
+/// a chain is listed in the direction its mass flowrate is 
+/// taken as positive, from its back (first pipe) to its 
+/// front (last pipe)
+#[derive(Debug,Clone,PartialEq)]
+pub struct ThermalPipeChain {
+    pub pipes: Vec<ThermalPipe>,
+}
+
+impl ThermalPipeChain {
+
+    /// constructs a chain of thermal pipes from their loop 
+    /// definitions, listed from the back of the chain to the front
+    pub fn new_from_definitions<'a, I>(component_definitions: I,
+        initial_temperature: ThermodynamicTemperature,
+        ambient_temperature: ThermodynamicTemperature) -> Self 
+    where I: IntoIterator<Item = &'a CIETComponentDefinition> {
+
+        let pipes: Vec<ThermalPipe> = component_definitions.into_iter()
+            .map(|component_definition| ThermalPipe::new_from_definition(
+                    component_definition,
+                    initial_temperature,
+                    ambient_temperature))
+            .collect();
+
+        return Self { pipes };
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.pipes.is_empty();
+    }
+}
+
+/// returns the temperature of the fluid in a header (eg. the 
+/// top or bottom of the CIET branches) where several streams 
+/// meet, given the mass flowrate of each stream into the header 
+/// and its temperature
+///
+/// streams with flow leaving the header (negative flowrates) 
+/// do not contribute, if nothing flows in, None is returned
+pub fn get_mixed_temperature(
+    streams: &[(MassRate, ThermodynamicTemperature)]) 
+    -> Option<ThermodynamicTemperature> {
+
+    let mut total_mass_flowrate_kg_per_s: f64 = 0.0;
+    let mut total_flow_weighted_temperature: f64 = 0.0;
+
+    for (mass_flowrate, temperature) in streams.iter() {
+        let mass_flowrate_kg_per_s = 
+            mass_flowrate.get::<kilogram_per_second>();
+
+        if mass_flowrate_kg_per_s > 0.0 {
+            total_mass_flowrate_kg_per_s += mass_flowrate_kg_per_s;
+            total_flow_weighted_temperature += 
+                mass_flowrate_kg_per_s * temperature.get::<kelvin>();
+        }
+    }
+
+    if total_mass_flowrate_kg_per_s <= 0.0 {
+        return None;
+    }
+
+    return Some(ThermodynamicTemperature::new::<kelvin>(
+            total_flow_weighted_temperature / total_mass_flowrate_kg_per_s));
+}
+
+// methods for ThermalPipe and ThermalPipeChain only, 
+// kept private as with the DHX
+mod preprocessing;
+
+mod calculation;
+
+mod postprocessing;
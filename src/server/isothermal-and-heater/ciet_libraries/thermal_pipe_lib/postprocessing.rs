@@ -0,0 +1,67 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::thermodynamic_temperature::kelvin;
+
+use super::{ThermalPipe, ThermalPipeChain};
+
+impl ThermalPipe {
+
+    pub fn therminol_array_temperature(&mut self) -> Vec<ThermodynamicTemperature>{
+        self.therminol_array.get_temperature_vector().unwrap()
+    }
+
+    pub fn get_bulk_temperature(&mut self) -> ThermodynamicTemperature {
+        let mut therminol_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        return therminol_array_clone.try_get_bulk_temperature().unwrap();
+    }
+
+    /// therminol temperature at the back (first node) of the pipe
+    pub fn get_back_temperature(&mut self) -> ThermodynamicTemperature {
+        self.therminol_array_temperature().into_iter().next().unwrap()
+    }
+
+    /// therminol temperature at the front (last node) of the pipe
+    pub fn get_front_temperature(&mut self) -> ThermodynamicTemperature {
+        self.therminol_array_temperature().into_iter().last().unwrap()
+    }
+}
+
+impl ThermalPipeChain {
+
+    /// bulk therminol temperature of each pipe, listed from 
+    /// the back of the chain to the front
+    pub fn get_bulk_temperatures(&mut self) -> Vec<ThermodynamicTemperature> {
+        return self.pipes.iter_mut()
+            .map(|pipe| pipe.get_bulk_temperature())
+            .collect();
+    }
+
+    /// therminol temperature at the back of the first pipe
+    pub fn get_back_temperature(&mut self) -> ThermodynamicTemperature {
+        return self.pipes.first_mut()
+            .expect("thermal pipe chain should not be empty")
+            .get_back_temperature();
+    }
+
+    /// therminol temperature at the front of the last pipe
+    pub fn get_front_temperature(&mut self) -> ThermodynamicTemperature {
+        return self.pipes.last_mut()
+            .expect("thermal pipe chain should not be empty")
+            .get_front_temperature();
+    }
+
+    /// average density of the therminol in the chain, used 
+    /// for the advection interactions (boussinesq approximation)
+    pub fn get_average_density(&mut self) -> MassDensity {
+        let bulk_temperatures = self.get_bulk_temperatures();
+
+        let average_temperature_kelvin: f64 = bulk_temperatures.iter()
+            .map(|temperature| temperature.get::<kelvin>())
+            .sum::<f64>() / bulk_temperatures.len() as f64;
+
+        return LiquidMaterial::TherminolVP1.density(
+            ThermodynamicTemperature::new::<kelvin>(
+                average_temperature_kelvin)).unwrap();
+    }
+}
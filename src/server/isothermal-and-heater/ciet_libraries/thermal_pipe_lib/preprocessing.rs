@@ -0,0 +1,370 @@
+use super::{ThermalPipe, ThermalPipeChain};
+use crate::ctah_heat_transfer_lib::get_therminol_tube_heat_transfer_coeff;
+use thermal_hydraulics_rs::heat_transfer_lib::control_volume_calculations::common_functions::try_get_thermal_conductance_annular_cylinder;
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::ConstZero;
+
+impl ThermalPipe {
+
+    /// used to connect the arrays laterally 
+    /// you'll need to set the mass flowrate
+    ///
+    /// the pipe steel loses heat through the insulation to 
+    /// the ambient air
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate){
+
+        // clone each array and set them later
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into() .unwrap();
+
+        let mut therminol_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        let mut insulation_array_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        // note, must set mass flowrate first 
+        // otherwise there is by default zero flow through 
+        // the array, and the convective heat transfer 
+        // coefficient would be that for stagnant fluid
+
+        therminol_array_clone.set_mass_flowrate(
+            mass_flowrate);
+
+        self.therminol_array.set(therminol_array_clone.clone().into()).unwrap();
+
+        // first let's get all the conductances 
+
+        let insulation_to_air_conductance: ThermalConductance 
+        = self.get_air_insulation_shell_conductance();
+
+        let tube_to_fluid_conductance: ThermalConductance 
+        = self.get_therminol_node_steel_shell_conductance();
+
+        let tube_to_insulation_conductance: ThermalConductance 
+        = self.get_steel_to_fiberglass_conductance();
+
+        // then get the ambient temperature 
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        let ambient_temperature_vector: Vec<ThermodynamicTemperature> 
+        = vec![self.ambient_temperature; number_of_temperature_nodes];
+
+        let steel_temp_vector: Vec<ThermodynamicTemperature> 
+        = steel_shell_clone.get_temperature_vector().unwrap();
+
+        let fluid_temp_vector: Vec<ThermodynamicTemperature> 
+        = therminol_array_clone.get_temperature_vector().unwrap();
+
+        let insulation_temp_vector: Vec<ThermodynamicTemperature> 
+        = insulation_array_clone.get_temperature_vector().unwrap();
+
+        // second, fill them into the each array 
+
+        // insulation to air interaction
+
+        insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+            insulation_to_air_conductance,
+            ambient_temperature_vector
+        ).unwrap();
+
+        // insulation to steel shell interaction 
+
+        steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+            tube_to_insulation_conductance,
+            insulation_temp_vector
+        ).unwrap();
+
+        insulation_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+            tube_to_insulation_conductance,
+            steel_temp_vector.clone()
+        ).unwrap();
+
+        // steel shell to therminol interaction
+
+        steel_shell_clone.lateral_link_new_temperature_vector_avg_conductance(
+            tube_to_fluid_conductance,
+            fluid_temp_vector
+        ).unwrap();
+
+        therminol_array_clone.lateral_link_new_temperature_vector_avg_conductance(
+            tube_to_fluid_conductance,
+            steel_temp_vector
+        ).unwrap();
+
+        // now that lateral connections are done, 
+        // modify the heat transfer entity 
+
+        self.therminol_array.set(therminol_array_clone.into()).unwrap();
+
+        self.steel_shell.set(steel_shell_clone.into()).unwrap();
+
+        self.insulation_array.set(insulation_array_clone.into()
+        ).unwrap();
+
+        // adiabatic bc connections to make things finished 
+
+        self.zero_power_bc_connection();
+    }
+
+    /// the end of each node should have a zero power boundary condition 
+    /// connected to each of them at the bare minimum
+    ///
+    /// the therminol array is linked axially to its neighbours 
+    /// through the ThermalPipeChain instead
+    #[inline]
+    fn zero_power_bc_connection(&mut self){
+
+        let zero_power: Power = Power::ZERO;
+
+        let mut zero_power_bc: HeatTransferEntity = 
+        BCType::UserSpecifiedHeatAddition(zero_power).into();
+
+        // constant heat addition interaction 
+
+        let interaction: HeatTransferInteractionType = 
+        HeatTransferInteractionType::UserSpecifiedHeatAddition;
+
+        self.insulation_array.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.insulation_array.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.steel_shell.link_to_front(&mut zero_power_bc,
+            interaction).unwrap();
+
+        self.steel_shell.link_to_back(&mut zero_power_bc,
+            interaction).unwrap();
+    }
+
+    /// obtains air to insulation conductance per node, 
+    /// including conduction through the outer half of the 
+    /// insulation
+    #[inline]
+    pub fn get_air_insulation_shell_conductance(&mut self) 
+        -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let fiberglass_shell_temperature = fiberglass_clone.try_get_bulk_temperature() 
+            .unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_shell_temperature
+        ).unwrap();
+
+        let node_length: Length = self.get_node_length();
+
+        let od: Length = self.insulation_outer_diameter;
+
+        let insulation_mid_diameter: Length 
+        = 0.5 * (od + self.insulation_inner_diameter);
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            insulation_mid_diameter,
+            od,
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+
+        let node_area: Area = od * PI * node_length;
+
+        let air_convection_conductance: ThermalConductance
+        = node_area * self.heat_transfer_to_air;
+
+        let total_resistance = 
+        1.0/air_convection_conductance + 
+        1.0/fiberglass_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    /// obtains therminol to steel shell conductance per node,
+    /// including conduction through the inner half of the 
+    /// steel wall
+    #[inline]
+    pub fn get_therminol_node_steel_shell_conductance(&mut self) 
+        -> ThermalConductance {
+
+        let mut therminol_fluid_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        let mut steel_shell_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let steel_temperature: ThermodynamicTemperature 
+        = steel_shell_clone.try_get_bulk_temperature().unwrap();
+
+        let h_to_therminol: HeatTransfer = 
+        get_therminol_tube_heat_transfer_coeff(
+            &mut therminol_fluid_array_clone,
+            steel_temperature);
+
+        let node_length: Length = self.get_node_length();
+        let id = self.tube_inner_diameter;
+        let cylinder_mid_diameter: Length = 
+        0.5 * (self.tube_inner_diameter + self.tube_outer_diameter);
+
+        let therminol_to_steel_conductance: ThermalConductance = 
+        h_to_therminol * id * PI * node_length;
+
+        let steel_conductivity: ThermalConductivity = 
+        SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+            steel_temperature
+        ).unwrap();
+
+        let cylinder_node_conductance: ThermalConductance 
+        = try_get_thermal_conductance_annular_cylinder(
+            id,
+            cylinder_mid_diameter,
+            node_length,
+            steel_conductivity
+        ).unwrap();
+
+        let total_resistance = 
+        1.0/therminol_to_steel_conductance + 
+        1.0/cylinder_node_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    /// obtains steel to fiberglass conductance per node, 
+    /// from the middle of the steel wall to the middle of 
+    /// the insulation
+    #[inline]
+    pub fn get_steel_to_fiberglass_conductance(
+    &self) -> ThermalConductance {
+
+        let mut fiberglass_clone: SolidColumn = 
+        self.insulation_array.clone().try_into().unwrap();
+
+        let mut steel_clone: SolidColumn = 
+        self.steel_shell.clone().try_into().unwrap();
+
+        let node_length: Length = self.get_node_length();
+
+        let steel_mid_section_diameter = 0.5 * (self.tube_outer_diameter 
+        + self.tube_inner_diameter);
+
+        let fiberglass_mid_section_diameter = 0.5 * (self.insulation_inner_diameter
+        + self.insulation_outer_diameter);
+
+        let steel_od = self.tube_outer_diameter;
+
+        let steel_conductivity: ThermalConductivity 
+        = SolidMaterial::SteelSS304L.try_get_thermal_conductivity(
+            steel_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let fiberglass_conductivity: ThermalConductivity 
+        = SolidMaterial::Fiberglass.try_get_thermal_conductivity(
+            fiberglass_clone.try_get_bulk_temperature().unwrap()
+        ).unwrap();
+
+        let fiberglass_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            steel_od,
+            fiberglass_mid_section_diameter,
+            node_length,
+            fiberglass_conductivity
+        ).unwrap();
+        
+        let steel_layer_conductance: ThermalConductance = 
+        try_get_thermal_conductance_annular_cylinder(
+            steel_mid_section_diameter,
+            steel_od,
+            node_length,
+            steel_conductivity
+        ).unwrap();
+
+        let total_resistance = 1.0/fiberglass_layer_conductance 
+            + 1.0/steel_layer_conductance;
+
+        return 1.0/total_resistance;
+    }
+
+    #[inline]
+    fn get_node_length(&self) -> Length {
+        let mut therminol_array_clone: FluidArray = 
+        self.therminol_array.clone().try_into().unwrap();
+
+        let number_of_temperature_nodes = self.inner_nodes + 2;
+
+        return therminol_array_clone.get_component_length() 
+            / number_of_temperature_nodes as f64;
+    }
+}
+
+impl ThermalPipeChain {
+
+    /// links the therminol arrays of each pipe to the next 
+    /// pipe in the chain (front of one pipe to the back of 
+    /// the next)
+    ///
+    /// the advection interaction should carry the chain mass 
+    /// flowrate
+    #[inline]
+    pub fn link_in_series(&mut self,
+        advection_interaction: HeatTransferInteractionType){
+
+        for index in 1..self.pipes.len() {
+            let (upstream_pipes, downstream_pipes) = 
+                self.pipes.split_at_mut(index);
+
+            upstream_pipes[index - 1].therminol_array.link_to_front(
+                &mut downstream_pipes[0].therminol_array,
+                advection_interaction
+            ).unwrap();
+        }
+    }
+
+    /// links the back of the first pipe in the chain to 
+    /// another heat transfer entity, eg. a boundary condition 
+    /// or the therminol array of a heat exchanger
+    #[inline]
+    pub fn link_back_to(&mut self,
+        heat_transfer_entity: &mut HeatTransferEntity,
+        advection_interaction: HeatTransferInteractionType){
+
+        self.pipes.first_mut()
+            .expect("thermal pipe chain should not be empty")
+            .therminol_array.link_to_back(
+                heat_transfer_entity,
+                advection_interaction
+            ).unwrap();
+    }
+
+    /// links the front of the last pipe in the chain to 
+    /// another heat transfer entity
+    #[inline]
+    pub fn link_front_to(&mut self,
+        heat_transfer_entity: &mut HeatTransferEntity,
+        advection_interaction: HeatTransferInteractionType){
+
+        self.pipes.last_mut()
+            .expect("thermal pipe chain should not be empty")
+            .therminol_array.link_to_front(
+                heat_transfer_entity,
+                advection_interaction
+            ).unwrap();
+    }
+
+    /// makes the lateral connections for every pipe in the 
+    /// chain, which all have the same mass flowrate
+    #[inline]
+    pub fn lateral_and_miscellaneous_connections(&mut self,
+        mass_flowrate: MassRate){
+
+        for pipe in self.pipes.iter_mut() {
+            pipe.lateral_and_miscellaneous_connections(mass_flowrate);
+        }
+    }
+}
@@ -1,5 +1,9 @@
+use std::sync::Arc;
+
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
 
+use crate::correlation::Correlation;
+
 
 
 
@@ -16,7 +20,7 @@ pub trait TherminolCustomComponentTraits<'trait_lifetime> :
 
 // first we create an therminol pipe struct
 // and start implementing it
-pub struct TherminolCustomComponent<'pipe_lifetime> {
+pub struct TherminolCustomComponent {
 
     therminol_properties: TherminolVP1Properties,
     fluid_temp: ThermodynamicTemperature,
@@ -32,25 +36,30 @@ pub struct TherminolCustomComponent<'pipe_lifetime> {
     absolute_roughness: Length,
     name: String,
     
-    // these are trait object references which cannot be brought into
-    // the scope
-    custom_k: &'pipe_lifetime dyn Fn(f64) -> f64,
-    custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) ->f64,
+    // the correlations are plain data so that the component
+    // can be cloned, sent across threads and serialised
+    custom_k: Correlation,
+    custom_darcy: Correlation,
+
+    // function forms of the above correlations, these are
+    // what the thermal_hydraulics_rs traits work with
+    custom_k_function: Arc<dyn Fn(f64) -> f64 + Send + Sync>,
+    custom_darcy_function: Arc<dyn Fn(f64,f64) -> f64 + Send + Sync>,
 
 }
 
 impl<'pipe_lifetime> 
-TherminolCustomComponentTraits<'pipe_lifetime> for TherminolCustomComponent<'pipe_lifetime> {}
+TherminolCustomComponentTraits<'pipe_lifetime> for TherminolCustomComponent {}
 
 
 impl<'pipe_lifetime> 
 FluidCustomComponentCalcPressureChange<'pipe_lifetime> 
-for TherminolCustomComponent<'pipe_lifetime> {
+for TherminolCustomComponent {
 }
 
 impl<'pipe_lifetime> 
 FluidCustomComponentCalcPressureLoss<'pipe_lifetime> 
-for TherminolCustomComponent<'pipe_lifetime> {
+for TherminolCustomComponent {
 
     fn get_custom_component_absolute_roughness(
         &mut self) -> Length {
@@ -67,7 +76,7 @@ for TherminolCustomComponent<'pipe_lifetime> {
     fn get_custom_darcy(&mut self) 
         -> &dyn Fn(f64, f64) -> f64 {
 
-            return self.custom_darcy;
+            return &*self.custom_darcy_function;
 
         }
 
@@ -75,37 +84,48 @@ for TherminolCustomComponent<'pipe_lifetime> {
     fn get_custom_darcy_immutable(&self) 
         -> &dyn Fn(f64, f64) -> f64 {
 
-            return self.custom_darcy;
+            return &*self.custom_darcy_function;
 
         }
 
     fn get_custom_k(&mut self) 
         -> &dyn Fn(f64) -> f64 {
 
-            return self.custom_k;
+            return &*self.custom_k_function;
 
         }
 
     fn get_custom_k_immutable(&self) 
         -> &dyn Fn(f64) -> f64 {
 
-            return self.custom_k;
+            return &*self.custom_k_function;
 
         }
 
+    /// a borrowed closure cannot be stored within the component,
+    /// so it is sampled into a table, set_custom_k_correlation
+    /// sets the K correlation exactly
     fn set_custom_k(
         &mut self,
         custom_k: &'pipe_lifetime dyn Fn(f64) -> f64){
 
-        self.custom_k = custom_k;
-
+        self.set_custom_k_correlation(
+            Correlation::from_sampled_k(custom_k));
     }
 
+    /// a borrowed closure cannot be stored within the component,
+    /// so it is sampled into a table at the roughness ratio of
+    /// the component, set_custom_darcy_correlation sets the
+    /// darcy friction factor correlation exactly
     fn set_custom_darcy(
         &mut self,
         custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) -> f64){
 
-        self.custom_darcy = custom_darcy;
+        let roughness_ratio: f64 = 
+            (self.absolute_roughness / self.hydraulic_diameter).value;
+
+        self.set_custom_darcy_correlation(
+            Correlation::from_sampled_darcy(custom_darcy, roughness_ratio));
     }
 
 
@@ -114,7 +134,7 @@ for TherminolCustomComponent<'pipe_lifetime> {
 }
 
 impl<'pipe_lifetime> 
-FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
+FluidComponent for TherminolCustomComponent{
     fn get_pressure_loss(&mut self) -> Pressure {
 
         let fluid_mass_flowrate = 
@@ -139,11 +159,15 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             self.get_custom_component_absolute_roughness();
 
         // i need to make some immutable borrows here...
-        let custom_darcy: &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+        let custom_darcy_function =
+            self.custom_darcy_function.clone();
+        let custom_darcy: &dyn Fn(f64, f64) -> f64 =
+            &*custom_darcy_function;
 
+        let custom_k_function =
+            self.custom_k_function.clone();
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*custom_k_function;
 
         let pressure_loss =
             Self::
@@ -190,11 +214,15 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             self.get_custom_component_absolute_roughness_immutable();
 
         // i need to make some immutable borrows here...
-        let custom_darcy: &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+        let custom_darcy_function =
+            self.custom_darcy_function.clone();
+        let custom_darcy: &dyn Fn(f64, f64) -> f64 =
+            &*custom_darcy_function;
 
+        let custom_k_function =
+            self.custom_k_function.clone();
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*custom_k_function;
 
         let pressure_loss =
             Self:: fluid_custom_component_calc_pressure_loss(
@@ -254,11 +282,15 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             + hydrostatic_pressure_change
             + internal_pressure_source;
 
-        let custom_darcy : &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+        let custom_darcy_function =
+            self.custom_darcy_function.clone();
+        let custom_darcy : &dyn Fn(f64, f64) -> f64 =
+            &*custom_darcy_function;
 
+        let custom_k_function =
+            self.custom_k_function.clone();
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*custom_k_function;
 
 
         let cross_sectional_area = 
@@ -336,11 +368,15 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
             + hydrostatic_pressure_change
             + internal_pressure_source;
 
-        let custom_darcy : &dyn Fn(f64, f64) -> f64 = 
-            self.custom_darcy;
+        let custom_darcy_function =
+            self.custom_darcy_function.clone();
+        let custom_darcy : &dyn Fn(f64, f64) -> f64 =
+            &*custom_darcy_function;
 
+        let custom_k_function =
+            self.custom_k_function.clone();
         let custom_k : &dyn Fn(f64) -> f64 =
-            self.custom_k;
+            &*custom_k_function;
 
 
         let cross_sectional_area = 
@@ -500,7 +536,7 @@ FluidComponent for TherminolCustomComponent<'pipe_lifetime>{
 }
 
 
-impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
+impl TherminolCustomComponent{
 
     // let's implement a generic constructor
     pub fn new(name: &str,
@@ -510,8 +546,12 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
                cross_sectional_area: Area,
                hydraulic_diameter: Length,
                absolute_roughness: Length,
-               custom_k: &'pipe_lifetime dyn Fn(f64)-> f64 ,
-               custom_darcy: &'pipe_lifetime dyn Fn(f64,f64) -> f64 ) -> Self {
+               custom_k: Correlation,
+               custom_darcy: Correlation) -> Self {
+
+        let custom_k_function = Self::get_custom_k_function(&custom_k);
+        let custom_darcy_function = Self::get_custom_darcy_function(
+            &custom_darcy);
 
         return Self { 
             name: name.to_string(),
@@ -527,6 +567,8 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
             absolute_roughness: absolute_roughness,
             custom_k: custom_k,
             custom_darcy: custom_darcy,
+            custom_k_function,
+            custom_darcy_function,
         };
 
     }
@@ -540,5 +582,83 @@ impl<'pipe_lifetime> TherminolCustomComponent<'pipe_lifetime>{
         self.name = name.to_string();
     }
 
+    /// gets the fluid temperature used for fluid properties
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        return self.fluid_temp;
+    }
+
+    /// sets the fluid temperature used for fluid properties,
+    /// this changes both the friction and hydrostatic terms
+    pub fn set_fluid_temperature(&mut self, 
+        fluid_temp: ThermodynamicTemperature) {
+
+        self.fluid_temp = fluid_temp;
+    }
+
+
+    /// returns the custom K correlation
+    pub fn get_custom_k_correlation(&self) -> &Correlation {
+        return &self.custom_k;
+    }
+
+    /// returns the custom darcy friction factor correlation
+    pub fn get_custom_darcy_correlation(&self) -> &Correlation {
+        return &self.custom_darcy;
+    }
+
+    /// sets the custom K correlation, this can be done at runtime
+    pub fn set_custom_k_correlation(&mut self, custom_k: Correlation) {
+        self.custom_k_function = Self::get_custom_k_function(&custom_k);
+        self.custom_k = custom_k;
+    }
+
+    /// sets the custom darcy friction factor correlation,
+    /// this can be done at runtime
+    pub fn set_custom_darcy_correlation(&mut self,
+        custom_darcy: Correlation) {
+        self.custom_darcy_function = Self::get_custom_darcy_function(
+            &custom_darcy);
+        self.custom_darcy = custom_darcy;
+    }
+
+    // wraps a correlation as a K function of Re
+    fn get_custom_k_function(custom_k: &Correlation)
+        -> Arc<dyn Fn(f64) -> f64 + Send + Sync> {
+        let custom_k = custom_k.clone();
+        return Arc::new(move |reynolds_number: f64|
+            custom_k.evaluate_k(reynolds_number));
+    }
+
+    // wraps a correlation as a darcy friction factor function
+    // of Re and roughness ratio
+    fn get_custom_darcy_function(custom_darcy: &Correlation)
+        -> Arc<dyn Fn(f64,f64) -> f64 + Send + Sync> {
+        let custom_darcy = custom_darcy.clone();
+        return Arc::new(move |reynolds_number: f64, roughness_ratio: f64|
+            custom_darcy.evaluate(reynolds_number, roughness_ratio));
+    }
+
 }
 
+
+impl Clone for TherminolCustomComponent {
+    fn clone(&self) -> Self {
+        return Self {
+            therminol_properties: TherminolVP1Properties::new(),
+            fluid_temp: self.fluid_temp,
+            fluid_mass_flowrate: self.fluid_mass_flowrate,
+            internal_pressure: self.internal_pressure,
+            incline_angle: self.incline_angle,
+            component_length: self.component_length,
+            cross_sectional_area: self.cross_sectional_area,
+            hydraulic_diameter: self.hydraulic_diameter,
+            pressure_loss: self.pressure_loss,
+            absolute_roughness: self.absolute_roughness,
+            name: self.name.clone(),
+            custom_k: self.custom_k.clone(),
+            custom_darcy: self.custom_darcy.clone(),
+            custom_k_function: self.custom_k_function.clone(),
+            custom_darcy_function: self.custom_darcy_function.clone(),
+        };
+    }
+}
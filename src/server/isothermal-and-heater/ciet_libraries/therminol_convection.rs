@@ -0,0 +1,38 @@
+use thermal_hydraulics_rs::prelude::alpha_nightly::*;
+use uom::si::ratio::ratio;
+
+/// fully developed laminar nusselt number in a tube with
+/// uniform heat flux
+///
+/// the heater and CTAH nusselt correlations go to zero at zero
+/// flow, in which case there is still conduction into the
+/// fluid, so nusselt numbers are floored at this value
+pub const LAMINAR_NUSSELT_FLOOR: f64 = 4.354;
+
+/// floors a nusselt number estimate at LAMINAR_NUSSELT_FLOOR
+#[inline]
+pub fn floor_at_laminar_nusselt(nusselt_estimate: Ratio) -> Ratio {
+
+    let laminar_nusselt = Ratio::new::<ratio>(LAMINAR_NUSSELT_FLOOR);
+
+    if nusselt_estimate < laminar_nusselt {
+        return laminar_nusselt;
+    }
+
+    return nusselt_estimate;
+}
+
+/// reynolds number based on the hydraulic diameter, used for
+/// heat transfer
+///
+/// flow may be zero (valve shut) or reversed (natural
+/// circulation), so the magnitude of the flowrate is used
+/// for heat transfer purposes
+#[inline]
+pub fn get_heat_transfer_reynolds(mass_flowrate: MassRate,
+    flow_area: Area,
+    hydraulic_diameter: Length,
+    viscosity: DynamicViscosity) -> Ratio {
+
+    return mass_flowrate.abs()/flow_area*hydraulic_diameter / viscosity;
+}
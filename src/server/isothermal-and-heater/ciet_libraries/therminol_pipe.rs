@@ -403,5 +403,36 @@ impl<'pipe_lifetime> TherminolPipe{
 
         self.name = name.to_string();
     }
+
+    /// gets the fluid temperature used for fluid properties
+    pub fn get_fluid_temperature(&self) -> ThermodynamicTemperature {
+        return self.fluid_temp;
+    }
+
+    /// sets the fluid temperature used for fluid properties,
+    /// this changes both the friction and hydrostatic terms
+    pub fn set_fluid_temperature(&mut self, 
+        fluid_temp: ThermodynamicTemperature) {
+
+        self.fluid_temp = fluid_temp;
+    }
 }
 
+
+impl Clone for TherminolPipe {
+    fn clone(&self) -> Self {
+        return Self {
+            therminol_properties: TherminolVP1Properties::new(),
+            fluid_temp: self.fluid_temp,
+            fluid_mass_flowrate: self.fluid_mass_flowrate,
+            internal_pressure: self.internal_pressure,
+            incline_angle: self.incline_angle,
+            component_length: self.component_length,
+            hydraulic_diameter: self.hydraulic_diameter,
+            pressure_loss: self.pressure_loss,
+            form_loss_k: self.form_loss_k,
+            absolute_roughness: self.absolute_roughness,
+            name: self.name.clone(),
+        };
+    }
+}
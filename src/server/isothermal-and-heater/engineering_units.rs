@@ -0,0 +1,262 @@
+//! engineering units and ranges of the CIET OPC-UA variables
+//!
+//! every numeric variable is an AnalogItemType with the
+//! EngineeringUnits, EURange and InstrumentRange properties, so
+//! that clients need not read the units from the node names
+//! (eg. _degC, _kilowatts, _pa)
+//!
+//! the units are those of the uom quantities the server reads
+//! its values from, with the UNECE Recommendation 20 codes
+//! OPC-UA uses to identify them
+//!
+//! the ranges are provisional unless a source is given, they 
+//! are representative of CIET operation and should be replaced 
+//! with the measured ranges of the CIET instruments
+use opcua::server::prelude::*;
+use uom::si::Unit;
+use uom::si::frequency::hertz;
+use uom::si::length::millimeter;
+use uom::si::mass_rate::kilogram_per_second;
+use uom::si::power::kilowatt;
+use uom::si::pressure::pascal;
+use uom::si::thermodynamic_temperature::degree_celsius;
+use uom::si::time::millisecond;
+
+use crate::object_model::get_child_node_id;
+use crate::CTAH_PUMP_CURVE_AT_RATED_SPEED;
+
+/// namespace of the UNECE unit codes in EUInformation
+pub const UNECE_UNITS_NAMESPACE_URI: &str =
+    "http://www.opcfoundation.org/UA/units/un/cefact";
+
+/// units of the CIET variables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EngineeringUnit {
+    DegreeCelsius,
+    Kilowatt,
+    Pascal,
+    KilogramPerSecond,
+    Hertz,
+    Millisecond,
+    Millimeter,
+    /// reactivity divided by the delayed neutron fraction, which
+    /// has no UNECE code
+    Dollar,
+    /// ratios such as the pump speed ratio or relative powers
+    Dimensionless,
+}
+
+impl EngineeringUnit {
+
+    /// UNECE Recommendation 20 common code, if the unit has one
+    pub fn get_unece_code(&self) -> Option<&'static str> {
+        match self {
+            EngineeringUnit::DegreeCelsius => Some("CEL"),
+            EngineeringUnit::Kilowatt => Some("KWT"),
+            EngineeringUnit::Pascal => Some("PAL"),
+            EngineeringUnit::KilogramPerSecond => Some("KGS"),
+            EngineeringUnit::Hertz => Some("HTZ"),
+            EngineeringUnit::Millisecond => Some("C26"),
+            EngineeringUnit::Millimeter => Some("MMT"),
+            EngineeringUnit::Dollar => None,
+            EngineeringUnit::Dimensionless => Some("C62"),
+        }
+    }
+
+    /// unit symbol, as uom abbreviates it
+    pub fn get_display_name(&self) -> &'static str {
+        match self {
+            EngineeringUnit::DegreeCelsius => degree_celsius::abbreviation(),
+            EngineeringUnit::Kilowatt => kilowatt::abbreviation(),
+            EngineeringUnit::Pascal => pascal::abbreviation(),
+            EngineeringUnit::KilogramPerSecond => kilogram_per_second::abbreviation(),
+            EngineeringUnit::Hertz => hertz::abbreviation(),
+            EngineeringUnit::Millisecond => millisecond::abbreviation(),
+            EngineeringUnit::Millimeter => millimeter::abbreviation(),
+            EngineeringUnit::Dollar => "$",
+            EngineeringUnit::Dimensionless => "1",
+        }
+    }
+
+    /// unit name, as uom spells it
+    pub fn get_description(&self) -> &'static str {
+        match self {
+            EngineeringUnit::DegreeCelsius => degree_celsius::singular(),
+            EngineeringUnit::Kilowatt => kilowatt::singular(),
+            EngineeringUnit::Pascal => pascal::singular(),
+            EngineeringUnit::KilogramPerSecond => kilogram_per_second::singular(),
+            EngineeringUnit::Hertz => hertz::singular(),
+            EngineeringUnit::Millisecond => millisecond::singular(),
+            EngineeringUnit::Millimeter => millimeter::singular(),
+            EngineeringUnit::Dollar => "dollar (reactivity over delayed neutron fraction)",
+            EngineeringUnit::Dimensionless => "one",
+        }
+    }
+
+    /// the unit id is the UNECE code packed into an integer,
+    /// one byte per character, or -1 for units without a code
+    pub fn get_unit_id(&self) -> i32 {
+        match self.get_unece_code() {
+            Some(unece_code) => unece_code.bytes()
+                .fold(0, |unit_id, character| (unit_id << 8) | character as i32),
+            None => -1,
+        }
+    }
+
+    pub fn get_eu_information(&self) -> EUInformation {
+        return EUInformation {
+            namespace_uri: UNECE_UNITS_NAMESPACE_URI.into(),
+            unit_id: self.get_unit_id(),
+            display_name: LocalizedText::new("en", self.get_display_name()),
+            description: LocalizedText::new("en", self.get_description()),
+        };
+    }
+}
+
+/// unit and ranges of a numeric variable
+///
+/// the EURange is the range the value normally takes while
+/// CIET operates, the InstrumentRange is the range the server
+/// can report (or accept, for controller nodes, whose writes 
+/// are rejected with BadOutOfRange outside of it)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalogItem {
+    pub unit: EngineeringUnit,
+    pub eu_range: (f64, f64),
+    pub instrument_range: (f64, f64),
+}
+
+impl AnalogItem {
+    pub fn new(unit: EngineeringUnit,
+        eu_range: (f64, f64),
+        instrument_range: (f64, f64)) -> Self {
+        return Self {
+            unit,
+            eu_range,
+            instrument_range,
+        };
+    }
+
+    /// therminol fluid temperatures, the therminol VP-1 property
+    /// correlations of thermal_hydraulics_rs are valid from 20 
+    /// to 180 degC, the instrument range is provisional
+    pub fn fluid_temperature() -> Self {
+        return Self::new(EngineeringUnit::DegreeCelsius,
+            (20.0, 180.0), (0.0, 400.0));
+    }
+
+    /// pressure changes across components and branches, up to 
+    /// the shutoff pressure of the ctah pump curve at its rated 
+    /// speed, which is itself representative (see 
+    /// CTAH_PUMP_CURVE_AT_RATED_SPEED), the instrument range is 
+    /// provisional
+    pub fn pressure_change() -> Self {
+        let (_, shutoff_pressure_pascals) = CTAH_PUMP_CURVE_AT_RATED_SPEED[0];
+        return Self::new(EngineeringUnit::Pascal,
+            (-shutoff_pressure_pascals, shutoff_pressure_pascals), 
+            (-1.0e6, 1.0e6));
+    }
+
+    /// primary loop branch flowrates, which reverse in natural
+    /// circulation, up to the runout flowrate of the ctah pump 
+    /// curve at its rated speed, which is itself representative 
+    /// (see CTAH_PUMP_CURVE_AT_RATED_SPEED), the instrument range
+    /// is provisional
+    pub fn branch_mass_flowrate() -> Self {
+        let (runout_flowrate_kg_per_s, _) = CTAH_PUMP_CURVE_AT_RATED_SPEED[
+            CTAH_PUMP_CURVE_AT_RATED_SPEED.len() - 1];
+        return Self::new(EngineeringUnit::KilogramPerSecond,
+            (-runout_flowrate_kg_per_s, runout_flowrate_kg_per_s), 
+            (-2.0, 2.0));
+    }
+
+    /// calculation times, up to the polling period they run in,
+    /// the server reports them as u16
+    pub fn calculation_time(period_ms: u64) -> Self {
+        return Self::new(EngineeringUnit::Millisecond,
+            (0.0, period_ms as f64), (0.0, u16::MAX as f64));
+    }
+
+    /// heater power in kilowatts, provisional, the CIET heater 
+    /// is taken to be rated at 10 kW
+    pub fn heater_power() -> Self {
+        return Self::new(EngineeringUnit::Kilowatt,
+            (0.0, 10.0), (0.0, 100.0));
+    }
+
+    /// heat removed by the heat exchangers in kilowatts, which
+    /// is negative should they heat the loop instead, provisional
+    pub fn heat_removal() -> Self {
+        return Self::new(EngineeringUnit::Kilowatt,
+            (0.0, 10.0), (-100.0, 100.0));
+    }
+
+    /// VFD frequencies of the pump and fans, up to twice the
+    /// rated speed, provisional
+    pub fn vfd_frequency(rated_speed_hertz: f64) -> Self {
+        return Self::new(EngineeringUnit::Hertz,
+            (0.0, rated_speed_hertz), (0.0, 2.0 * rated_speed_hertz));
+    }
+
+    /// reactivity in dollars, at one dollar the reactor is
+    /// prompt critical, the instrument range is provisional
+    pub fn reactivity() -> Self {
+        return Self::new(EngineeringUnit::Dollar,
+            (-1.0, 1.0), (-10.0, 10.0));
+    }
+
+    /// whether a value is within the instrument range, NaN and 
+    /// infinite values never are
+    pub fn is_in_instrument_range(&self, value: f64) -> bool {
+        let (low, high) = self.instrument_range;
+        return value.is_finite() && low <= value && value <= high;
+    }
+}
+
+/// adds the EngineeringUnits, EURange and InstrumentRange
+/// properties to a numeric variable and makes it an
+/// AnalogItemType
+pub fn add_analog_item_properties(address_space: &mut AddressSpace,
+    variable: &NodeId,
+    analog_item: &AnalogItem) {
+
+    let eu_information = analog_item.unit.get_eu_information();
+    let eu_range = Range {
+        low: analog_item.eu_range.0,
+        high: analog_item.eu_range.1,
+    };
+    let instrument_range = Range {
+        low: analog_item.instrument_range.0,
+        high: analog_item.instrument_range.1,
+    };
+
+    let properties = [
+        ("EngineeringUnits", DataTypeId::EUInformation,
+         ExtensionObject::from_encodable(
+             ObjectId::EUInformation_Encoding_DefaultBinary, &eu_information)),
+        ("EURange", DataTypeId::Range,
+         ExtensionObject::from_encodable(
+             ObjectId::Range_Encoding_DefaultBinary, &eu_range)),
+        ("InstrumentRange", DataTypeId::Range,
+         ExtensionObject::from_encodable(
+             ObjectId::Range_Encoding_DefaultBinary, &instrument_range)),
+    ];
+
+    for (property_name, data_type, value) in properties {
+        VariableBuilder::new(&get_child_node_id(variable, property_name),
+            property_name, property_name)
+            .data_type(data_type)
+            .value(value)
+            .property_of(variable.clone())
+            .has_type_definition(VariableTypeId::PropertyType)
+            .insert(address_space);
+    }
+
+    // variables made with VariableBuilder are BaseDataVariableType,
+    // those made with Variable::new have no type definition
+    let base_data_variable_type: NodeId =
+        VariableTypeId::BaseDataVariableType.into();
+    address_space.delete_reference(variable, &base_data_variable_type,
+        ReferenceTypeId::HasTypeDefinition);
+    address_space.set_node_type(variable, VariableTypeId::AnalogItemType);
+}
@@ -187,7 +187,7 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
     dhx_branch_valve_open: bool,
     ctah_branch_valve_open: bool,
     temperature_degrees_c: f64,
-    error_fraction: f64) -> Pressure {
+    error_fraction: f64) -> Result<Pressure, FlowSolverError> {
 
     // check if mass flowrate value is less than 0.0004 kg/s 
     // if so, return 0 Pa (negligible pressure error)
@@ -197,18 +197,18 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
     // 10 Pa is the branch pressure change I will use 
     // for finite differencing.
     if ctah_branch_mass_flowrate.value.abs() <= 0.0004_f64 {
-        return Pressure::new::<pascal>(0.0);
+        return Ok(Pressure::new::<pascal>(0.0));
     }
 
     // if ctah branch is closed, or if dhx and heater branch are
     // both closed, return zero, prevents crashes
     // or panics due to nonconvergence
     if ctah_branch_valve_open == false {
-        return Pressure::new::<pascal>(0.0);
+        return Ok(Pressure::new::<pascal>(0.0));
     }
 
     if !dhx_branch_valve_open && !heater_branch_valve_open == true {
-        return Pressure::new::<pascal>(0.0);
+        return Ok(Pressure::new::<pascal>(0.0));
     }
     // now let me obtain the branch pressure change for the
     // fixed ctah_branch_mass_flowrate
@@ -226,13 +226,13 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
         get_heater_branch_mass_flowrate(
             ctah_branch_pressure_change_pascals, 
             temperature_degrees_c, 
-            heater_branch_valve_open);
+            heater_branch_valve_open)?;
 
     let dhx_branch_mass_flowrate: f64 = 
         get_dhx_branch_mass_flowrate(
             ctah_branch_pressure_change_pascals, 
             temperature_degrees_c, 
-            dhx_branch_valve_open);
+            dhx_branch_valve_open)?;
 
     let mass_conservation_error: f64 = 
         ctah_branch_mass_flowrate.value
@@ -262,20 +262,20 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
         temperature_degrees_c: f64,
         heater_branch_valve_open: bool,
         dhx_branch_valve_open: bool,
-        ctah_branch_valve_open: bool) -> (MassRate, Pressure) {
+        ctah_branch_valve_open: bool) -> Result<(MassRate, Pressure), FlowSolverError> {
         // step 1, obtain mass flowrate for dhx and heater branch
 
         let heater_branch_flowrate_kg_per_s: f64
             = get_heater_branch_mass_flowrate(
                 branch_pressure_change_pascals, 
                 temperature_degrees_c, 
-                heater_branch_valve_open);
+                heater_branch_valve_open)?;
 
         let dhx_branch_mass_flowrate_kg_per_s: f64 
             = get_dhx_branch_mass_flowrate(
                 branch_pressure_change_pascals, 
                 temperature_degrees_c, 
-                dhx_branch_valve_open);
+                dhx_branch_valve_open)?;
 
         // now obtain mass flowrate for ctah_branch
         // normally 
@@ -325,7 +325,7 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
         let ctah_pump_pressure = 
             Pressure::new::<pascal>(ctah_pump_pressure_pascals);
 
-        return (ctah_mass_flow,ctah_pump_pressure);
+        return Ok((ctah_mass_flow,ctah_pump_pressure));
 
     }
 
@@ -335,7 +335,7 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
             temperature_degrees_c, 
             heater_branch_valve_open, 
             dhx_branch_valve_open, 
-            ctah_branch_valve_open);
+            ctah_branch_valve_open)?;
 
     let (ctah_mass_flow_2, ctah_pump_pressure_2) = 
         obtain_mass_flowrate_and_ctah_loop_pressure_drop_pair(
@@ -343,7 +343,7 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
             temperature_degrees_c, 
             heater_branch_valve_open, 
             dhx_branch_valve_open, 
-            ctah_branch_valve_open);
+            ctah_branch_valve_open)?;
 
     let pressure_error_due_to_flowrate_error: Pressure = 
         ctah_branch_mass_flowrate * 
@@ -351,7 +351,7 @@ pub fn parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
         (ctah_pump_pressure_2 - ctah_pump_pressure_1)/
         (ctah_mass_flow_2 - ctah_mass_flow_1);
 
-    return pressure_error_due_to_flowrate_error;
+    return Ok(pressure_error_due_to_flowrate_error);
 }
 
 
@@ -374,10 +374,6 @@ pub fn get_manometer_reading_error_pascals() -> Pressure {
 pub fn get_fldk_error_pascals_ctah_branch(mass_flowrate: MassRate,
     error_fraction: f64) -> Pressure {
 
-    //import necessary things...
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::factory;
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::CalcPressureChange;
-
     let temperature_degrees_c: f64 = 20.0;
     // for this, we are taking a blanket 10\% error
     // but we can customise the error fraction
@@ -385,92 +381,17 @@ pub fn get_fldk_error_pascals_ctah_branch(mass_flowrate: MassRate,
     let fluid_temp = ThermodynamicTemperature::new::<
         degree_celsius>(temperature_degrees_c);
 
-    let zero_mass_flow = 
-        MassRate::new::<kilogram_per_second>(0.0);
-
-
-
-
-    //MX41
-    let static_mixer_41_6 = factory::StaticMixer41::get();
-
-    // let's get the component for ctah
-    // only the horizontal bit has experimentally determined
-    // fldk values
-    let ctah_horizontal_7b = factory::CTAHHorizontal::get();
-
-    // MX40
-    let static_mixer_40_8 = factory::StaticMixer40::get();
-
-    // let's get flowmeter 14a
-    let flowmeter_40_14a = factory::Flowmeter40::get();
-
-    // now that we've gotten our items, we can
-    // then sum up the pressure change contributions
-    // given
-
-
-
-    // static mixer 41 pressure
-
-    let pressure_drop_mx41 =
-        CalcPressureChange::from_mass_rate(
-            &static_mixer_41_6,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &static_mixer_41_6,
-            zero_mass_flow,
-            fluid_temp);
-
-
-    // ctah pressure drop
-
-    let pressure_drop_ctah = 
-        CalcPressureChange::from_mass_rate(
-            &ctah_horizontal_7b,
-            mass_flowrate,
-            fluid_temp) 
-        - CalcPressureChange::from_mass_rate(
-            &ctah_horizontal_7b,
-            zero_mass_flow,
-            fluid_temp);
-
-    // static mixer 40 pressure
-    let pressure_drop_mx40 =
-        CalcPressureChange::from_mass_rate(
-            &static_mixer_40_8,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &static_mixer_40_8,
-            zero_mass_flow,
-            fluid_temp);
-
-    // fm40 pressure drop
-    let pressure_drop_fm40 =
-        CalcPressureChange::from_mass_rate(
-            &flowmeter_40_14a,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &flowmeter_40_14a,
-            zero_mass_flow,
-            fluid_temp);
-
-
-    // now we calculate the sum of squares
-    let pressure_sq_deviation = 
-        (error_fraction * pressure_drop_mx41) * (error_fraction * pressure_drop_mx41)
-        + (error_fraction * pressure_drop_mx40) * (error_fraction * pressure_drop_mx40)
-        + (error_fraction * pressure_drop_fm40) * (error_fraction * pressure_drop_fm40)
-        + (error_fraction * pressure_drop_ctah) * (error_fraction * pressure_drop_ctah);
-
-    let pressure_deviation_heater_branch = 
-        pressure_sq_deviation.sqrt();
-
-    // convert the object to f64 and return
-    return pressure_deviation_heater_branch;
+    // MX41, the ctah (only the horizontal bit has experimentally
+    // determined fldk values), MX40 and flowmeter 14a
+    let component_names = [
+        "static_mixer_41_label_6",
+        "ctah_horizontal_label_7b",
+        "static_mixer_40_label_8",
+        "flowmeter_40_14a",
+    ];
+
+    return get_fldk_error_pascals(&component_names, mass_flowrate,
+        fluid_temp, error_fraction);
 }
 
 /// obtains the pressure loss coefficient errors due
@@ -479,10 +400,6 @@ pub fn get_fldk_error_pascals_ctah_branch(mass_flowrate: MassRate,
 pub fn get_fldk_error_pascals_heater_branch(mass_flowrate: MassRate,
     error_fraction: f64) -> Pressure {
 
-    //import necessary things...
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::factory;
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::CalcPressureChange;
-
     let temperature_degrees_c: f64 = 20.0;
     // for this, we are taking a blanket 10\% error
     // but we can customise the error fraction
@@ -490,58 +407,14 @@ pub fn get_fldk_error_pascals_heater_branch(mass_flowrate: MassRate,
     let fluid_temp = ThermodynamicTemperature::new::<
         degree_celsius>(temperature_degrees_c);
 
-    let zero_mass_flow = 
-        MassRate::new::<kilogram_per_second>(0.0);
-
-
-    let mx10_2 = factory::StaticMixer10::get();
-
-    let heater_version_1_1 =
-        factory::CietHeaterVersion1::get();
-
+    // static mixer 2 and the heater
+    let component_names = [
+        "static_mixer_10_label_2",
+        "heater_version_1_label_1",
+    ];
 
-
-    // now that we've gotten our items, we can
-    // then sum up the pressure change contributions
-    // given
-
-
-
-    // static mixer 2 pressure
-
-    let pressure_drop_mx10 =
-        CalcPressureChange::from_mass_rate(
-            &mx10_2,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &mx10_2,
-            zero_mass_flow,
-            fluid_temp);
-
-
-    // heater
-
-    let pressure_drop_heater = 
-        CalcPressureChange::from_mass_rate(
-            &heater_version_1_1,
-            mass_flowrate,
-            fluid_temp) 
-        - CalcPressureChange::from_mass_rate(
-            &heater_version_1_1,
-            zero_mass_flow,
-            fluid_temp);
-
-    // now we calculate the sum of squares
-    let pressure_sq_deviation = 
-        (error_fraction * pressure_drop_mx10) * (error_fraction * pressure_drop_mx10)
-        + (error_fraction * pressure_drop_heater) * (error_fraction * pressure_drop_heater);
-
-    let pressure_deviation_heater_branch = 
-        pressure_sq_deviation.sqrt();
-
-    // convert the object to f64 and return
-    return pressure_deviation_heater_branch;
+    return get_fldk_error_pascals(&component_names, mass_flowrate,
+        fluid_temp, error_fraction);
 }
 
 /// obtains the pressure loss coefficient errors due
@@ -550,10 +423,6 @@ pub fn get_fldk_error_pascals_heater_branch(mass_flowrate: MassRate,
 pub fn get_fldk_error_pascals_dhx_branch(mass_flowrate: MassRate,
     error_fraction: f64) -> Pressure {
 
-    //import necessary things...
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::factory;
-    use thermal_hydraulics_rs::fluid_mechanics_lib::therminol_component::CalcPressureChange;
-
     let temperature_degrees_c: f64 = 20.0;
     // for this, we are taking a blanket 10\% error
     // but we can customise the error fraction
@@ -561,70 +430,35 @@ pub fn get_fldk_error_pascals_dhx_branch(mass_flowrate: MassRate,
     let fluid_temp = ThermodynamicTemperature::new::<
         degree_celsius>(temperature_degrees_c);
 
-    let zero_mass_flow = 
-        MassRate::new::<kilogram_per_second>(0.0);
-
-
-
-    // static mixer 20 and 21
-    let mx20_23 = factory::StaticMixer20::get();
-    let mx21_25 = factory::StaticMixer21::get();
-
-    // flowmeter 21a (FM-20)
-    let flowmeter_20_21a = factory::Flowmeter20::get();
-
-    // now that we've gotten our items, we can
-    // then sum up the pressure change contributions
-    // given
-
-
-
-    // static mixer 20 and 21 pressure
-
-    let pressure_drop_mx20 = 
-        CalcPressureChange::from_mass_rate(
-            &mx20_23,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &mx20_23,
-            zero_mass_flow,
-            fluid_temp);
-
-
-    let pressure_drop_mx21 = 
-        CalcPressureChange::from_mass_rate(
-            &mx21_25,
-            mass_flowrate,
-            fluid_temp) - 
-        CalcPressureChange::from_mass_rate(
-            &mx21_25,
-            zero_mass_flow,
-            fluid_temp);
-
-    // FM20
+    // static mixer 20 and 21 and flowmeter 21a (FM-20)
+    let component_names = [
+        "static_mixer_20_label_23",
+        "static_mixer_21_label_25",
+        "flowmeter_20_label_21a",
+    ];
 
+    return get_fldk_error_pascals(&component_names, mass_flowrate,
+        fluid_temp, error_fraction);
+}
 
-    let pressure_drop_fm20 = 
-        CalcPressureChange::from_mass_rate(
-            &flowmeter_20_21a,
-            mass_flowrate,
-            fluid_temp) 
-        - CalcPressureChange::from_mass_rate(
-            &flowmeter_20_21a,
-            zero_mass_flow,
-            fluid_temp);
+/// combines the pressure loss errors of several components of 
+/// the default loop definition, each taken as error_fraction of
+/// its pressure loss, as the square root of their sum of squares
+fn get_fldk_error_pascals(component_names: &[&str],
+    mass_flowrate: MassRate,
+    fluid_temp: ThermodynamicTemperature,
+    error_fraction: f64) -> Pressure {
 
+    let mut pressure_sq_deviation = 
+        Pressure::new::<pascal>(0.0) * Pressure::new::<pascal>(0.0);
 
-    // now we calculate the sum of squares
-    let pressure_sq_deviation = 
-        (error_fraction * pressure_drop_fm20) * (error_fraction * pressure_drop_fm20)
-        + (error_fraction * pressure_drop_mx21) * (error_fraction * pressure_drop_mx21)
-        + (error_fraction * pressure_drop_mx20) * (error_fraction * pressure_drop_mx20);
+    for component_name in component_names {
+        let component = get_default_ciet_component(component_name, fluid_temp);
+        let pressure_drop = get_pressure_loss(&component, mass_flowrate);
 
-    let pressure_deviation_dhx_branch = 
-        pressure_sq_deviation.sqrt();
+        pressure_sq_deviation = pressure_sq_deviation
+            + (error_fraction * pressure_drop) * (error_fraction * pressure_drop);
+    }
 
-    // convert the object to f64 and return
-    return pressure_deviation_dhx_branch;
+    return pressure_sq_deviation.sqrt();
 }
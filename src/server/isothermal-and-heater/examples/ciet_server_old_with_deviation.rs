@@ -1,28 +1,72 @@
 
 #[warn(missing_docs)]
 use opcua::server::prelude::*;
-use local_ip_address::local_ip;
-use opcua::server::config;
 
 use thermal_hydraulics_rs::prelude::alpha_nightly::*;
 use uom::si::power::kilowatt;
+use uom::si::frequency::hertz;
 
 use super::ciet_functions_for_deviation_calcs::*;
-use std::{time::{Instant, SystemTime}, sync::{Arc, Mutex}};
+use std::{time::{Instant, SystemTime, Duration}, sync::{Arc, Mutex}, thread};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::HashMap;
+use opcua::server::session::SessionManager;
+use crate::isothermal_ciet_facility::CIETIsothermalFacility;
 use crate::heater::{*, struct_supports::StructuralSupport};
+use crate::ctah_heat_transfer_lib::{CoiledTubeAirHeatExchanger, 
+    CTAH_FAN_RATED_SPEED_HERTZ, TCHX_FAN_RATED_SPEED_HERTZ};
+use crate::dhx_heat_transfer_lib::DHXShellAndTubeHeatExchanger;
+use crate::dracs_loop::{DRACSLoop, DRACSLoopTemperatures};
+use crate::loop_temperatures::CIETLoopTemperatures;
+use crate::loop_definition::{CIETLoopDefinition, CIETComponentDefinition,
+    CIETThermalComponentNames, split_at_thermal_components};
+use crate::thermal_pipe_lib::{ThermalPipeChain, get_mixed_temperature};
+use crate::point_kinetics_lib::PointKinetics;
+use crate::pressure_breakdown::ComponentPressureBreakdown;
+use crate::manometer::ManometerBoard;
+use crate::server_config::{CIETServerConfig, EndpointSecurity};
+use crate::user_roles::{UserRoles, UserRole};
+use crate::object_model::{self, CIETObjectTypes, CIETBranchObjectNodes, 
+    CIETComponentObjectNodes};
+use crate::engineering_units::{AnalogItem, EngineeringUnit, 
+    add_analog_item_properties};
 //use opcua::server::address_space;
 
 /// In this example, we use the legacy ciet server codes used in maturin
 /// to generate the results
-pub fn construct_and_run_ciet_server(run_server: bool){
-
-    let mut server = build_standard_server();
+///
+/// the heater_version selects which heated section model 
+/// (heater version 2 or heater version 1, bare or insulated) 
+/// is used for the heater thermal hydraulics
+///
+/// the air_heat_transfer_model selects whether the heater and its 
+/// supports use the callibrated heat transfer coefficients to air, 
+/// or natural convection and radiation correlations
+///
+/// the axial_power_shape is the initial heater axial power shape,
+/// which can be changed afterwards through the 
+/// heater_axial_power_profile array
+///
+/// if point_kinetics_enabled, the heater power is computed by the 
+/// point kinetics model rather than set by the operator, this can 
+/// also be switched on and off through point_kinetics_enabled
+///
+/// the server_config sets the endpoint the server binds to, the
+/// namespace URI and the polling periods
+pub fn construct_and_run_ciet_server(run_server: bool,
+    server_config: &CIETServerConfig,
+    heater_version: HeaterVersionSelection,
+    air_heat_transfer_model: AirHeatTransferModel,
+    axial_power_shape: AxialPowerShape,
+    point_kinetics_enabled: bool){
+
+    let mut server = build_standard_server(server_config);
 
     let ns = {
         let address_space = server.address_space();
         let mut address_space = address_space.write();
         address_space
-            .register_namespace("urn:simple-server")
+            .register_namespace(&server_config.namespace_uri)
             .unwrap()
     };
 
@@ -36,6 +80,35 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     let dhx_branch_mass_flowrate_node = NodeId::new(ns, "dhx_branch_flowrate");
     let ctah_pump_pressure_node = NodeId::new(ns, "ctah_pump_pressure");
 
+    // operators may instead drive the ctah pump by its VFD
+    // frequency, in which case the pump follows its pump curve
+    // and the developed pump pressure is reported back
+    let ctah_pump_speed_node = NodeId::new(ns, "ctah_pump_speed");
+    let ctah_pump_speed_control_node = NodeId::new(ns, "ctah_pump_speed_control");
+    let ctah_pump_developed_pressure_node = 
+        NodeId::new(ns, "ctah_pump_developed_pressure");
+
+    // the ctah pump can be tripped (and reset) by calling 
+    // these methods, after which it coasts down and the loop
+    // goes into natural circulation
+    let trip_pump_method_node = NodeId::new(ns, "trip_pump");
+    let reset_pump_trip_method_node = NodeId::new(ns, "reset_pump_trip");
+
+    // the standard Write service cannot tell which session a 
+    // write comes from, so operators and instructors also write 
+    // controller nodes through this method, where their role 
+    // can be checked
+    let write_controller_value_method_node = 
+        NodeId::new(ns, "write_controller_value");
+    // instructors can put the simulation back to its initial 
+    // state with this method
+    let reset_simulation_method_node = NodeId::new(ns, "reset_simulation");
+    // the Controller folder has a fixed node id, as clients 
+    // need it to call write_controller_value
+    let controller_folder_node = NodeId::new(ns, "controller");
+    let ctah_pump_tripped_node = NodeId::new(ns, "ctah_pump_tripped");
+    let ctah_pump_speed_ratio_node = NodeId::new(ns, "ctah_pump_speed_ratio");
+
     // now for the CIET Heater, I'll a few more nodes. I need 
     // at least the BT-11 temperature (heater inlet),
     // BT-12 temperature (heater outlet),
@@ -43,13 +116,59 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     //
     // I will not be recording surface temperatures for now
     // nor am I using the csv writer just yet
-    // The user should be able to adjust bt11_temperature and 
-    // heater power, 
-    // and consequently, observe bt12_temperature
+    // The user should be able to adjust heater power, 
+    // and consequently, observe bt11_temperature and 
+    // bt12_temperature, BT-11 is computed now that the 
+    // piping around the loop is modelled thermally
     let bt11_temperature_node = NodeId::new(ns, "bt11_temperature_degC");
     let bt12_temperature_node = NodeId::new(ns, "bt12_temperature_degC");
     let heater_power_node = NodeId::new(ns, "heater_power_kilowatts");
 
+    // the heater axial power profile is an array of relative 
+    // powers from the heater inlet to outlet, one per heated 
+    // section node by default, though any length is interpolated
+    let heater_axial_power_profile_node = 
+        NodeId::new(ns, "heater_axial_power_profile");
+
+    // optionally, the heater power comes from point kinetics,
+    // the instructor then controls the external reactivity (in 
+    // dollars) and can scram the reactor instead of the heater 
+    // power being set directly
+    let point_kinetics_enabled_node = NodeId::new(ns, "point_kinetics_enabled");
+    let external_reactivity_node = NodeId::new(ns, "external_reactivity_dollars");
+    let reactor_scram_node = NodeId::new(ns, "reactor_scram");
+    let point_kinetics_power_node = 
+        NodeId::new(ns, "point_kinetics_heater_power_kilowatts");
+    let total_reactivity_node = NodeId::new(ns, "total_reactivity_dollars");
+    let decay_heat_node = NodeId::new(ns, "decay_heat_kilowatts");
+
+    // for the CTAH, BT-41 (CTAH inlet) and BT-43 (CTAH outlet)
+    // temperatures as well as the heat removed, the user
+    // controls the CTAH through its fan speed
+    let bt41_temperature_node = NodeId::new(ns, "bt41_temperature_degC");
+    let bt43_temperature_node = NodeId::new(ns, "bt43_temperature_degC");
+    let ctah_heat_removal_node = NodeId::new(ns, "ctah_heat_removal_kilowatts");
+    let ctah_fan_speed_node = NodeId::new(ns, "ctah_fan_speed_hz");
+
+    // for the DHX and DRACS loop, the DRACS flowrate (FM-60),
+    // DHX shell side (primary) and tube side (DRACS) inlet and 
+    // outlet temperatures, the TCHX outlet temperature and 
+    // the heat removed by the DHX, the user controls the 
+    // DRACS loop through the TCHX fan speed
+    let dracs_mass_flowrate_node = NodeId::new(ns, "dracs_loop_flowrate");
+    let dhx_shell_inlet_temperature_node = 
+        NodeId::new(ns, "dhx_shell_inlet_temperature_degC");
+    let dhx_shell_outlet_temperature_node = 
+        NodeId::new(ns, "dhx_shell_outlet_temperature_degC");
+    let dhx_tube_inlet_temperature_node = 
+        NodeId::new(ns, "dhx_tube_inlet_temperature_degC");
+    let dhx_tube_outlet_temperature_node = 
+        NodeId::new(ns, "dhx_tube_outlet_temperature_degC");
+    let tchx_outlet_temperature_node = 
+        NodeId::new(ns, "tchx_outlet_temperature_degC");
+    let dhx_heat_removal_node = NodeId::new(ns, "dhx_heat_removal_kilowatts");
+    let tchx_fan_speed_node = NodeId::new(ns, "tchx_fan_speed_hz");
+
 
 
     // I'll have another two here to close off the Heater and DHX branch respectively
@@ -64,6 +183,25 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     let fluid_total_calc_time_node = NodeId::new(ns, "construction_time_plus_calc_time");
     let heater_calculation_time_node = NodeId::new(ns, "heater_calculation_time");
 
+    // these tell the user if the loop hydraulics could be solved,
+    // if not, the flowrates from the last successful solve are kept
+    let hydraulics_converged_node = NodeId::new(ns, "hydraulics_converged");
+    let hydraulics_solver_status_node = NodeId::new(ns, "hydraulics_solver_status");
+    // number of times the newton solver failed and brent's 
+    // method was used instead
+    let hydraulics_brent_fallback_count_node = 
+        NodeId::new(ns, "hydraulics_brent_fallback_count");
+
+    // and these tell the user which heater models the server 
+    // was started with, the current heater axial power shape, 
+    // and whether the last axial power profile written was accepted
+    let heated_section_model_node = NodeId::new(ns, "heated_section_model");
+    let air_heat_transfer_model_node = NodeId::new(ns, "air_heat_transfer_model");
+    let heater_axial_power_shape_node = NodeId::new(ns, "heater_axial_power_shape");
+    let heater_axial_power_profile_status_node = 
+        NodeId::new(ns, "heater_axial_power_profile_status");
+    let point_kinetics_status_node = NodeId::new(ns, "point_kinetics_status");
+
     // And then some more variables for 
     // (1) manometer reading error
     // (2) loop pressure drop error due to flowrate error of 2\%
@@ -81,6 +219,11 @@ pub fn construct_and_run_ciet_server(run_server: bool){
 
 
 
+    // heater nodalisation, needed here for the axial power profile
+    let number_of_inner_temperature_nodes: usize = 6;
+    let number_of_heater_temperature_nodes: usize = 
+        number_of_inner_temperature_nodes + 2;
+
     let address_space = server.address_space();
 
     // this part is responsible for sensor data
@@ -113,6 +256,10 @@ pub fn construct_and_run_ciet_server(run_server: bool){
                 Variable::new(&fluid_total_calc_time_node, 
                               "fluid_construction_time_plus_calc_time_ms", 
                               "fluid_construction_time_plus_calc_time_ms", 0 as f64),
+                Variable::new(&bt11_temperature_node, 
+                "bt11_temperature_degC_heater_inlet", 
+                "bt11_temperature_degC_heater_inlet", 
+                79.12 as f64),
                 Variable::new(&bt12_temperature_node, 
                 "bt12_temperature_degC_heater_outlet", 
                 "bt12_temperature_degC_heater_outlet", 
@@ -120,6 +267,94 @@ pub fn construct_and_run_ciet_server(run_server: bool){
                 Variable::new(&heater_calculation_time_node, 
                 "heater_calculation_time_ms", 
                 "heater_calculation_time_ms", 0 as f64),
+                Variable::new(&point_kinetics_power_node, 
+                "point_kinetics_heater_power_kilowatts", 
+                "point_kinetics_heater_power_kilowatts", 0 as f64),
+                Variable::new(&total_reactivity_node, 
+                "total_reactivity_dollars", 
+                "total_reactivity_dollars", 0 as f64),
+                Variable::new(&decay_heat_node, 
+                "decay_heat_kilowatts", 
+                "decay_heat_kilowatts", 0 as f64),
+                Variable::new(&bt41_temperature_node, 
+                "bt41_temperature_degC_ctah_inlet", 
+                "bt41_temperature_degC_ctah_inlet", 
+                79.12 as f64),
+                Variable::new(&bt43_temperature_node, 
+                "bt43_temperature_degC_ctah_outlet", 
+                "bt43_temperature_degC_ctah_outlet", 
+                79.12 as f64),
+                Variable::new(&ctah_heat_removal_node, 
+                "ctah_heat_removal_kilowatts", 
+                "ctah_heat_removal_kilowatts", 0 as f64),
+                Variable::new(&dracs_mass_flowrate_node, 
+                "dracs_mass_flowrate_kg_per_s_FM60", 
+                "dracs_mass_flowrate_kg_per_s_FM60", 0 as f64),
+                Variable::new(&dhx_shell_inlet_temperature_node, 
+                "dhx_shell_inlet_temperature_degC", 
+                "dhx_shell_inlet_temperature_degC", 
+                79.12 as f64),
+                Variable::new(&dhx_shell_outlet_temperature_node, 
+                "dhx_shell_outlet_temperature_degC", 
+                "dhx_shell_outlet_temperature_degC", 
+                79.12 as f64),
+                Variable::new(&dhx_tube_inlet_temperature_node, 
+                "dhx_tube_inlet_temperature_degC", 
+                "dhx_tube_inlet_temperature_degC", 
+                79.12 as f64),
+                Variable::new(&dhx_tube_outlet_temperature_node, 
+                "dhx_tube_outlet_temperature_degC", 
+                "dhx_tube_outlet_temperature_degC", 
+                79.12 as f64),
+                Variable::new(&tchx_outlet_temperature_node, 
+                "tchx_outlet_temperature_degC", 
+                "tchx_outlet_temperature_degC", 
+                79.12 as f64),
+                Variable::new(&dhx_heat_removal_node, 
+                "dhx_heat_removal_kilowatts", 
+                "dhx_heat_removal_kilowatts", 0 as f64),
+                Variable::new(&ctah_pump_developed_pressure_node, 
+                "ctah_pump_developed_pressure_pa", 
+                "ctah_pump_developed_pressure_pa", 0 as f64),
+                Variable::new(&ctah_pump_tripped_node, 
+                "ctah_pump_tripped", 
+                "ctah_pump_tripped", false),
+                Variable::new(&ctah_pump_speed_ratio_node, 
+                "ctah_pump_speed_ratio", 
+                "ctah_pump_speed_ratio", 1 as f64),
+                Variable::new(&hydraulics_converged_node, 
+                "hydraulics_converged", 
+                "hydraulics_converged", true),
+                Variable::new(&hydraulics_solver_status_node, 
+                "hydraulics_solver_status", 
+                "hydraulics_solver_status", 
+                UAString::from("converged")),
+                Variable::new(&hydraulics_brent_fallback_count_node, 
+                "hydraulics_brent_fallback_count", 
+                "hydraulics_brent_fallback_count", 0 as u64),
+                Variable::new(&heated_section_model_node, 
+                "heated_section_model", 
+                "heated_section_model", 
+                UAString::from(heater_version.to_string())),
+                Variable::new(&air_heat_transfer_model_node, 
+                "air_heat_transfer_model", 
+                "air_heat_transfer_model", 
+                UAString::from(air_heat_transfer_model.to_string())),
+                Variable::new(&heater_axial_power_shape_node, 
+                "heater_axial_power_shape", 
+                "heater_axial_power_shape", 
+                UAString::from(axial_power_shape.to_string())),
+                Variable::new(&heater_axial_power_profile_status_node, 
+                "heater_axial_power_profile_status", 
+                "heater_axial_power_profile_status", 
+                UAString::from("accepted")),
+                Variable::new(&point_kinetics_status_node, 
+                "point_kinetics_status", 
+                "point_kinetics_status", 
+                UAString::from(match point_kinetics_enabled {
+                    true => "enabled",
+                    false => "disabled",
+                })),
             ],
             &sample_folder_id,
         );
@@ -154,13 +389,171 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         );
     }
 
+    // the hydraulics and the thermal pipe chains are both built 
+    // from this loop definition
+    let ciet_loop_definition: CIETLoopDefinition = server_config
+        .get_loop_definition()
+        .unwrap_or_else(|error| panic!("{}", error));
+
+    // CIET is constructed once, the facility owns all its
+    // components so it can be recalculated every time
+    let start_of_object_init = Instant::now();
+    let ciet_facility = CIETIsothermalFacility::from_definition(
+        &ciet_loop_definition)
+        .unwrap_or_else(|error| panic!("{}", error));
+    let initiation_duration = start_of_object_init.elapsed();
+
+    // this part is responsible for the per component pressure
+    // breakdown, which helps when comparing against manometer
+    // readings, there is one folder per branch and one folder
+    // per component within it
+    let initial_pressure_breakdown = ciet_facility.get_pressure_breakdown();
+    let (heater_branch_breakdown_nodes,
+        ctah_branch_breakdown_nodes,
+        dhx_branch_breakdown_nodes) = {
+        let mut address_space = address_space.write();
+
+        let breakdown_folder_id = address_space
+            .add_folder("hydraulics breakdown", "hydraulics breakdown", 
+                &NodeId::objects_folder_id())
+            .unwrap();
+
+        let heater_branch_breakdown_nodes = add_pressure_breakdown_variables(
+            &mut address_space, ns, &breakdown_folder_id, "heater branch", 
+            &initial_pressure_breakdown.heater_branch);
+        let ctah_branch_breakdown_nodes = add_pressure_breakdown_variables(
+            &mut address_space, ns, &breakdown_folder_id, "ctah branch", 
+            &initial_pressure_breakdown.ctah_branch);
+        let dhx_branch_breakdown_nodes = add_pressure_breakdown_variables(
+            &mut address_space, ns, &breakdown_folder_id, "dhx branch", 
+            &initial_pressure_breakdown.dhx_branch);
+
+        (heater_branch_breakdown_nodes, 
+         ctah_branch_breakdown_nodes, 
+         dhx_branch_breakdown_nodes)
+    };
+
+    // besides the flat folders, the facility is also described 
+    // as CIET -> branches -> components -> variables with its own 
+    // object types, so that generic OPC-UA browsers and historians
+    // can discover the plant structure
+    let (heater_branch_object_nodes,
+        ctah_branch_object_nodes,
+        dhx_branch_object_nodes) = {
+        let mut address_space = address_space.write();
+
+        let object_types = object_model::add_ciet_object_types(
+            &mut address_space, ns);
+        let facility_object = object_model::add_ciet_facility_object(
+            &mut address_space, ns, &object_types);
+
+        let heater_branch_object_nodes = add_branch_object_model(
+            &mut address_space, &object_types, &facility_object, 
+            object_model::HEATER_BRANCH_OBJECT_NAME,
+            &heater_branch_breakdown_nodes,
+            &initial_pressure_breakdown.heater_branch);
+        let ctah_branch_object_nodes = add_branch_object_model(
+            &mut address_space, &object_types, &facility_object, 
+            object_model::CTAH_BRANCH_OBJECT_NAME,
+            &ctah_branch_breakdown_nodes,
+            &initial_pressure_breakdown.ctah_branch);
+        let dhx_branch_object_nodes = add_branch_object_model(
+            &mut address_space, &object_types, &facility_object, 
+            object_model::DHX_BRANCH_OBJECT_NAME,
+            &dhx_branch_breakdown_nodes,
+            &initial_pressure_breakdown.dhx_branch);
+
+        (heater_branch_object_nodes,
+         ctah_branch_object_nodes,
+         dhx_branch_object_nodes)
+    };
+
+    // this part is responsible for the simulated manometer
+    // board, so that operators can compare the readings against
+    // the manometer board of the physical facility
+    let manometer_board = ManometerBoard::default();
+
+    // check that every manometer tap is in the loop before
+    // any nodes are made for it
+    ciet_facility.get_manometer_readings(&manometer_board)
+        .expect("manometer taps should be within the CIET loop");
+
+    // each manometer publishes its pressure difference, column 
+    // height difference and reading error
+    let manometer_nodes: Vec<(NodeId, NodeId, NodeId)> = {
+        let mut address_space = address_space.write();
+
+        let manometer_folder_id = address_space
+            .add_folder("manometers", "manometers", 
+                &NodeId::objects_folder_id())
+            .unwrap();
+
+        let mut manometer_nodes: Vec<(NodeId, NodeId, NodeId)> = vec![];
+
+        for manometer in manometer_board.get_manometers().iter() {
+
+            let pressure_difference_node = NodeId::new(ns, 
+                format!("{}_manometer_reading_pa", manometer.name));
+            let column_height_node = NodeId::new(ns, 
+                format!("{}_manometer_reading_mm", manometer.name));
+            let reading_error_node = NodeId::new(ns, 
+                format!("{}_manometer_reading_error_pa", manometer.name));
+
+            let pressure_difference_name = 
+                format!("{}_manometer_reading_pa", manometer.name);
+            let column_height_name = 
+                format!("{}_manometer_reading_mm", manometer.name);
+            let reading_error_name = 
+                format!("{}_manometer_reading_error_pa", manometer.name);
+
+            let _ = address_space.add_variables(
+                vec![
+                    Variable::new(&pressure_difference_node, 
+                        pressure_difference_name.as_str(), 
+                        pressure_difference_name.as_str(), 0 as f64),
+                    Variable::new(&column_height_node, 
+                        column_height_name.as_str(), 
+                        column_height_name.as_str(), 0 as f64),
+                    Variable::new(&reading_error_node, 
+                        reading_error_name.as_str(), 
+                        reading_error_name.as_str(), 0 as f64),
+                ],
+                &manometer_folder_id,
+            );
+
+            manometer_nodes.push((pressure_difference_node, 
+                    column_height_node, reading_error_node));
+        }
+
+        manometer_nodes
+    };
+
+    // set by the trip_pump and reset_pump_trip methods and
+    // read by the hydraulics thread
+    let ctah_pump_trip_requested = Arc::new(AtomicBool::new(false));
+    let hydraulics_pump_trip_requested = ctah_pump_trip_requested.clone();
+    let heater_loop_pump_trip_requested = ctah_pump_trip_requested.clone();
+
+    // set by the reset_simulation method, the heater loop and 
+    // the hydraulics each consume their own request
+    let simulation_reset_requested = Arc::new(AtomicBool::new(false));
+    let hydraulics_reset_requested = Arc::new(AtomicBool::new(false));
+
+    // roles of the users logging in, and the controller values 
+    // written through write_controller_value, which are set in 
+    // the address space by a polling action
+    let user_roles = Arc::new(UserRoles::new(&server_config.security)
+        .unwrap_or_else(|error| panic!("{}", error)));
+    let pending_controller_writes: Arc<Mutex<Vec<(NodeId, Variant)>>> = 
+        Arc::new(Mutex::new(vec![]));
+
     // this is the piece of code for the writeonly variable
     // we can use booleans or floats
     {
         let mut address_space = address_space.write();
-        let folder_id = address_space
-            .add_folder("Controller", "Controller", &NodeId::objects_folder_id())
-            .unwrap();
+        let folder_id = controller_folder_node.clone();
+        address_space.add_folder_with_id(&folder_id, 
+            "Controller", "Controller", &NodeId::objects_folder_id());
 
 
         VariableBuilder::new(&ctah_pump_pressure_node, 
@@ -171,6 +564,44 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             .organized_by(&folder_id)
             .insert(&mut address_space);
 
+        VariableBuilder::new(&ctah_pump_speed_node, 
+                             "ctah_pump_speed_hz", "ctah_pump_speed_hz")
+            .description("ctah pump speed, used when ctah_pump_speed_control \
+                is true. The default pump curve is a placeholder, not the \
+                measured CIET pump curve, set ctah_pump_curve in the loop \
+                definition file to use the measured one")
+            .data_type(DataTypeId::Float)
+            .value(0 as f64)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&ctah_fan_speed_node, 
+                             "ctah_fan_speed_hz", "ctah_fan_speed_hz")
+            .data_type(DataTypeId::Float)
+            .value(CTAH_FAN_RATED_SPEED_HERTZ as f64)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&tchx_fan_speed_node, 
+                             "tchx_fan_speed_hz", "tchx_fan_speed_hz")
+            .data_type(DataTypeId::Float)
+            .value(TCHX_FAN_RATED_SPEED_HERTZ as f64)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        // false: the pump supplies ctah_pump_pressure_pa,
+        // true: the pump runs at ctah_pump_speed_hz on its pump curve
+        VariableBuilder::new(&ctah_pump_speed_control_node,
+                             "ctah_pump_speed_control", "ctah_pump_speed_control")
+            .data_type(DataTypeId::Boolean)
+            .value(false as bool)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
         VariableBuilder::new(&heater_branch_valve_node,
                              "heater_branch_valve_open", "heater_branch_valve_open")
             .data_type(DataTypeId::Boolean)
@@ -195,42 +626,351 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             .organized_by(&folder_id)
             .insert(&mut address_space);
 
-        VariableBuilder::new(&bt11_temperature_node, 
-            "bt11_temperature_degC_heater_inlet", 
-            "bt11_temperature_degC_heater_inlet")
+        VariableBuilder::new(&heater_power_node, 
+            "heater_power_kilowatts", 
+            "heater_power_kilowatts")
             .data_type(DataTypeId::Float)
-            .value(79.12 as f64)
+            .value(8.0 as f64)
             .writable()
             .organized_by(&folder_id)
             .insert(&mut address_space);
 
-        VariableBuilder::new(&heater_power_node, 
-            "heater_power_kilowatts", 
-            "heater_power_kilowatts")
+        // relative powers, scaled so the average is one
+        let initial_axial_power_profile: Vec<f64> = axial_power_shape
+            .get_power_fractions(number_of_heater_temperature_nodes)
+            .iter().map(|fraction| {
+                fraction * number_of_heater_temperature_nodes as f64
+            }).collect();
+
+        VariableBuilder::new(&point_kinetics_enabled_node, 
+            "point_kinetics_enabled", 
+            "point_kinetics_enabled")
+            .data_type(DataTypeId::Boolean)
+            .value(point_kinetics_enabled)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&external_reactivity_node, 
+            "external_reactivity_dollars", 
+            "external_reactivity_dollars")
             .data_type(DataTypeId::Float)
-            .value(8.0 as f64)
+            .value(0.0 as f64)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&reactor_scram_node, 
+            "reactor_scram", 
+            "reactor_scram")
+            .data_type(DataTypeId::Boolean)
+            .value(false as bool)
+            .writable()
+            .organized_by(&folder_id)
+            .insert(&mut address_space);
+
+        VariableBuilder::new(&heater_axial_power_profile_node, 
+            "heater_axial_power_profile", 
+            "heater_axial_power_profile")
+            .data_type(DataTypeId::Double)
+            .value_rank(1)
+            .array_dimensions(&[number_of_heater_temperature_nodes as u32])
+            .value(initial_axial_power_profile)
             .writable()
             .organized_by(&folder_id)
             .insert(&mut address_space);
+
+        // the methods only raise or clear the trip request,
+        // the hydraulics thread then trips or resets the pump
+        MethodBuilder::new(&trip_pump_method_node, "trip_pump", "trip_pump")
+            .component_of(folder_id.clone())
+            .callback(Box::new(CTAHPumpTripMethod {
+                pump_trip_requested: ctah_pump_trip_requested.clone(),
+                trip: true,
+                user_roles: user_roles.clone(),
+            }))
+            .insert(&mut address_space);
+
+        MethodBuilder::new(&reset_pump_trip_method_node, 
+            "reset_pump_trip", "reset_pump_trip")
+            .component_of(folder_id.clone())
+            .callback(Box::new(CTAHPumpTripMethod {
+                pump_trip_requested: ctah_pump_trip_requested.clone(),
+                trip: false,
+                user_roles: user_roles.clone(),
+            }))
+            .insert(&mut address_space);
+
+        MethodBuilder::new(&reset_simulation_method_node, 
+            "reset_simulation", "reset_simulation")
+            .component_of(folder_id.clone())
+            .callback(Box::new(ResetSimulationMethod {
+                simulation_reset_requested: simulation_reset_requested.clone(),
+                hydraulics_reset_requested: hydraulics_reset_requested.clone(),
+                user_roles: user_roles.clone(),
+            }))
+            .insert(&mut address_space);
+
+        // the controller nodes by browse name, with the kind of 
+        // value each takes, whether it is a fault node, which 
+        // only instructors may write, and for numbers, the units 
+        // and the instrument range written values must be in
+        let controller_nodes: HashMap<String, ControllerNode> = [
+            ("ctah_pump_pressure_pa", &ctah_pump_pressure_node, 
+             ControllerValueKind::Number, false, 
+             Some(AnalogItem::pressure_change())),
+            ("ctah_pump_speed_hz", &ctah_pump_speed_node, 
+             ControllerValueKind::Number, false, 
+             Some(AnalogItem::vfd_frequency(crate::CTAH_PUMP_RATED_SPEED_HERTZ))),
+            ("ctah_fan_speed_hz", &ctah_fan_speed_node, 
+             ControllerValueKind::Number, false, 
+             Some(AnalogItem::vfd_frequency(CTAH_FAN_RATED_SPEED_HERTZ))),
+            ("tchx_fan_speed_hz", &tchx_fan_speed_node, 
+             ControllerValueKind::Number, false, 
+             Some(AnalogItem::vfd_frequency(TCHX_FAN_RATED_SPEED_HERTZ))),
+            ("ctah_pump_speed_control", &ctah_pump_speed_control_node, 
+             ControllerValueKind::Boolean, false, None),
+            ("heater_branch_valve_open", &heater_branch_valve_node, 
+             ControllerValueKind::Boolean, false, None),
+            ("dhx_branch_valve_open", &dhx_branch_valve_node, 
+             ControllerValueKind::Boolean, false, None),
+            ("ctah_branch_valve_open", &ctah_branch_valve_node, 
+             ControllerValueKind::Boolean, false, None),
+            ("heater_power_kilowatts", &heater_power_node, 
+             ControllerValueKind::Number, false, 
+             Some(AnalogItem::heater_power())),
+            ("point_kinetics_enabled", &point_kinetics_enabled_node, 
+             ControllerValueKind::Boolean, true, None),
+            ("external_reactivity_dollars", &external_reactivity_node, 
+             ControllerValueKind::Number, true, 
+             Some(AnalogItem::reactivity())),
+            ("reactor_scram", &reactor_scram_node, 
+             ControllerValueKind::Boolean, true, None),
+            // relative powers average one, so none exceeds the
+            // number of heated section nodes
+            ("heater_axial_power_profile", &heater_axial_power_profile_node, 
+             ControllerValueKind::NumberArray, true, 
+             Some(AnalogItem::new(
+                EngineeringUnit::Dimensionless, (0.0, 2.0), 
+                (0.0, number_of_heater_temperature_nodes as f64)))),
+        ].into_iter().map(|(name, node, kind, fault_node, analog_item)| {
+            (name.to_string(), ControllerNode { 
+                node: node.clone(), kind, fault_node, analog_item })
+        }).collect();
+
+        // standard writes to the controller nodes are checked and 
+        // queued like those made through write_controller_value, 
+        // but are only allowed if every session may make them
+        for controller_node in controller_nodes.values() {
+            if let Some(analog_item) = &controller_node.analog_item {
+                add_analog_item_properties(&mut address_space, 
+                    &controller_node.node, analog_item);
+            }
+            if let Some(variable) = 
+                address_space.find_variable_mut(controller_node.node.clone()) {
+                variable.set_user_access_level(
+                    user_roles.get_user_access_level(controller_node.fault_node));
+                variable.set_value_setter(Arc::new(opcua::sync::Mutex::new(
+                    ControllerNodeSetter {
+                        controller_node: controller_node.clone(),
+                        pending_controller_writes: pending_controller_writes.clone(),
+                        least_privileged_role: user_roles.get_least_privileged_role(),
+                    })));
+            }
+        }
+
+        MethodBuilder::new(&write_controller_value_method_node, 
+            "write_controller_value", "write_controller_value")
+            .component_of(folder_id.clone())
+            .input_args(&mut address_space, &[
+                ("node_name", DataTypeId::String).into(),
+                ("value", DataTypeId::BaseDataType).into(),
+            ])
+            .callback(Box::new(WriteControllerValueMethod {
+                controller_nodes,
+                pending_controller_writes: pending_controller_writes.clone(),
+                user_roles: user_roles.clone(),
+            }))
+            .insert(&mut address_space);
+
+        // method nodes are built as not executable, which
+        // clients show as greyed out
+        for method_node in [&trip_pump_method_node, &reset_pump_trip_method_node,
+            &write_controller_value_method_node, &reset_simulation_method_node] {
+            if let Some(NodeType::Method(method)) = 
+                address_space.find_mut(method_node.clone()) {
+                method.set_executable(true);
+                method.set_user_executable(true);
+            }
+        }
+    }
+
+    // every numeric variable gets its engineering units, and the 
+    // range it normally takes (EURange) and can take 
+    // (InstrumentRange), so clients need not parse the units 
+    // from the node names
+    {
+        let mut address_space = address_space.write();
+
+        let hydraulics_calculation_time = AnalogItem::calculation_time(
+            server_config.hydraulics_calculation_period_ms);
+        let dimensionless_ratio = AnalogItem::new(
+            EngineeringUnit::Dimensionless, (0.0, 1.0), (0.0, 2.0));
+
+        let analog_items: Vec<(&NodeId, AnalogItem)> = vec![
+            // sensor data
+            (&ctah_branch_mass_flowrate_node, AnalogItem::branch_mass_flowrate()),
+            (&heater_branch_mass_flowrate_node, AnalogItem::branch_mass_flowrate()),
+            (&dhx_branch_mass_flowrate_node, AnalogItem::branch_mass_flowrate()),
+            (&dracs_mass_flowrate_node, AnalogItem::branch_mass_flowrate()),
+            (&fluid_calculation_time_node, hydraulics_calculation_time),
+            (&initiation_time_node, hydraulics_calculation_time),
+            (&fluid_total_calc_time_node, hydraulics_calculation_time),
+            (&heater_calculation_time_node, 
+             AnalogItem::calculation_time(server_config.heater_timestep_ms)),
+            (&bt11_temperature_node, AnalogItem::fluid_temperature()),
+            (&bt12_temperature_node, AnalogItem::fluid_temperature()),
+            (&bt41_temperature_node, AnalogItem::fluid_temperature()),
+            (&bt43_temperature_node, AnalogItem::fluid_temperature()),
+            (&dhx_shell_inlet_temperature_node, AnalogItem::fluid_temperature()),
+            (&dhx_shell_outlet_temperature_node, AnalogItem::fluid_temperature()),
+            (&dhx_tube_inlet_temperature_node, AnalogItem::fluid_temperature()),
+            (&dhx_tube_outlet_temperature_node, AnalogItem::fluid_temperature()),
+            (&tchx_outlet_temperature_node, AnalogItem::fluid_temperature()),
+            (&point_kinetics_power_node, AnalogItem::heater_power()),
+            (&decay_heat_node, AnalogItem::heater_power()),
+            (&total_reactivity_node, AnalogItem::reactivity()),
+            (&ctah_heat_removal_node, AnalogItem::heat_removal()),
+            (&dhx_heat_removal_node, AnalogItem::heat_removal()),
+            (&ctah_pump_developed_pressure_node, AnalogItem::pressure_change()),
+            (&ctah_pump_speed_ratio_node, dimensionless_ratio),
+            // deviation and error
+            (&manometer_reading_error_pascals_node, AnalogItem::pressure_change()),
+            (&loop_pressure_drop_error_due_to_coriolis_flowmeter_pascals_node, 
+             AnalogItem::pressure_change()),
+            (&loop_pressure_drop_error_due_to_fldk_pascals_node, 
+             AnalogItem::pressure_change()),
+            (&loop_pressure_drop_error_total_node, AnalogItem::pressure_change()),
+            // the controller nodes have theirs added with the 
+            // Controller folder, as writes are checked against them
+        ];
+
+        for (node, analog_item) in analog_items {
+            add_analog_item_properties(&mut address_space, node, &analog_item);
+        }
+
+        // hydraulics breakdown, shared with the CIET object model
+        for component_nodes in heater_branch_breakdown_nodes.iter()
+            .chain(ctah_branch_breakdown_nodes.iter())
+            .chain(dhx_branch_breakdown_nodes.iter()) {
+            for node in [&component_nodes.friction_loss, 
+                &component_nodes.form_loss,
+                &component_nodes.hydrostatic_pressure_change,
+                &component_nodes.internal_pressure_source] {
+                add_analog_item_properties(&mut address_space, node, 
+                    &AnalogItem::pressure_change());
+            }
+        }
+
+        // CIET object model
+        for (branch_nodes, component_object_nodes) in [
+            &heater_branch_object_nodes, 
+            &ctah_branch_object_nodes, 
+            &dhx_branch_object_nodes] {
+
+            add_analog_item_properties(&mut address_space, 
+                &branch_nodes.mass_flowrate, 
+                &AnalogItem::branch_mass_flowrate());
+
+            for component_nodes in component_object_nodes.iter() {
+                add_analog_item_properties(&mut address_space, 
+                    &component_nodes.pressure_change, 
+                    &AnalogItem::pressure_change());
+                if let Some(fluid_temperature_node) = 
+                    &component_nodes.fluid_temperature {
+                    add_analog_item_properties(&mut address_space, 
+                        fluid_temperature_node, 
+                        &AnalogItem::fluid_temperature());
+                }
+            }
+        }
+
+        // manometers, the columns are a little over 2 m tall at 
+        // the rated pump pressure
+        for (pressure_difference_node, column_height_node, reading_error_node) in 
+            manometer_nodes.iter() {
+            add_analog_item_properties(&mut address_space, 
+                pressure_difference_node, &AnalogItem::pressure_change());
+            add_analog_item_properties(&mut address_space, 
+                reading_error_node, &AnalogItem::pressure_change());
+            add_analog_item_properties(&mut address_space, 
+                column_height_node, &AnalogItem::new(
+                    EngineeringUnit::Millimeter, 
+                    (-2500.0, 2500.0), (-1.0e5, 1.0e5)));
+        }
     }
 
 
 
 
+    // values the controller and heater status nodes are put 
+    // back to when the simulation is reset
+    let initial_node_values: Vec<(NodeId, Variant)> = {
+        let address_space = address_space.read();
+
+        [&ctah_pump_pressure_node, &ctah_pump_speed_node, 
+            &ctah_fan_speed_node, &tchx_fan_speed_node, 
+            &ctah_pump_speed_control_node, &heater_branch_valve_node, 
+            &dhx_branch_valve_node, &ctah_branch_valve_node, 
+            &heater_power_node, &point_kinetics_enabled_node, 
+            &external_reactivity_node, &reactor_scram_node, 
+            &heater_axial_power_profile_node, &heater_axial_power_shape_node,
+            &heater_axial_power_profile_status_node, &point_kinetics_status_node]
+            .into_iter().map(|node| {
+                let value: Variant = address_space
+                    .get_variable_value(node.clone()).unwrap()
+                    .value.unwrap();
+                (node.clone(), value)
+            }).collect()
+    };
+
     // adding functions to ciet's server now...
     //
-    // this one prints the endpoint every 5s so the user knows
-    // how to connect to ciet
+    // this one prints the endpoint every 5s (by default) so the 
+    // user knows how to connect to ciet
 
-    let print_endpoint_simple = || {
-        let ip_add = get_ip_as_str();
-
-        println!("\n opc.tcp://{}:{}{} \n",ip_add,4840,CUSTOM_ENDPOINT_PATH);
+    let endpoint_url = server_config.get_endpoint_url();
+    let print_endpoint_simple = move || {
+        println!("\n {} \n", endpoint_url);
     };
 
 
     //server.add_polling_action(5000, print_endpoint);
-    server.add_polling_action(5000, print_endpoint_simple);
+    server.add_polling_action(server_config.endpoint_print_period_ms, 
+        print_endpoint_simple);
+
+    // controller values written through write_controller_value are 
+    // set in the address space here, the method itself cannot as 
+    // the address space is locked while it runs
+    let controller_writes_address_space = server.address_space();
+    let apply_controller_writes = move || {
+        let controller_writes: Vec<(NodeId, Variant)> = 
+            pending_controller_writes.lock().unwrap().drain(..).collect();
+
+        if controller_writes.is_empty() {
+            return;
+        }
+
+        let now = DateTime::now();
+        let mut address_space_lock = controller_writes_address_space.write();
+
+        for (node, value) in controller_writes {
+            let _ = address_space_lock.set_variable_value(node, value, &now, &now);
+        }
+    };
+
+    server.add_polling_action(server_config.heater_timestep_ms, 
+        apply_controller_writes);
 
 
     // we need to prepare transmitters and receivers for the
@@ -239,257 +979,498 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     //let (tx, rx) = mpsc::channel();
 
 
-    // now this algorithm is REALLY inefficient, i am instantiating CIET at
-    // EVERY timestep in addition to calculation
-    //
-    // but if it works, it works
+    // CIET is instantiated once when the hydraulics thread starts,
+    // after which only the pump pressure, valve states and loop
+    // temperatures are changed before every calculation
+    let hydraulics_calculation_period = Duration::from_millis(
+        server_config.hydraulics_calculation_period_ms);
 
     // clone address space for ciet loop
     let address_space_clone = address_space.clone();
+    // the heater loop reads the solved heater branch flowrate
+    // from this node, so it needs its own copy of the node id
+    let heater_loop_heater_branch_flowrate_node = 
+        heater_branch_mass_flowrate_node.clone();
+    // likewise for the ctah branch flowrate, which sets the 
+    // therminol flowrate through the CTAH
+    let heater_loop_ctah_branch_flowrate_node = 
+        ctah_branch_mass_flowrate_node.clone();
+    // and the dhx branch and DRACS loop flowrates, which set the 
+    // shell and tube side flowrates through the DHX
+    let heater_loop_dhx_branch_flowrate_node = 
+        dhx_branch_mass_flowrate_node.clone();
+    let heater_loop_dracs_flowrate_node = 
+        dracs_mass_flowrate_node.clone();
+
+    // fluid temperatures of every component in the loop,
+    // the heater loop updates these every timestep and the 
+    // hydraulics use them so that buoyancy (natural circulation)
+    // is accounted for
+    let loop_temperatures_shared_ptr: Arc<Mutex<CIETLoopTemperatures>> = 
+        Arc::new(Mutex::new(
+            ciet_facility.get_uniform_loop_temperatures(
+                ThermodynamicTemperature::new::<degree_celsius>(79.12))));
+    let heater_loop_temperatures_shared_ptr = 
+        loop_temperatures_shared_ptr.clone();
+
+    // the DRACS loop is solved alongside the primary loop,
+    // its temperatures also come from the heater loop
+    let dracs_loop = DRACSLoop::new();
+    let dracs_loop_temperatures_shared_ptr: Arc<Mutex<DRACSLoopTemperatures>> = 
+        Arc::new(Mutex::new(
+            DRACSLoopTemperatures::new_uniform(
+                ThermodynamicTemperature::new::<degree_celsius>(79.12))));
+    let heater_loop_dracs_temperatures_shared_ptr = 
+        dracs_loop_temperatures_shared_ptr.clone();
     let calculate_flowrate_and_pressure_loss = move || {
+        let mut ciet_facility = ciet_facility;
+        let mut dracs_loop = dracs_loop;
 
-        // construct CIET
-        let start_of_object_init = Instant::now();
-        let initiation_duration = start_of_object_init.elapsed();
+        loop {
 
-        let start_of_calc_time = Instant::now();
+            let start_of_calc_time = Instant::now();
 
-        let mut address_space_lock = address_space_clone.write();
+            let mut address_space_lock = address_space_clone.write();
         
-        // step 1, find the correct node object for 
-        // pump pressure and the
-        // boolean for valve control open or close
-        let ctah_pump_node = ctah_pump_pressure_node.clone();
-        let pump_pressure_value = address_space_lock.
-            get_variable_value(ctah_pump_node).unwrap();
-        let pump_pressure_value: f64 = pump_pressure_value.
-            value.unwrap().as_f64().unwrap();
-
-        // now for heater valve, ctah valve and dhx valve
-        // control
-        let heater_valve_open = address_space_lock.
-            get_variable_value(heater_branch_valve_node.clone()).unwrap();
-        let heater_valve_open = 
-            heater_valve_open.value.unwrap();
-
-        // this is an opcua Variant::Boolean 
-        // we can use a match statement to extract true or false values
-        // kind of a clunky way but it can work
-
-        fn match_true_false(opcua_bool: Variant) -> bool{
-
-            match opcua_bool {
-                Variant::Boolean(true) => return true,
-                Variant::Boolean(false) => return false,
-                // for all other types, throw an error,
-                _ => panic!("value must be true or false"),
-            }
+            // step 1, find the correct node object for 
+            // pump pressure and the
+            // boolean for valve control open or close
+            let ctah_pump_node = ctah_pump_pressure_node.clone();
+            let pump_pressure_value = address_space_lock.
+                get_variable_value(ctah_pump_node).unwrap();
+            let pump_pressure_value: f64 = pump_pressure_value.
+                value.unwrap().as_f64().unwrap();
+
+            // now for heater valve, ctah valve and dhx valve
+            // control
+            let heater_valve_open = address_space_lock.
+                get_variable_value(heater_branch_valve_node.clone()).unwrap();
+            let heater_valve_open = 
+                heater_valve_open.value.unwrap();
+
+            // this is an opcua Variant::Boolean 
+            // we can use a match statement to extract true or false values
+            // kind of a clunky way but it can work
+
+            fn match_true_false(opcua_bool: Variant) -> bool{
+
+                match opcua_bool {
+                    Variant::Boolean(true) => return true,
+                    Variant::Boolean(false) => return false,
+                    // for all other types, throw an error,
+                    _ => panic!("value must be true or false"),
+                }
 
-        }
+            }
 
-        let heater_valve_open: bool =
-            match_true_false(heater_valve_open);
+            let heater_valve_open: bool =
+                match_true_false(heater_valve_open);
         
 
-        let dhx_valve_open = address_space_lock.
-            get_variable_value(dhx_branch_valve_node.clone()).unwrap().value.unwrap();
-        let dhx_valve_open:bool = match_true_false(dhx_valve_open);
+            let dhx_valve_open = address_space_lock.
+                get_variable_value(dhx_branch_valve_node.clone()).unwrap().value.unwrap();
+            let dhx_valve_open:bool = match_true_false(dhx_valve_open);
 
-        let ctah_valve_open = address_space_lock.
-            get_variable_value(ctah_branch_valve_node.clone()).unwrap().value.unwrap();
-        let ctah_valve_open:bool = match_true_false(ctah_valve_open);
+            let ctah_valve_open = address_space_lock.
+                get_variable_value(ctah_branch_valve_node.clone()).unwrap().value.unwrap();
+            let ctah_valve_open:bool = match_true_false(ctah_valve_open);
+
+            let ctah_pump_speed_control = address_space_lock.
+                get_variable_value(ctah_pump_speed_control_node.clone())
+                .unwrap().value.unwrap();
+            let ctah_pump_speed_control: bool = 
+                match_true_false(ctah_pump_speed_control);
+
+            let ctah_pump_speed_value: f64 = address_space_lock.
+                get_variable_value(ctah_pump_speed_node.clone())
+                .unwrap().value.unwrap().as_f64().unwrap();
         
 
-        let ciet_temp_deg_c: f64 = 20.0;
-        // step 2 calculate mass flowrate for ctah,
-        // heater and dhx branch
-        let (ctah_branch_flowrate,
-             ctah_branch_pressure_change) = 
-            get_ciet_isothermal_mass_flowrate(
-                pump_pressure_value,
-                ciet_temp_deg_c,
-                dhx_valve_open,
-                heater_valve_open,
-                ctah_valve_open
-                );
+            // simulation reset from the reset_simulation method, 
+            // the flowrates go back to zero and the ctah pump 
+            // trip is reset
+            if hydraulics_reset_requested.swap(false, Ordering::SeqCst) {
+                hydraulics_pump_trip_requested.store(false, Ordering::SeqCst);
+                ciet_facility.reset_flowrates();
+                dracs_loop.reset_mass_flowrate();
+            }
 
-        let heater_branch_flowrate = 
-            get_heater_branch_mass_flowrate(
-                ctah_branch_pressure_change.value,
-                ciet_temp_deg_c,
-                heater_valve_open);
+            // pump trips come from the trip_pump and 
+            // reset_pump_trip methods
+            let pump_trip_requested: bool = 
+                hydraulics_pump_trip_requested.load(Ordering::SeqCst);
 
-        let dhx_branch_flowrate = 
-            get_dhx_branch_mass_flowrate(
-                ctah_branch_pressure_change.value,
-                ciet_temp_deg_c,
-                dhx_valve_open);
+            if pump_trip_requested && !ciet_facility.is_ctah_pump_tripped() {
+                ciet_facility.trip_ctah_pump();
+            } else if !pump_trip_requested && ciet_facility.is_ctah_pump_tripped() {
+                ciet_facility.reset_ctah_pump_trip();
+            }
 
-        // step 3, calc time
-        let calc_time = start_of_calc_time.elapsed();
+            // fluid temperatures come from the heater loop
+            let loop_temperatures: CIETLoopTemperatures = 
+                loop_temperatures_shared_ptr.lock().unwrap().clone();
+            // step 2 calculate mass flowrate for ctah,
+            // heater and dhx branch
+            if ctah_pump_speed_control {
+                ciet_facility.set_ctah_pump_speed(
+                    Frequency::new::<hertz>(ctah_pump_speed_value));
+            } else {
+                ciet_facility.set_ctah_pump_pressure(
+                    Pressure::new::<pascal>(pump_pressure_value));
+            }
+            ciet_facility.set_heater_branch_valve_open(heater_valve_open);
+            ciet_facility.set_dhx_branch_valve_open(dhx_valve_open);
+            ciet_facility.set_ctah_branch_valve_open(ctah_valve_open);
+
+            // if the solve fails (eg. the operator writes an 
+            // absurd pump pressure), the server keeps running with the
+            // flowrates from the last successful solve and reports
+            // the failure on the solver status nodes
+            let mut hydraulics_converged: bool = true;
+            let mut hydraulics_solver_status: String = 
+                "converged".to_string();
+
+            // temperatures which do not match the facility 
+            // components are reported rather than used, the 
+            // components then keep their last temperatures
+            if let Err(loop_definition_error) = ciet_facility.
+                set_loop_temperatures(&loop_temperatures) {
+                hydraulics_converged = false;
+                hydraulics_solver_status = format!(
+                    "loop temperatures not set: {}", loop_definition_error);
+            }
 
+            // the flowrates evolve with the fluid inertia of each
+            // branch, so pump trips and valve changes play out
+            // over time rather than instantly
+            let hydraulics_timestep = Time::new::<uom::si::time::second>(
+                hydraulics_calculation_period.as_secs_f64());
+
+            if let Err(solver_error) = ciet_facility.
+                calculate_with_inertia(hydraulics_timestep) {
+                hydraulics_converged = false;
+                hydraulics_solver_status = format!(
+                    "loop flowrates not converged: {}", solver_error);
+            }
 
-        let calc_time_taken_milleseconds: u16 = 
-            calc_time.as_millis().try_into().unwrap();
+            // the DRACS loop has no pump, its flowrate comes
+            // from natural circulation between the DHX and TCHX
+            let dracs_loop_temperatures: DRACSLoopTemperatures = 
+                *dracs_loop_temperatures_shared_ptr.lock().unwrap();
+            dracs_loop.set_loop_temperatures(&dracs_loop_temperatures);
+
+            if let Err(solver_error) = dracs_loop.
+                calculate_with_inertia(hydraulics_timestep) {
+                hydraulics_converged = false;
+                hydraulics_solver_status = format!(
+                    "dracs loop flowrate not converged: {}", solver_error);
+            }
 
-        // step 4, update values into nodes
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            fluid_calculation_time_node.clone(), 
-            calc_time_taken_milleseconds as f64,
-            &now, 
-            &now);
+            let dracs_loop_flowrate: f64 = dracs_loop.
+                get_mass_flowrate().get::<kilogram_per_second>();
 
-        let initiation_time_taken_millseconds: u16 =
-            initiation_duration.as_millis().try_into().unwrap();
+            let ctah_branch_flowrate: f64 = ciet_facility.
+                get_ctah_branch_mass_flowrate().get::<kilogram_per_second>();
+            let heater_branch_flowrate: f64 = ciet_facility.
+                get_heater_branch_mass_flowrate().get::<kilogram_per_second>();
+            let dhx_branch_flowrate: f64 = ciet_facility.
+                get_dhx_branch_mass_flowrate().get::<kilogram_per_second>();
+            // for a speed driven pump, this is the pump curve pressure
+            // at the operating point
+            let pump_pressure_value: f64 = ciet_facility.
+                get_ctah_pump_pressure().get::<pascal>();
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            initiation_time_node.clone(), 
-            initiation_time_taken_millseconds as f64,
-            &now, 
-            &now);
-        let total_time_taken: u16 =
-            calc_time_taken_milleseconds + initiation_time_taken_millseconds;
+            // step 3, calc time
+            let calc_time = start_of_calc_time.elapsed();
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            fluid_total_calc_time_node.clone(), 
-            total_time_taken as f64,
-            &now, 
-            &now);
+
+            let calc_time_taken_milleseconds: u16 = 
+                calc_time.as_millis().try_into().unwrap();
+
+            // step 4, update values into nodes
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                fluid_calculation_time_node.clone(), 
+                calc_time_taken_milleseconds as f64,
+                &now, 
+                &now);
+
+            let initiation_time_taken_millseconds: u16 =
+                initiation_duration.as_millis().try_into().unwrap();
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                initiation_time_node.clone(), 
+                initiation_time_taken_millseconds as f64,
+                &now, 
+                &now);
+            let total_time_taken: u16 =
+                calc_time_taken_milleseconds + initiation_time_taken_millseconds;
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                fluid_total_calc_time_node.clone(), 
+                total_time_taken as f64,
+                &now, 
+                &now);
 
         
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            ctah_branch_mass_flowrate_node.clone(), 
-            ctah_branch_flowrate as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                ctah_pump_developed_pressure_node.clone(), 
+                pump_pressure_value,
+                &now, 
+                &now);
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            heater_branch_mass_flowrate_node.clone(), 
-            heater_branch_flowrate as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                ctah_pump_tripped_node.clone(), 
+                ciet_facility.is_ctah_pump_tripped(),
+                &now, 
+                &now);
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            dhx_branch_mass_flowrate_node.clone(), 
-            dhx_branch_flowrate as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                ctah_pump_speed_ratio_node.clone(), 
+                ciet_facility.get_ctah_pump_speed_ratio(),
+                &now, 
+                &now);
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                ctah_branch_mass_flowrate_node.clone(), 
+                ctah_branch_flowrate as f64,
+                &now, 
+                &now);
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                heater_branch_mass_flowrate_node.clone(), 
+                heater_branch_flowrate as f64,
+                &now, 
+                &now);
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                dhx_branch_mass_flowrate_node.clone(), 
+                dhx_branch_flowrate as f64,
+                &now, 
+                &now);
 
-        // step 5, calculate errors and print
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                dracs_mass_flowrate_node.clone(), 
+                dracs_loop_flowrate as f64,
+                &now, 
+                &now);
 
-        //(1) 2\% flowrate error
-        //let two_percent_flowrate_error_ctah_heater_only_flow = 
-        //    get_loop_pressure_drop_error_due_to_flowmeter_ctah_heater(
-        //        MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
-        //        Pressure::new::<pascal>(pump_pressure_value),
-        //        0.02);
+            // per component pressure breakdown at the solved
+            // flowrates
+            let pressure_breakdown = ciet_facility.get_pressure_breakdown();
+            set_pressure_breakdown_values(&mut address_space_lock,
+                &heater_branch_breakdown_nodes,
+                &pressure_breakdown.heater_branch);
+            set_pressure_breakdown_values(&mut address_space_lock,
+                &ctah_branch_breakdown_nodes,
+                &pressure_breakdown.ctah_branch);
+            set_pressure_breakdown_values(&mut address_space_lock,
+                &dhx_branch_breakdown_nodes,
+                &pressure_breakdown.dhx_branch);
+
+            // and the same values in the CIET object model
+            set_branch_object_model_values(&mut address_space_lock,
+                &heater_branch_object_nodes,
+                heater_branch_flowrate,
+                &pressure_breakdown.heater_branch,
+                &loop_temperatures.heater_branch);
+            set_branch_object_model_values(&mut address_space_lock,
+                &ctah_branch_object_nodes,
+                ctah_branch_flowrate,
+                &pressure_breakdown.ctah_branch,
+                &loop_temperatures.ctah_branch);
+            set_branch_object_model_values(&mut address_space_lock,
+                &dhx_branch_object_nodes,
+                dhx_branch_flowrate,
+                &pressure_breakdown.dhx_branch,
+                &loop_temperatures.dhx_branch);
+
+            // manometer readings at the solved flowrates
+            if let Ok(manometer_readings) = 
+                ciet_facility.get_manometer_readings(&manometer_board) {
+
+                let now = DateTime::now();
+                for ((pressure_difference_node, column_height_node, reading_error_node), 
+                    reading) in 
+                    manometer_nodes.iter().zip(manometer_readings.iter()) {
+
+                    let _ = address_space_lock.set_variable_value(
+                        pressure_difference_node.clone(), 
+                        reading.pressure_difference.get::<pascal>(),
+                        &now, 
+                        &now);
+                    let _ = address_space_lock.set_variable_value(
+                        column_height_node.clone(), 
+                        reading.column_height_difference.get::<millimeter>(),
+                        &now, 
+                        &now);
+                    let _ = address_space_lock.set_variable_value(
+                        reading_error_node.clone(), 
+                        reading.reading_error.get::<pascal>(),
+                        &now, 
+                        &now);
+                }
+            }
 
-        let two_percent_flowrate_error_ctah_heater_only_flow = 
-            parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
-                MassRate::new::<kilogram_per_second>(ctah_branch_flowrate), 
-                Pressure::new::<pascal>(pump_pressure_value), 
-                heater_valve_open, 
-                dhx_valve_open, 
-                ctah_valve_open, 
-                20.0, // temperature degrees C
-                0.02);
+            // step 5, calculate errors and print
+
+            //(1) 2\% flowrate error
+            //let two_percent_flowrate_error_ctah_heater_only_flow = 
+            //    get_loop_pressure_drop_error_due_to_flowmeter_ctah_heater(
+            //        MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
+            //        Pressure::new::<pascal>(pump_pressure_value),
+            //        0.02);
+
+            let two_percent_flowrate_error_ctah_heater_only_flow = 
+                match parameterically_estimate_ctah_loop_pressure_drop_error_due_to_flowrate(
+                    MassRate::new::<kilogram_per_second>(ctah_branch_flowrate), 
+                    Pressure::new::<pascal>(pump_pressure_value), 
+                    heater_valve_open, 
+                    dhx_valve_open, 
+                    ctah_valve_open, 
+                    20.0, // temperature degrees C
+                    0.02) {
+                        Ok(pressure_error) => pressure_error,
+                        Err(solver_error) => {
+                            hydraulics_converged = false;
+                            hydraulics_solver_status = format!(
+                                "flowrate error estimate not converged: {}", 
+                                solver_error);
+                            Pressure::new::<pascal>(0.0)
+                        },
+                    };
 
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            loop_pressure_drop_error_due_to_coriolis_flowmeter_pascals_node.clone(), 
-            two_percent_flowrate_error_ctah_heater_only_flow.value as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                loop_pressure_drop_error_due_to_coriolis_flowmeter_pascals_node.clone(), 
+                two_percent_flowrate_error_ctah_heater_only_flow.value as f64,
+                &now, 
+                &now);
 
-        //(2) 14.7 Pa manometer error
-        let manometer_reading_error_pascals = 
-            get_manometer_reading_error_pascals();
+            //(2) 14.7 Pa manometer error
+            let manometer_reading_error_pascals = 
+                get_manometer_reading_error_pascals();
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            manometer_reading_error_pascals_node.clone(), 
-            manometer_reading_error_pascals.value as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                manometer_reading_error_pascals_node.clone(), 
+                manometer_reading_error_pascals.value as f64,
+                &now, 
+                &now);
 
-        //(3) 10\% fldk error
+            //(3) 10\% fldk error
 
-        let mut fldk_error_pascals_squared = 
-            get_fldk_error_pascals_ctah_branch(
-                MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
-                0.10)
-            * get_fldk_error_pascals_ctah_branch(
-                MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
-                0.10);
+            let mut fldk_error_pascals_squared = 
+                get_fldk_error_pascals_ctah_branch(
+                    MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
+                    0.10)
+                * get_fldk_error_pascals_ctah_branch(
+                    MassRate::new::<kilogram_per_second>(ctah_branch_flowrate),
+                    0.10);
 
-        // if only CTAH and heater branch open add the heater branch error
+            // if only CTAH and heater branch open add the heater branch error
 
-        if ctah_valve_open && heater_valve_open {
-            fldk_error_pascals_squared += get_fldk_error_pascals_heater_branch(
-                MassRate::new::<kilogram_per_second>(heater_branch_flowrate),
-                0.10)
-            * get_fldk_error_pascals_heater_branch(
-                MassRate::new::<kilogram_per_second>(heater_branch_flowrate),
-                0.10);
+            if ctah_valve_open && heater_valve_open {
+                fldk_error_pascals_squared += get_fldk_error_pascals_heater_branch(
+                    MassRate::new::<kilogram_per_second>(heater_branch_flowrate),
+                    0.10)
+                * get_fldk_error_pascals_heater_branch(
+                    MassRate::new::<kilogram_per_second>(heater_branch_flowrate),
+                    0.10);
 
-        }
-        // if and only if ctah and dhx branch valve open,
-        // then add the dhx branch errors
+            }
+            // if and only if ctah and dhx branch valve open,
+            // then add the dhx branch errors
 
-        if ctah_valve_open && dhx_valve_open {
-            fldk_error_pascals_squared += get_fldk_error_pascals_dhx_branch(
-                MassRate::new::<kilogram_per_second>(dhx_branch_flowrate),
-                0.10)
-            * get_fldk_error_pascals_dhx_branch(
-                MassRate::new::<kilogram_per_second>(dhx_branch_flowrate),
-                0.10);
+            if ctah_valve_open && dhx_valve_open {
+                fldk_error_pascals_squared += get_fldk_error_pascals_dhx_branch(
+                    MassRate::new::<kilogram_per_second>(dhx_branch_flowrate),
+                    0.10)
+                * get_fldk_error_pascals_dhx_branch(
+                    MassRate::new::<kilogram_per_second>(dhx_branch_flowrate),
+                    0.10);
 
-        }
+            }
 
-        let fldk_error_pascals = 
-            fldk_error_pascals_squared.sqrt();
+            let fldk_error_pascals = 
+                fldk_error_pascals_squared.sqrt();
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            loop_pressure_drop_error_due_to_fldk_pascals_node.clone(), 
-            fldk_error_pascals.value as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                loop_pressure_drop_error_due_to_fldk_pascals_node.clone(), 
+                fldk_error_pascals.value as f64,
+                &now, 
+                &now);
 
-        //(4) total error
+            //(4) total error
 
-        let total_pressure_error_estimate_pascals_squared = 
-            two_percent_flowrate_error_ctah_heater_only_flow * 
-            two_percent_flowrate_error_ctah_heater_only_flow
-            + manometer_reading_error_pascals *
-            manometer_reading_error_pascals
-            + fldk_error_pascals_squared;
+            let total_pressure_error_estimate_pascals_squared = 
+                two_percent_flowrate_error_ctah_heater_only_flow * 
+                two_percent_flowrate_error_ctah_heater_only_flow
+                + manometer_reading_error_pascals *
+                manometer_reading_error_pascals
+                + fldk_error_pascals_squared;
 
-        let total_pressure_error_estimate = 
-            total_pressure_error_estimate_pascals_squared.sqrt();
+            let total_pressure_error_estimate = 
+                total_pressure_error_estimate_pascals_squared.sqrt();
 
 
-        let now = DateTime::now();
-        let _ = address_space_lock.set_variable_value(
-            loop_pressure_drop_error_total_node.clone(), 
-            total_pressure_error_estimate.value as f64,
-            &now, 
-            &now);
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                loop_pressure_drop_error_total_node.clone(), 
+                total_pressure_error_estimate.value as f64,
+                &now, 
+                &now);
+
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                hydraulics_converged_node.clone(), 
+                hydraulics_converged,
+                &now, 
+                &now);
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                hydraulics_solver_status_node.clone(), 
+                UAString::from(hydraulics_solver_status.as_str()),
+                &now, 
+                &now);
 
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                hydraulics_brent_fallback_count_node.clone(), 
+                ciet_facility.get_brent_fallback_count() as u64,
+                &now, 
+                &now);
 
-        // i think we are done!
+            // i think we are done!
 
+            // release the address space before waiting for 
+            // the next calculation
+            drop(address_space_lock);
 
+            let time_taken_for_calculation = start_of_calc_time.elapsed();
+            if time_taken_for_calculation < hydraulics_calculation_period {
+                thread::sleep(hydraulics_calculation_period 
+                    - time_taken_for_calculation);
+            }
+        }
     };
 
-    server.add_polling_action(500, calculate_flowrate_and_pressure_loss);
+    // the hydraulics run in their own thread at a fixed
+    // period rather than in a polling action
+    thread::spawn(calculate_flowrate_and_pressure_loss);
 
     // to check if polling server adds the polling time to
     // the execution time
@@ -503,23 +1484,23 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     // the second polling action is the ciet heater code
 
     // first, initial conditions and timestep
-    let timestep = Time::new::<uom::si::time::millisecond>(15.0);
+    let timestep = Time::new::<uom::si::time::millisecond>(
+        server_config.heater_timestep_ms as f64);
     let initial_temperature: ThermodynamicTemperature = 
     ThermodynamicTemperature::new::<degree_celsius>(79.12);
-    let inlet_temperature = initial_temperature;
     let ambient_air_temp: ThermodynamicTemperature = 
     ThermodynamicTemperature::new::<degree_celsius>(21.67);
 
-    // next, heater nodalisation
-    let number_of_inner_temperature_nodes: usize = 6;
-
 
-    let heater_v2_bare_shared_ptr = Arc::new(Mutex::new(
-        HeaterVersion2Bare::new_dewet_model(
+    let heated_section_shared_ptr = Arc::new(Mutex::new(
+        HeatedSection::new(
+        heater_version,
         initial_temperature,
         ambient_air_temp,
         number_of_inner_temperature_nodes
     )));
+    heated_section_shared_ptr.lock().unwrap()
+        .set_axial_power_shape(axial_power_shape.clone());
 
     let heater_top_head_bare_shared_ptr
     = Arc::new(Mutex::new(
@@ -570,13 +1551,124 @@ pub fn construct_and_run_ciet_server(run_server: bool){
     let structural_support_mx_10_shared_ptr = 
     Arc::new(Mutex::new(structural_support_mx_10));
 
-    let inlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> 
-    = Arc::new(Mutex::new(BCType::new_const_temperature( 
-        inlet_temperature).into()));
-
-    let outlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> = 
-    Arc::new(Mutex::new(
-        BCType::new_adiabatic_bc().into()));
+    // CTAH, this is solved in the same timestep as the heater
+    let ctah_shared_ptr = Arc::new(Mutex::new(
+        CoiledTubeAirHeatExchanger::new_ciet_ctah(
+        initial_temperature,
+        ambient_air_temp,
+        number_of_inner_temperature_nodes
+    )));
+
+    // DHX and TCHX, likewise solved in the same timestep, 
+    // the DRACS piping between them is not modelled thermally
+    // so each takes its inlet temperature from the outlet 
+    // of the other
+    let dhx_shared_ptr = Arc::new(Mutex::new(
+        DHXShellAndTubeHeatExchanger::new_ciet_dhx(
+        initial_temperature,
+        number_of_inner_temperature_nodes
+    )));
+
+    let tchx_shared_ptr = Arc::new(Mutex::new(
+        CoiledTubeAirHeatExchanger::new_dracs_tchx(
+        initial_temperature,
+        ambient_air_temp,
+        number_of_inner_temperature_nodes
+    )));
+
+    let dhx_tube_inlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> 
+    = Arc::new(Mutex::new(BCType::new_const_temperature( 
+        initial_temperature).into()));
+
+    let dhx_tube_outlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> = 
+    Arc::new(Mutex::new(
+        BCType::new_adiabatic_bc().into()));
+
+    let tchx_inlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> 
+    = Arc::new(Mutex::new(BCType::new_const_temperature( 
+        initial_temperature).into()));
+
+    let tchx_outlet_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> = 
+    Arc::new(Mutex::new(
+        BCType::new_adiabatic_bc().into()));
+
+    // the pipes of each branch, between the branch ends 
+    // (where the branches meet) and the heated or cooled 
+    // components, these are built from the same loop 
+    // definition as the hydraulics
+    //
+    // each chain is listed in the direction its flow is taken 
+    // as positive, for the heater branch this is upwards 
+    // (BT-11 to BT-12), and for the ctah and dhx branches 
+    // this is downwards as in the hydraulics
+    //
+    // the loop definition names the components modelled by the 
+    // heater, CTAH and DHX thermal models, and its validation 
+    // checks that they are next to each other in their branch
+    let thermal_components: CIETThermalComponentNames = 
+        ciet_loop_definition.thermal_components.clone();
+
+    let (heater_upper_pipes, heater_section, heater_lower_pipes) = 
+        split_at_thermal_components(&ciet_loop_definition.heater_branch, 
+            &thermal_components.get_heater_branch_names());
+    let (ctah_upper_pipes, ctah_section, ctah_lower_pipes) = 
+        split_at_thermal_components(&ciet_loop_definition.ctah_branch, 
+            &thermal_components.get_ctah_branch_names());
+    let (dhx_upper_pipes, dhx_section, dhx_lower_pipes) = 
+        split_at_thermal_components(&ciet_loop_definition.dhx_branch, 
+            &thermal_components.get_dhx_branch_names());
+
+    // names of the thermally modelled components in the order 
+    // they appear in each branch, the thermal model temperatures 
+    // are put in this order for the hydraulics
+    let get_names = |section: Vec<CIETComponentDefinition>| {
+        section.iter()
+            .map(|component| component.get_name().to_string())
+            .collect::<Vec<String>>()
+    };
+    let heater_section_names = get_names(heater_section);
+    let ctah_section_names = get_names(ctah_section);
+    let dhx_section_names = get_names(dhx_section);
+
+    let new_pipe_chain_shared_ptr = 
+    |component_definitions: Vec<CIETComponentDefinition>| {
+        Arc::new(Mutex::new(
+            ThermalPipeChain::new_from_definitions(
+                component_definitions.iter(),
+                initial_temperature,
+                ambient_air_temp)))
+    };
+
+    // BT-11 to the heater bottom head (pipe 18), and 
+    // static mixer 10 pipe (BT-12) to the top of the branch
+    let heater_lower_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        heater_lower_pipes);
+    let heater_upper_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        heater_upper_pipes.into_iter().rev().collect());
+
+    // top of the branch to BT-41, and BT-43 to the bottom 
+    // of the branch
+    let ctah_upper_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        ctah_upper_pipes);
+    let ctah_lower_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        ctah_lower_pipes);
+
+    let dhx_upper_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        dhx_upper_pipes);
+    let dhx_lower_pipes_shared_ptr = new_pipe_chain_shared_ptr(
+        dhx_lower_pipes);
+
+    // the branches meet at the top and bottom of the loop,
+    // fluid entering a branch from either end comes in at 
+    // the mixed temperature of the streams flowing into 
+    // that end, these are updated every timestep
+    let top_of_branches_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> 
+    = Arc::new(Mutex::new(BCType::new_const_temperature( 
+        initial_temperature).into()));
+
+    let bottom_of_branches_bc_shared_ptr: Arc<Mutex<HeatTransferEntity>> 
+    = Arc::new(Mutex::new(BCType::new_const_temperature( 
+        initial_temperature).into()));
 
     let approx_support_conductance: ThermalConductance = 
     structural_support_heater_top_head_shared_ptr.lock().unwrap()
@@ -584,15 +1676,49 @@ pub fn construct_and_run_ciet_server(run_server: bool){
 
     let ambient_air_temp_bc_shared_ptr: 
     Arc<Mutex<HeatTransferEntity>> = Arc::new(Mutex::new(
-        inlet_bc_shared_ptr.lock().unwrap().clone()
+        BCType::new_const_temperature(initial_temperature).into()
     ));
     // struct support conductance assumed constant
     // kind of negligible so doesn't matter
     let support_conductance_interaction = HeatTransferInteractionType::
         UserSpecifiedThermalConductance(approx_support_conductance);
 
-    // mass flowrate constant
-    let mass_flowrate = MassRate::new::<kilogram_per_second>(0.18);
+    // the point kinetics model only exists while it is enabled
+    let point_kinetics_shared_ptr: Arc<Mutex<Option<PointKinetics>>> = 
+        Arc::new(Mutex::new(None));
+
+    // every component of the heater loop is put back to its 
+    // initial state when an instructor resets the simulation
+    let reset_heater_loop_components: Vec<Box<dyn Fn() + Send + Sync>> = vec![
+        get_reset_function(&heated_section_shared_ptr),
+        get_reset_function(&heater_top_head_bare_shared_ptr),
+        get_reset_function(&heater_bottom_head_bare_shared_ptr),
+        get_reset_function(&static_mixer_mx_10_object_shared_ptr),
+        get_reset_function(&static_mixer_mx_10_pipe_shared_ptr),
+        get_reset_function(&structural_support_heater_top_head_shared_ptr),
+        get_reset_function(&structural_support_heater_bottom_head_shared_ptr),
+        get_reset_function(&structural_support_mx_10_shared_ptr),
+        get_reset_function(&ctah_shared_ptr),
+        get_reset_function(&dhx_shared_ptr),
+        get_reset_function(&tchx_shared_ptr),
+        get_reset_function(&dhx_tube_inlet_bc_shared_ptr),
+        get_reset_function(&dhx_tube_outlet_bc_shared_ptr),
+        get_reset_function(&tchx_inlet_bc_shared_ptr),
+        get_reset_function(&tchx_outlet_bc_shared_ptr),
+        get_reset_function(&heater_lower_pipes_shared_ptr),
+        get_reset_function(&heater_upper_pipes_shared_ptr),
+        get_reset_function(&ctah_upper_pipes_shared_ptr),
+        get_reset_function(&ctah_lower_pipes_shared_ptr),
+        get_reset_function(&dhx_upper_pipes_shared_ptr),
+        get_reset_function(&dhx_lower_pipes_shared_ptr),
+        get_reset_function(&top_of_branches_bc_shared_ptr),
+        get_reset_function(&bottom_of_branches_bc_shared_ptr),
+        get_reset_function(&ambient_air_temp_bc_shared_ptr),
+        get_reset_function(&point_kinetics_shared_ptr),
+        get_reset_function(&heater_loop_temperatures_shared_ptr),
+        get_reset_function(&heater_loop_dracs_temperatures_shared_ptr),
+    ];
+
     // main loop for ciet heater
 
     let loop_time = SystemTime::now();
@@ -600,6 +1726,24 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         // timer start 
         let loop_time_start = loop_time.elapsed().unwrap();
 
+        // simulation reset from the reset_simulation method, 
+        // the components, controller and status nodes go back 
+        // to their initial state and the ctah pump trip is reset
+        if simulation_reset_requested.swap(false, Ordering::SeqCst) {
+            for reset_component in reset_heater_loop_components.iter() {
+                reset_component();
+            }
+
+            heater_loop_pump_trip_requested.store(false, Ordering::SeqCst);
+
+            let now = DateTime::now();
+            let mut address_space_lock = address_space.write();
+            for (node, value) in initial_node_values.iter() {
+                let _ = address_space_lock.set_variable_value(
+                    node.clone(), value.clone(), &now, &now);
+            }
+        }
+
         // bcs 
 
 
@@ -617,17 +1761,17 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         // this is needed for heated section 
         // bulk outlet temperature
         let mut therminol_array_clone: FluidArray 
-        = heater_v2_bare_shared_ptr.lock().unwrap().
-            therminol_array.clone().try_into().unwrap();
+        = heated_section_shared_ptr.lock().unwrap().
+            therminol_array().clone().try_into().unwrap();
 
 
         let heater_fluid_bulk_temp: ThermodynamicTemperature = 
         therminol_array_clone.try_get_bulk_temperature().unwrap();
 
         // this is needed for heater surface temperatures
-        let _heater_surface_array_clone: SolidColumn 
-        = heater_v2_bare_shared_ptr.lock() 
-        .unwrap().steel_shell.clone().try_into().unwrap();
+        let mut heater_surface_array_clone: SolidColumn 
+        = heated_section_shared_ptr.lock() 
+        .unwrap().steel_shell().clone().try_into().unwrap();
 
 
         // heater top head exit temperature for comparison
@@ -661,52 +1805,547 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         LiquidMaterial::TherminolVP1.density(
             heater_fluid_bulk_temp).unwrap();
 
+        // the mass flowrate comes from the hydraulics polling action,
+        // which solves the heater branch flowrate from the pump 
+        // pressure and valve positions
+        //
+        // in the branch convention, positive flow leaves the top 
+        // of the branch, so flow going up through the heater 
+        // (BT-11 to BT-12) is negative. The thermal arrays here 
+        // are ordered from BT-11 to BT-12, so I flip the sign.
+        // When the heater valve is shut, this is zero.
+        let mass_flowrate: MassRate = {
+            let address_space_lock = address_space.write();
+            let heater_branch_flowrate_kg_per_s = address_space_lock.
+                get_variable_value(
+                    heater_loop_heater_branch_flowrate_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
+
+            -MassRate::new::<kilogram_per_second>(
+                heater_branch_flowrate_kg_per_s)
+        };
+
         let generic_advection_interaction = 
         HeatTransferInteractionType::new_advection_interaction(
             mass_flowrate,
             heater_therminol_avg_density,
             heater_therminol_avg_density,
         );
-        // calculation steps, read bt11_temperature_degC and 
-        // heater power from opc-ua input
-        let heater_power: Power;
-        let heater_inlet_temp: ThermodynamicTemperature;
+        // calculation steps, read heater power from opc-ua input
+        let mut heater_power: Power;
         {
             let address_space_lock = address_space.write();
-            let bt11_user_input_value_deg_c = address_space_lock.
+            let heater_user_input_value_kilowatts = address_space_lock.
                 get_variable_value(
-                    bt11_temperature_node.clone())
+                    heater_power_node.clone())
                 .unwrap().value.unwrap()
                 .as_f64().unwrap();
 
-            heater_inlet_temp = ThermodynamicTemperature::new::
-                <degree_celsius>(bt11_user_input_value_deg_c);
+            heater_power = Power::new::<kilowatt>(
+                heater_user_input_value_kilowatts);
+        }
+
+        // point kinetics, if enabled, starts at steady state at 
+        // the operator heater power and then computes heater 
+        // power from reactivity
+        //
+        // a reactor at zero power stays at zero power, so enabling
+        // point kinetics at zero heater power is rejected and 
+        // point_kinetics_enabled is switched off again
+        {
+            let mut address_space_lock = address_space.write();
+
+            let point_kinetics_enabled: bool = matches!(address_space_lock.
+                get_variable_value(point_kinetics_enabled_node.clone())
+                .unwrap().value, Some(Variant::Boolean(true)));
 
-            let user_set_inlet_bc: HeatTransferEntity = 
-            BCType::new_const_temperature( 
-                heater_inlet_temp).into();
-            // change inlet bc ptr 
+            let reactor_scram: bool = matches!(address_space_lock.
+                get_variable_value(reactor_scram_node.clone())
+                .unwrap().value, Some(Variant::Boolean(true)));
 
-            inlet_bc_shared_ptr.lock().unwrap().set(
-                user_set_inlet_bc).unwrap();
+            let external_reactivity_dollars: f64 = address_space_lock.
+                get_variable_value(external_reactivity_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
 
+            let mut point_kinetics_option = 
+                point_kinetics_shared_ptr.lock().unwrap();
+
+            let mut point_kinetics_status: Option<String> = None;
+
+            if !point_kinetics_enabled {
+                if point_kinetics_option.take().is_some() {
+                    point_kinetics_status = Some("disabled".to_string());
+                }
+            } else if point_kinetics_option.is_none() 
+                && heater_power <= Power::new::<kilowatt>(0.0) {
+                point_kinetics_status = Some(format!(
+                    "rejected: point kinetics cannot start at a heater \
+                    power of {} kW", heater_power.get::<kilowatt>()));
+                let now = DateTime::now();
+                let _ = address_space_lock.set_variable_value(
+                    point_kinetics_enabled_node.clone(), 
+                    false,
+                    &now, &now);
+            } else {
+                if point_kinetics_option.is_none() {
+                    point_kinetics_status = Some("enabled".to_string());
+                }
+
+                let fuel_temperature: ThermodynamicTemperature = 
+                    heater_surface_array_clone.try_get_bulk_temperature().unwrap();
+                let coolant_temperature = heater_fluid_bulk_temp;
+
+                let point_kinetics = point_kinetics_option.get_or_insert_with(|| {
+                    PointKinetics::new_steady_state(
+                        heater_power,
+                        fuel_temperature,
+                        coolant_temperature)
+                });
+
+                point_kinetics.set_external_reactivity_dollars(
+                    external_reactivity_dollars);
+                point_kinetics.scrammed = reactor_scram;
+
+                point_kinetics.advance_timestep(
+                    timestep,
+                    fuel_temperature,
+                    coolant_temperature);
+
+                heater_power = point_kinetics.get_heater_power();
+            }
 
-            let heater_user_input_value_kilowatts = address_space_lock.
+            let (point_kinetics_power_kilowatts, total_reactivity_dollars,
+                decay_heat_kilowatts): (f64, f64, f64) = 
+                match point_kinetics_option.as_ref() {
+                    Some(point_kinetics) => (
+                        point_kinetics.get_heater_power().get::<kilowatt>(),
+                        point_kinetics.get_total_reactivity_dollars(),
+                        point_kinetics.get_decay_heat().get::<kilowatt>()),
+                    None => (0.0, 0.0, 0.0),
+                };
+
+            let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                point_kinetics_power_node.clone(), 
+                point_kinetics_power_kilowatts,
+                &now, 
+                &now);
+            let _ = address_space_lock.set_variable_value(
+                total_reactivity_node.clone(), 
+                total_reactivity_dollars,
+                &now, 
+                &now);
+            let _ = address_space_lock.set_variable_value(
+                decay_heat_node.clone(), 
+                decay_heat_kilowatts,
+                &now, 
+                &now);
+
+            if let Some(point_kinetics_status) = point_kinetics_status {
+                let _ = address_space_lock.set_variable_value(
+                    point_kinetics_status_node.clone(), 
+                    UAString::from(point_kinetics_status),
+                    &now, 
+                    &now);
+            }
+        }
+
+        // heater axial power profile from opc-ua input, only 
+        // changed if the power fractions differ from the current 
+        // shape, an invalid profile is rejected and overwritten 
+        // with the current shape, either way the outcome is 
+        // published on heater_axial_power_profile_status
+        {
+            let mut address_space_lock = address_space.write();
+            let profile_value = address_space_lock.
                 get_variable_value(
-                    heater_power_node.clone())
+                    heater_axial_power_profile_node.clone())
+                .unwrap().value.unwrap();
+
+            let relative_powers: Option<Vec<f64>> = match profile_value {
+                Variant::Array(array) => array.values.iter()
+                    .map(|value| value.as_f64()).collect(),
+                _ => None,
+            };
+
+            let mut heated_section = heated_section_shared_ptr.lock().unwrap();
+            let current_power_fractions = heated_section.axial_power_shape()
+                .get_power_fractions(number_of_heater_temperature_nodes);
+
+            let new_shape: Result<AxialPowerShape, String> = match relative_powers {
+                Some(relative_powers) => AxialPowerShape::new_table(relative_powers),
+                None => Err("heater axial power profile is not an array \
+                    of numbers".to_string()),
+            };
+
+            match new_shape {
+                Ok(new_shape) => {
+                    let new_power_fractions = new_shape
+                        .get_power_fractions(number_of_heater_temperature_nodes);
+
+                    let profile_changed = new_power_fractions.iter()
+                        .zip(current_power_fractions.iter())
+                        .any(|(new, current)| (new - current).abs() > 1e-12);
+
+                    if profile_changed {
+                        let now = DateTime::now();
+                        let _ = address_space_lock.set_variable_value(
+                            heater_axial_power_shape_node.clone(), 
+                            UAString::from(new_shape.to_string()),
+                            &now, &now);
+                        let _ = address_space_lock.set_variable_value(
+                            heater_axial_power_profile_status_node.clone(), 
+                            UAString::from("accepted"),
+                            &now, &now);
+                        heated_section.set_axial_power_shape(new_shape);
+                    }
+                },
+                Err(error) => {
+                    let current_profile: Vec<f64> = current_power_fractions
+                        .iter().map(|fraction| {
+                            fraction * number_of_heater_temperature_nodes as f64
+                        }).collect();
+                    let now = DateTime::now();
+                    let _ = address_space_lock.set_variable_value(
+                        heater_axial_power_profile_node.clone(), 
+                        current_profile,
+                        &now, &now);
+                    let _ = address_space_lock.set_variable_value(
+                        heater_axial_power_profile_status_node.clone(), 
+                        UAString::from(format!("rejected: {}", error)),
+                        &now, &now);
+                },
+            }
+        }
+
+        // CTAH inputs, the therminol flowrate comes from the ctah 
+        // branch flowrate, where positive flow goes top to bottom,
+        // ie. forward through the CTAH from BT-41 to BT-43
+        let ctah_mass_flowrate: MassRate;
+        {
+            let address_space_lock = address_space.write();
+            let ctah_branch_flowrate_kg_per_s = address_space_lock.
+                get_variable_value(
+                    heater_loop_ctah_branch_flowrate_node.clone())
                 .unwrap().value.unwrap()
                 .as_f64().unwrap();
 
-            heater_power = Power::new::<kilowatt>(
-                heater_user_input_value_kilowatts);
+            ctah_mass_flowrate = MassRate::new::<kilogram_per_second>(
+                ctah_branch_flowrate_kg_per_s);
+
+            let ctah_fan_speed_hz = address_space_lock.
+                get_variable_value(
+                    ctah_fan_speed_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
+
+            ctah_shared_ptr.lock().unwrap().set_fan_speed(
+                Frequency::new::<hertz>(ctah_fan_speed_hz));
+        }
+
+        // BT-41 is at the end of the pipes leading to the CTAH
+        let ctah_inlet_temp: ThermodynamicTemperature = 
+        ctah_upper_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+
+        let ctah_outlet_temp: ThermodynamicTemperature = 
+        ctah_shared_ptr.lock().unwrap().get_outlet_temperature();
+
+        let mut ctah_therminol_array_clone: FluidArray = 
+        ctah_shared_ptr.lock().unwrap()
+            .therminol_array.clone().try_into().unwrap();
+
+        let ctah_therminol_avg_density: MassDensity = 
+        LiquidMaterial::TherminolVP1.density(
+            ctah_therminol_array_clone.try_get_bulk_temperature().unwrap())
+            .unwrap();
+
+        let ctah_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            ctah_mass_flowrate,
+            ctah_therminol_avg_density,
+            ctah_therminol_avg_density,
+        );
+
+        // DHX and DRACS inputs, the shell side flowrate is the dhx 
+        // branch flowrate (positive going down the DHX), and the 
+        // tube side flowrate is the DRACS loop flowrate (positive 
+        // going up the DHX and then through the TCHX)
+        let dhx_shell_side_mass_flowrate: MassRate;
+        let dracs_mass_flowrate: MassRate;
+        {
+            let address_space_lock = address_space.write();
+            let dhx_branch_flowrate_kg_per_s = address_space_lock.
+                get_variable_value(
+                    heater_loop_dhx_branch_flowrate_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
+
+            dhx_shell_side_mass_flowrate = MassRate::new::<kilogram_per_second>(
+                dhx_branch_flowrate_kg_per_s);
+
+            let dracs_flowrate_kg_per_s = address_space_lock.
+                get_variable_value(
+                    heater_loop_dracs_flowrate_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
+
+            dracs_mass_flowrate = MassRate::new::<kilogram_per_second>(
+                dracs_flowrate_kg_per_s);
+
+            let tchx_fan_speed_hz = address_space_lock.
+                get_variable_value(
+                    tchx_fan_speed_node.clone())
+                .unwrap().value.unwrap()
+                .as_f64().unwrap();
+
+            tchx_shared_ptr.lock().unwrap().set_fan_speed(
+                Frequency::new::<hertz>(tchx_fan_speed_hz));
         }
 
+        let dhx_shell_inlet_temp: ThermodynamicTemperature = 
+        dhx_upper_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+        let dhx_shell_outlet_temp: ThermodynamicTemperature = 
+        dhx_shared_ptr.lock().unwrap().get_shell_side_outlet_temperature();
+        let dhx_tube_outlet_temp: ThermodynamicTemperature = 
+        dhx_shared_ptr.lock().unwrap().get_tube_side_outlet_temperature();
+        let tchx_outlet_temp: ThermodynamicTemperature = 
+        tchx_shared_ptr.lock().unwrap().get_outlet_temperature();
+
+        // the DHX tube inlet is fed from the TCHX outlet, and the 
+        // TCHX inlet from the DHX tube outlet
+        let dhx_tube_inlet_temp: ThermodynamicTemperature = tchx_outlet_temp;
+        let tchx_inlet_temp: ThermodynamicTemperature = dhx_tube_outlet_temp;
+
+        let dhx_tube_inlet_bc: HeatTransferEntity = 
+        BCType::new_const_temperature(dhx_tube_inlet_temp).into();
+        dhx_tube_inlet_bc_shared_ptr.lock().unwrap().set(
+            dhx_tube_inlet_bc).unwrap();
+
+        let tchx_inlet_bc: HeatTransferEntity = 
+        BCType::new_const_temperature(tchx_inlet_temp).into();
+        tchx_inlet_bc_shared_ptr.lock().unwrap().set(
+            tchx_inlet_bc).unwrap();
+
+        let dhx_shell_side_bulk_temp: ThermodynamicTemperature = 
+        dhx_shared_ptr.lock().unwrap().get_shell_side_bulk_temperature();
+        let dhx_tube_side_bulk_temp: ThermodynamicTemperature = 
+        dhx_shared_ptr.lock().unwrap().get_tube_side_bulk_temperature();
+
+        let mut tchx_therminol_array_clone: FluidArray = 
+        tchx_shared_ptr.lock().unwrap()
+            .therminol_array.clone().try_into().unwrap();
+        let tchx_bulk_temp: ThermodynamicTemperature = 
+        tchx_therminol_array_clone.try_get_bulk_temperature().unwrap();
+
+        let dhx_shell_side_avg_density: MassDensity = 
+        LiquidMaterial::TherminolVP1.density(
+            dhx_shell_side_bulk_temp).unwrap();
+        let dhx_tube_side_avg_density: MassDensity = 
+        LiquidMaterial::TherminolVP1.density(
+            dhx_tube_side_bulk_temp).unwrap();
+        let tchx_therminol_avg_density: MassDensity = 
+        LiquidMaterial::TherminolVP1.density(
+            tchx_bulk_temp).unwrap();
+
+        let dhx_shell_side_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            dhx_shell_side_mass_flowrate,
+            dhx_shell_side_avg_density,
+            dhx_shell_side_avg_density,
+        );
+        let dhx_tube_side_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            dracs_mass_flowrate,
+            dhx_tube_side_avg_density,
+            dhx_tube_side_avg_density,
+        );
+        let tchx_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            dracs_mass_flowrate,
+            tchx_therminol_avg_density,
+            tchx_therminol_avg_density,
+        );
+
+        // the branches mix at the top and bottom of the loop,
+        // at the top, fluid flows in from the heater branch 
+        // when it flows upwards and from the ctah and dhx branches
+        // when they flow upwards (reverse flow), at the bottom,
+        // the other way round
+        //
+        // if nothing flows into either end, its temperature 
+        // is left as it was
+        let heater_branch_top_temp: ThermodynamicTemperature = 
+        heater_upper_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+        let heater_branch_bottom_temp: ThermodynamicTemperature = 
+        heater_lower_pipes_shared_ptr.lock().unwrap().get_back_temperature();
+        let ctah_branch_top_temp: ThermodynamicTemperature = 
+        ctah_upper_pipes_shared_ptr.lock().unwrap().get_back_temperature();
+        let ctah_branch_bottom_temp: ThermodynamicTemperature = 
+        ctah_lower_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+        let dhx_branch_top_temp: ThermodynamicTemperature = 
+        dhx_upper_pipes_shared_ptr.lock().unwrap().get_back_temperature();
+        let dhx_branch_bottom_temp: ThermodynamicTemperature = 
+        dhx_lower_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+
+        let top_of_branches_temp: Option<ThermodynamicTemperature> = 
+        get_mixed_temperature(&[
+            (mass_flowrate, heater_branch_top_temp),
+            (-ctah_mass_flowrate, ctah_branch_top_temp),
+            (-dhx_shell_side_mass_flowrate, dhx_branch_top_temp),
+        ]);
+
+        let bottom_of_branches_temp: Option<ThermodynamicTemperature> = 
+        get_mixed_temperature(&[
+            (-mass_flowrate, heater_branch_bottom_temp),
+            (ctah_mass_flowrate, ctah_branch_bottom_temp),
+            (dhx_shell_side_mass_flowrate, dhx_branch_bottom_temp),
+        ]);
+
+        if let Some(top_of_branches_temp) = top_of_branches_temp {
+            top_of_branches_bc_shared_ptr.lock().unwrap().set(
+                BCType::new_const_temperature(top_of_branches_temp).into()
+            ).unwrap();
+        }
+
+        if let Some(bottom_of_branches_temp) = bottom_of_branches_temp {
+            bottom_of_branches_bc_shared_ptr.lock().unwrap().set(
+                BCType::new_const_temperature(bottom_of_branches_temp).into()
+            ).unwrap();
+        }
+
+        // BT-11 is at the end of pipe 18, where fluid enters 
+        // the heater bottom head
+        let bt_11_temperature: ThermodynamicTemperature = 
+        heater_lower_pipes_shared_ptr.lock().unwrap().get_front_temperature();
+
+        let heater_lower_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            mass_flowrate,
+            heater_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            heater_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+        let heater_upper_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            mass_flowrate,
+            heater_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            heater_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+        let ctah_upper_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            ctah_mass_flowrate,
+            ctah_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            ctah_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+        let ctah_lower_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            ctah_mass_flowrate,
+            ctah_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            ctah_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+        let dhx_upper_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            dhx_shell_side_mass_flowrate,
+            dhx_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            dhx_upper_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+        let dhx_lower_pipes_advection_interaction = 
+        HeatTransferInteractionType::new_advection_interaction(
+            dhx_shell_side_mass_flowrate,
+            dhx_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+            dhx_lower_pipes_shared_ptr.lock().unwrap().get_average_density(),
+        );
+
         // postprocessing, print out temperature sensors
         {
             let bt_12_temperature: ThermodynamicTemperature = 
             static_mixer_pipe_therminol_clone.get_temperature_vector().unwrap() 
                 .into_iter().last().unwrap();
 
+            // update the loop temperatures for the hydraulics,
+            // every component temperature now comes from a 
+            // thermal model, listed from the top of each branch 
+            // to the bottom
+            let heater_bottom_head_bare_therminol_clone: FluidArray = 
+            heater_bottom_head_bare_shared_ptr.lock().unwrap()
+                .therminol_array.clone().try_into().unwrap();
+
+            let (ctah_vertical_temperature, ctah_horizontal_temperature) = 
+            ctah_shared_ptr.lock().unwrap()
+                .get_vertical_and_horizontal_temperatures();
+
+            let heater_section_temperatures = order_as_in_branch(
+                &heater_section_names, &[
+                    (&thermal_components.static_mixer_10, 
+                     static_mixer_therminol_clone.clone()
+                        .try_get_bulk_temperature().unwrap()),
+                    (&thermal_components.static_mixer_10_pipe, 
+                     static_mixer_pipe_therminol_clone.clone()
+                        .try_get_bulk_temperature().unwrap()),
+                    (&thermal_components.heater_top_head, 
+                     heater_top_head_bare_therminol_clone.clone()
+                        .try_get_bulk_temperature().unwrap()),
+                    (&thermal_components.heated_section, 
+                     heater_fluid_bulk_temp),
+                    (&thermal_components.heater_bottom_head, 
+                     heater_bottom_head_bare_therminol_clone.clone()
+                        .try_get_bulk_temperature().unwrap()),
+                ]);
+
+            let heater_branch_temperatures: Vec<ThermodynamicTemperature> = 
+            heater_upper_pipes_shared_ptr.lock().unwrap()
+                .get_bulk_temperatures().into_iter().rev()
+                .chain(heater_section_temperatures)
+                .chain(heater_lower_pipes_shared_ptr.lock().unwrap()
+                    .get_bulk_temperatures())
+                .collect();
+
+            let ctah_section_temperatures = order_as_in_branch(
+                &ctah_section_names, &[
+                    (&thermal_components.ctah_vertical, ctah_vertical_temperature),
+                    (&thermal_components.ctah_horizontal, ctah_horizontal_temperature),
+                ]);
+
+            let ctah_branch_temperatures: Vec<ThermodynamicTemperature> = 
+            ctah_upper_pipes_shared_ptr.lock().unwrap()
+                .get_bulk_temperatures().into_iter()
+                .chain(ctah_section_temperatures)
+                .chain(ctah_lower_pipes_shared_ptr.lock().unwrap()
+                    .get_bulk_temperatures())
+                .collect();
+
+            let dhx_section_temperatures = order_as_in_branch(
+                &dhx_section_names, &[
+                    (&thermal_components.dhx_shell_side, dhx_shell_side_bulk_temp),
+                ]);
+
+            let dhx_branch_temperatures: Vec<ThermodynamicTemperature> = 
+            dhx_upper_pipes_shared_ptr.lock().unwrap()
+                .get_bulk_temperatures().into_iter()
+                .chain(dhx_section_temperatures)
+                .chain(dhx_lower_pipes_shared_ptr.lock().unwrap()
+                    .get_bulk_temperatures())
+                .collect();
+
+            *heater_loop_dracs_temperatures_shared_ptr.lock().unwrap() = 
+                DRACSLoopTemperatures {
+                    dhx_tube_side: dhx_tube_side_bulk_temp,
+                    hot_leg: dhx_tube_outlet_temp,
+                    tchx: tchx_bulk_temp,
+                    cold_leg: tchx_outlet_temp,
+                };
+
+            // the hydraulics check these against the components
+            // of the facility by name and count
+            {
+                let mut loop_temperatures = 
+                    heater_loop_temperatures_shared_ptr.lock().unwrap();
+                loop_temperatures.heater_branch = heater_branch_temperatures;
+                loop_temperatures.ctah_branch = ctah_branch_temperatures;
+                loop_temperatures.dhx_branch = dhx_branch_temperatures;
+            }
+
             // get bt_12_temperature in degrees c rounded to 1
             // decimal place
             let bt12_temperature_deg_c: f64 = 
@@ -714,30 +2353,124 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             .round()
             /10.0;
 
-            // set bt12 temperature node
+            let bt11_temperature_deg_c: f64 = 
+            (bt_11_temperature.get::<degree_celsius>()*10.0)
+            .round()
+            /10.0;
+
+            // set bt11 and bt12 temperature nodes
             let mut address_space_lock = address_space.write();
             let now = DateTime::now();
+            let _ = address_space_lock.set_variable_value(
+                bt11_temperature_node.clone(), 
+                bt11_temperature_deg_c as f64,
+                &now, 
+                &now);
+
             let _ = address_space_lock.set_variable_value(
                 bt12_temperature_node.clone(), 
                 bt12_temperature_deg_c as f64,
                 &now, 
                 &now);
+
+            // CTAH inlet and outlet temperatures, and the heat
+            // removed from the therminol
+            let bt41_temperature_deg_c: f64 = 
+            (ctah_inlet_temp.get::<degree_celsius>()*10.0)
+            .round()
+            /10.0;
+
+            let bt43_temperature_deg_c: f64 = 
+            (ctah_outlet_temp.get::<degree_celsius>()*10.0)
+            .round()
+            /10.0;
+
+            let ctah_heat_removal_kilowatts: f64 = 
+            ctah_shared_ptr.lock().unwrap().get_heat_removal_rate(
+                ctah_mass_flowrate,
+                ctah_inlet_temp).get::<kilowatt>();
+
+            let _ = address_space_lock.set_variable_value(
+                bt41_temperature_node.clone(), 
+                bt41_temperature_deg_c as f64,
+                &now, 
+                &now);
+
+            let _ = address_space_lock.set_variable_value(
+                bt43_temperature_node.clone(), 
+                bt43_temperature_deg_c as f64,
+                &now, 
+                &now);
+
+            let _ = address_space_lock.set_variable_value(
+                ctah_heat_removal_node.clone(), 
+                ctah_heat_removal_kilowatts as f64,
+                &now, 
+                &now);
+
+            // DHX and DRACS loop temperatures, and the heat 
+            // removed from the primary loop by the DHX
+            let dhx_heat_removal_kilowatts: f64 = 
+            dhx_shared_ptr.lock().unwrap().get_heat_removal_rate(
+                dhx_shell_side_mass_flowrate,
+                dhx_shell_inlet_temp).get::<kilowatt>();
+
+            let dracs_temperature_nodes = [
+                (&dhx_shell_inlet_temperature_node, dhx_shell_inlet_temp),
+                (&dhx_shell_outlet_temperature_node, dhx_shell_outlet_temp),
+                (&dhx_tube_inlet_temperature_node, dhx_tube_inlet_temp),
+                (&dhx_tube_outlet_temperature_node, dhx_tube_outlet_temp),
+                (&tchx_outlet_temperature_node, tchx_outlet_temp),
+            ];
+
+            for (temperature_node, temperature) in dracs_temperature_nodes {
+                let temperature_deg_c: f64 = 
+                (temperature.get::<degree_celsius>()*10.0)
+                .round()
+                /10.0;
+
+                let _ = address_space_lock.set_variable_value(
+                    temperature_node.clone(), 
+                    temperature_deg_c as f64,
+                    &now, 
+                    &now);
+            }
+
+            let _ = address_space_lock.set_variable_value(
+                dhx_heat_removal_node.clone(), 
+                dhx_heat_removal_kilowatts as f64,
+                &now, 
+                &now);
         }
 
         // make axial connections to BCs 
-        
-        heater_bottom_head_bare_shared_ptr.lock().unwrap()
-            .therminol_array.link_to_back(
-            &mut inlet_bc_shared_ptr.lock().unwrap(),
-            generic_advection_interaction
-        ).unwrap();
+        //
+        // the heater branch pipes first, from the bottom of 
+        // the branch to the heater bottom head, and from 
+        // static mixer 10 pipe to the top of the branch
+        {
+            let mut heater_lower_pipes = 
+            heater_lower_pipes_shared_ptr.lock().unwrap();
+
+            heater_lower_pipes.link_back_to(
+                &mut bottom_of_branches_bc_shared_ptr.lock().unwrap(),
+                heater_lower_pipes_advection_interaction);
 
-        heater_v2_bare_shared_ptr.lock().unwrap().therminol_array.link_to_back(
+            heater_lower_pipes.link_in_series(
+                heater_lower_pipes_advection_interaction);
+
+            heater_lower_pipes.link_front_to(
+                &mut heater_bottom_head_bare_shared_ptr.lock().unwrap()
+                    .therminol_array,
+                heater_lower_pipes_advection_interaction);
+        }
+
+        heated_section_shared_ptr.lock().unwrap().therminol_array().link_to_back(
             &mut heater_bottom_head_bare_shared_ptr.lock().unwrap().therminol_array,
             generic_advection_interaction
         ).unwrap();
 
-        heater_v2_bare_shared_ptr.lock().unwrap().therminol_array.link_to_front(
+        heated_section_shared_ptr.lock().unwrap().therminol_array().link_to_front(
             &mut heater_top_head_bare_shared_ptr.lock().unwrap().therminol_array,
             generic_advection_interaction
         ).unwrap();
@@ -753,14 +2486,133 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             generic_advection_interaction
         ).unwrap();
 
-        static_mixer_mx_10_pipe_shared_ptr.lock().unwrap().therminol_array.link_to_front(
-            &mut outlet_bc_shared_ptr.lock().unwrap(),
-            generic_advection_interaction
+        {
+            let mut heater_upper_pipes = 
+            heater_upper_pipes_shared_ptr.lock().unwrap();
+
+            heater_upper_pipes.link_back_to(
+                &mut static_mixer_mx_10_pipe_shared_ptr.lock().unwrap()
+                    .therminol_array,
+                heater_upper_pipes_advection_interaction);
+
+            heater_upper_pipes.link_in_series(
+                heater_upper_pipes_advection_interaction);
+
+            heater_upper_pipes.link_front_to(
+                &mut top_of_branches_bc_shared_ptr.lock().unwrap(),
+                heater_upper_pipes_advection_interaction);
+        }
+
+        // the ctah branch, from the top of the branch through 
+        // the CTAH to the bottom of the branch
+        {
+            let mut ctah_upper_pipes = 
+            ctah_upper_pipes_shared_ptr.lock().unwrap();
+
+            ctah_upper_pipes.link_back_to(
+                &mut top_of_branches_bc_shared_ptr.lock().unwrap(),
+                ctah_upper_pipes_advection_interaction);
+
+            ctah_upper_pipes.link_in_series(
+                ctah_upper_pipes_advection_interaction);
+
+            ctah_upper_pipes.link_front_to(
+                &mut ctah_shared_ptr.lock().unwrap().therminol_array,
+                ctah_advection_interaction);
+        }
+
+        {
+            let mut ctah_lower_pipes = 
+            ctah_lower_pipes_shared_ptr.lock().unwrap();
+
+            ctah_lower_pipes.link_back_to(
+                &mut ctah_shared_ptr.lock().unwrap().therminol_array,
+                ctah_advection_interaction);
+
+            ctah_lower_pipes.link_in_series(
+                ctah_lower_pipes_advection_interaction);
+
+            ctah_lower_pipes.link_front_to(
+                &mut bottom_of_branches_bc_shared_ptr.lock().unwrap(),
+                ctah_lower_pipes_advection_interaction);
+        }
+
+        // and likewise the dhx branch through the DHX shell side
+        {
+            let mut dhx_upper_pipes = 
+            dhx_upper_pipes_shared_ptr.lock().unwrap();
+
+            dhx_upper_pipes.link_back_to(
+                &mut top_of_branches_bc_shared_ptr.lock().unwrap(),
+                dhx_upper_pipes_advection_interaction);
+
+            dhx_upper_pipes.link_in_series(
+                dhx_upper_pipes_advection_interaction);
+
+            dhx_upper_pipes.link_front_to(
+                &mut dhx_shared_ptr.lock().unwrap().shell_side_therminol_array,
+                dhx_shell_side_advection_interaction);
+        }
+
+        {
+            let mut dhx_lower_pipes = 
+            dhx_lower_pipes_shared_ptr.lock().unwrap();
+
+            dhx_lower_pipes.link_back_to(
+                &mut dhx_shared_ptr.lock().unwrap().shell_side_therminol_array,
+                dhx_shell_side_advection_interaction);
+
+            dhx_lower_pipes.link_in_series(
+                dhx_lower_pipes_advection_interaction);
+
+            dhx_lower_pipes.link_front_to(
+                &mut bottom_of_branches_bc_shared_ptr.lock().unwrap(),
+                dhx_lower_pipes_advection_interaction);
+        }
+
+        dhx_shared_ptr.lock().unwrap().tube_side_therminol_array.link_to_back(
+            &mut dhx_tube_inlet_bc_shared_ptr.lock().unwrap(),
+            dhx_tube_side_advection_interaction
+        ).unwrap();
+
+        dhx_shared_ptr.lock().unwrap().tube_side_therminol_array.link_to_front(
+            &mut dhx_tube_outlet_bc_shared_ptr.lock().unwrap(),
+            dhx_tube_side_advection_interaction
+        ).unwrap();
+
+        tchx_shared_ptr.lock().unwrap().therminol_array.link_to_back(
+            &mut tchx_inlet_bc_shared_ptr.lock().unwrap(),
+            tchx_advection_interaction
+        ).unwrap();
+
+        tchx_shared_ptr.lock().unwrap().therminol_array.link_to_front(
+            &mut tchx_outlet_bc_shared_ptr.lock().unwrap(),
+            tchx_advection_interaction
         ).unwrap();
 
+        // update heat transfer coefficients to air if they are 
+        // not callibrated constants
+
+        if let AirHeatTransferModel::NaturalConvectionAndRadiation { 
+            emissivity } = air_heat_transfer_model {
+
+            heated_section_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+            heater_bottom_head_bare_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+            heater_top_head_bare_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+            structural_support_heater_top_head_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+            structural_support_heater_bottom_head_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+            structural_support_mx_10_shared_ptr.lock().unwrap().
+                update_heat_transfer_to_air(emissivity);
+        }
+
         // lateral connections without thread spawning 
 
-        heater_v2_bare_shared_ptr.lock().unwrap().
+        heated_section_shared_ptr.lock().unwrap().
             lateral_and_miscellaneous_connections(
                 mass_flowrate,
                 heater_power);
@@ -782,6 +2634,43 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             lateral_and_miscellaneous_connections(
             mass_flowrate);
 
+        ctah_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            ctah_mass_flowrate);
+
+        dhx_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            dhx_shell_side_mass_flowrate,
+            dracs_mass_flowrate);
+
+        tchx_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            dracs_mass_flowrate);
+
+        heater_lower_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            mass_flowrate);
+
+        heater_upper_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            mass_flowrate);
+
+        ctah_upper_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            ctah_mass_flowrate);
+
+        ctah_lower_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            ctah_mass_flowrate);
+
+        dhx_upper_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            dhx_shell_side_mass_flowrate);
+
+        dhx_lower_pipes_shared_ptr.lock().unwrap().
+            lateral_and_miscellaneous_connections(
+            dhx_shell_side_mass_flowrate);
+
 
         // link struct supports to ambient air
         // axially 
@@ -827,26 +2716,30 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         // i will also connect heater shell to the structural support 
         // via the head as in ciet 
 
-        heater_v2_bare_shared_ptr.lock().unwrap().steel_shell.link_to_back(
+        heated_section_shared_ptr.lock().unwrap().steel_shell().link_to_back(
             &mut heater_bottom_head_bare_shared_ptr.lock().unwrap().steel_shell,
             support_conductance_interaction
         ).unwrap();
 
-        heater_v2_bare_shared_ptr.lock().unwrap().steel_shell.link_to_front(
+        heated_section_shared_ptr.lock().unwrap().steel_shell().link_to_front(
             &mut heater_top_head_bare_shared_ptr.lock().unwrap().steel_shell,
             support_conductance_interaction
         ).unwrap();
 
         // probably edit this to include twisted tape conductance
-        heater_v2_bare_shared_ptr.lock().unwrap().twisted_tape_interior.link_to_back(
-            &mut heater_bottom_head_bare_shared_ptr.lock().unwrap().twisted_tape_interior,
-            support_conductance_interaction
-        ).unwrap();
+        // (only heater version 2 has a twisted tape)
+        if let Some(twisted_tape_interior) = heated_section_shared_ptr
+            .lock().unwrap().twisted_tape_interior() {
+            twisted_tape_interior.link_to_back(
+                &mut heater_bottom_head_bare_shared_ptr.lock().unwrap().twisted_tape_interior,
+                support_conductance_interaction
+            ).unwrap();
 
-        heater_v2_bare_shared_ptr.lock().unwrap().twisted_tape_interior.link_to_front(
-            &mut heater_top_head_bare_shared_ptr.lock().unwrap().twisted_tape_interior,
-            support_conductance_interaction
-        ).unwrap();
+            twisted_tape_interior.link_to_front(
+                &mut heater_top_head_bare_shared_ptr.lock().unwrap().twisted_tape_interior,
+                support_conductance_interaction
+            ).unwrap();
+        }
 
         // now link it laterally to ambient temperatures
         structural_support_heater_top_head_shared_ptr.lock().unwrap().
@@ -858,7 +2751,7 @@ pub fn construct_and_run_ciet_server(run_server: bool){
             lateral_and_miscellaneous_connections();
 
         // advance timesteps
-        heater_v2_bare_shared_ptr.lock().unwrap().
+        heated_section_shared_ptr.lock().unwrap().
             advance_timestep(timestep);
 
         heater_bottom_head_bare_shared_ptr.lock().unwrap(). 
@@ -874,6 +2767,26 @@ pub fn construct_and_run_ciet_server(run_server: bool){
         static_mixer_mx_10_pipe_shared_ptr.lock().unwrap().advance_timestep(
             timestep);
 
+        ctah_shared_ptr.lock().unwrap().advance_timestep(
+            timestep);
+
+        dhx_shared_ptr.lock().unwrap().advance_timestep(
+            timestep);
+
+        tchx_shared_ptr.lock().unwrap().advance_timestep(
+            timestep);
+
+        for pipe_chain_shared_ptr in [
+            &heater_lower_pipes_shared_ptr,
+            &heater_upper_pipes_shared_ptr,
+            &ctah_upper_pipes_shared_ptr,
+            &ctah_lower_pipes_shared_ptr,
+            &dhx_upper_pipes_shared_ptr,
+            &dhx_lower_pipes_shared_ptr] {
+            pipe_chain_shared_ptr.lock().unwrap().advance_timestep(
+                timestep);
+        }
+
 
         structural_support_heater_bottom_head_shared_ptr.lock().unwrap().
             advance_timestep(timestep);
@@ -910,7 +2823,7 @@ pub fn construct_and_run_ciet_server(run_server: bool){
 
     
     server.add_polling_action(
-        timestep.get::<uom::si::time::millisecond>().round() as u64, 
+        server_config.heater_timestep_ms, 
         ciet_heater_loop);
 
 
@@ -918,53 +2831,518 @@ pub fn construct_and_run_ciet_server(run_server: bool){
 
 }
 
-const CUSTOM_ENDPOINT_PATH: &str = "/rust_ciet_opcua_server";
-fn build_standard_server() -> Server {
+/// node ids for the pressure breakdown of a single component
+struct PressureBreakdownNodes {
+    friction_loss: NodeId,
+    form_loss: NodeId,
+    hydrostatic_pressure_change: NodeId,
+    internal_pressure_source: NodeId,
+}
+
+/// adds a folder for the branch under the parent folder, and 
+/// within it, a folder of pressure breakdown variables for each 
+/// component in the branch
+fn add_pressure_breakdown_variables(
+    address_space: &mut AddressSpace,
+    ns: u16,
+    parent_folder_id: &NodeId,
+    branch_name: &str,
+    pressure_breakdown: &[ComponentPressureBreakdown]) -> Vec<PressureBreakdownNodes> {
+
+    let branch_folder_id = address_space
+        .add_folder(branch_name, branch_name, parent_folder_id)
+        .unwrap();
+
+    let mut breakdown_nodes: Vec<PressureBreakdownNodes> = vec![];
+
+    for component_breakdown in pressure_breakdown.iter() {
+
+        let component_name = &component_breakdown.name;
+
+        let component_folder_id = address_space
+            .add_folder(component_name.as_str(), component_name.as_str(), 
+                &branch_folder_id)
+            .unwrap();
+
+        let component_nodes = PressureBreakdownNodes {
+            friction_loss: NodeId::new(ns, 
+                format!("{}_friction_loss_pa", component_name)),
+            form_loss: NodeId::new(ns, 
+                format!("{}_form_loss_pa", component_name)),
+            hydrostatic_pressure_change: NodeId::new(ns, 
+                format!("{}_hydrostatic_pressure_change_pa", component_name)),
+            internal_pressure_source: NodeId::new(ns, 
+                format!("{}_internal_pressure_source_pa", component_name)),
+        };
+
+        let _ = address_space.add_variables(
+            vec![
+                Variable::new(&component_nodes.friction_loss, 
+                    "friction_loss_pa", "friction_loss_pa", 0 as f64),
+                Variable::new(&component_nodes.form_loss, 
+                    "form_loss_pa", "form_loss_pa", 0 as f64),
+                Variable::new(&component_nodes.hydrostatic_pressure_change, 
+                    "hydrostatic_pressure_change_pa", 
+                    "hydrostatic_pressure_change_pa", 0 as f64),
+                Variable::new(&component_nodes.internal_pressure_source, 
+                    "internal_pressure_source_pa", 
+                    "internal_pressure_source_pa", 0 as f64),
+            ],
+            &component_folder_id,
+        );
+
+        breakdown_nodes.push(component_nodes);
+    }
+
+    return breakdown_nodes;
+}
+
+/// writes the pressure breakdown of each component in a branch
+/// into its nodes
+fn set_pressure_breakdown_values(
+    address_space: &mut AddressSpace,
+    breakdown_nodes: &[PressureBreakdownNodes],
+    pressure_breakdown: &[ComponentPressureBreakdown]){
+
+    let now = DateTime::now();
+
+    for (component_nodes, component_breakdown) in 
+        breakdown_nodes.iter().zip(pressure_breakdown.iter()) {
+
+        let values = [
+            (&component_nodes.friction_loss, 
+             component_breakdown.friction_loss),
+            (&component_nodes.form_loss, 
+             component_breakdown.form_loss),
+            (&component_nodes.hydrostatic_pressure_change, 
+             component_breakdown.hydrostatic_pressure_change),
+            (&component_nodes.internal_pressure_source, 
+             component_breakdown.internal_pressure_source),
+        ];
+
+        for (node, pressure) in values {
+            let _ = address_space.set_variable_value(
+                node.clone(), 
+                pressure.get::<pascal>(),
+                &now, 
+                &now);
+        }
+    }
+}
+
+/// adds a branch object and its component objects to the CIET 
+/// object model, the components reference the pressure breakdown 
+/// variables already in the hydraulics breakdown folder
+///
+/// the branch valve is listed last and has no fluid temperature
+fn add_branch_object_model(
+    address_space: &mut AddressSpace,
+    object_types: &CIETObjectTypes,
+    facility_object: &NodeId,
+    branch_name: &str,
+    breakdown_nodes: &[PressureBreakdownNodes],
+    pressure_breakdown: &[ComponentPressureBreakdown]
+    ) -> (CIETBranchObjectNodes, Vec<CIETComponentObjectNodes>) {
+
+    let branch_nodes = object_model::add_ciet_branch_object(
+        address_space, object_types, facility_object, branch_name);
+
+    let valve_index = pressure_breakdown.len().saturating_sub(1);
+
+    let component_nodes = breakdown_nodes.iter()
+        .zip(pressure_breakdown.iter())
+        .enumerate()
+        .map(|(index, (component_breakdown_nodes, component_breakdown))| {
+            object_model::add_ciet_component_object(
+                address_space, object_types, &branch_nodes.branch_object,
+                &component_breakdown.name,
+                [&component_breakdown_nodes.friction_loss,
+                 &component_breakdown_nodes.form_loss,
+                 &component_breakdown_nodes.hydrostatic_pressure_change,
+                 &component_breakdown_nodes.internal_pressure_source],
+                index != valve_index)
+        })
+        .collect();
+
+    return (branch_nodes, component_nodes);
+}
+
+/// writes the branch flowrate, and the pressure change and fluid
+/// temperature of each component, into the CIET object model
+///
+/// the pressure breakdown variables are shared with the hydraulics
+/// breakdown folder, so set_pressure_breakdown_values already 
+/// updates them
+fn set_branch_object_model_values(
+    address_space: &mut AddressSpace,
+    branch_object_nodes: &(CIETBranchObjectNodes, Vec<CIETComponentObjectNodes>),
+    branch_mass_flowrate_kg_per_s: f64,
+    pressure_breakdown: &[ComponentPressureBreakdown],
+    fluid_temperatures: &[ThermodynamicTemperature]){
+
+    let (branch_nodes, component_nodes) = branch_object_nodes;
+    let now = DateTime::now();
+
+    let _ = address_space.set_variable_value(
+        branch_nodes.mass_flowrate.clone(), 
+        branch_mass_flowrate_kg_per_s,
+        &now, 
+        &now);
+
+    for (component_object_nodes, component_breakdown) in 
+        component_nodes.iter().zip(pressure_breakdown.iter()) {
+
+        let _ = address_space.set_variable_value(
+            component_object_nodes.pressure_change.clone(), 
+            component_breakdown.get_pressure_change().get::<pascal>(),
+            &now, 
+            &now);
+    }
+
+    // the fluid temperatures are listed in the same order as the
+    // components, without the branch valve
+    for (component_object_nodes, fluid_temperature) in 
+        component_nodes.iter().zip(fluid_temperatures.iter()) {
+
+        if let Some(fluid_temperature_node) = 
+            &component_object_nodes.fluid_temperature {
+            let _ = address_space.set_variable_value(
+                fluid_temperature_node.clone(), 
+                fluid_temperature.get::<degree_celsius>(),
+                &now, 
+                &now);
+        }
+    }
+}
+
+/// opcua method which trips (or resets the trip of) the ctah pump
+///
+/// it takes no arguments, it only sets the trip request which the
+/// hydraulics thread acts on in its next calculation
+///
+/// pump trips are faults, so only instructors may call it
+struct CTAHPumpTripMethod {
+    pump_trip_requested: Arc<AtomicBool>,
+    // true for trip_pump, false for reset_pump_trip
+    trip: bool,
+    user_roles: Arc<UserRoles>,
+}
+
+impl opcua::server::callbacks::Method for CTAHPumpTripMethod {
+    fn call(
+        &mut self,
+        session_id: &NodeId,
+        session_manager: Arc<opcua::sync::RwLock<SessionManager>>,
+        _request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+
+        let role: UserRole = self.user_roles
+            .get_session_role(session_id, session_manager);
+
+        if !role.can_inject_faults() {
+            return Err(StatusCode::BadUserAccessDenied);
+        }
+
+        self.pump_trip_requested.store(self.trip, Ordering::SeqCst);
+
+        return Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: None,
+            input_argument_diagnostic_infos: None,
+            output_arguments: None,
+        });
+    }
+}
+
+/// opcua method which puts the simulation back to its initial
+/// state, reset_simulation()
+///
+/// it only sets the reset requests which the heater loop and
+/// the hydraulics act on in their next timestep, only 
+/// instructors may call it
+struct ResetSimulationMethod {
+    simulation_reset_requested: Arc<AtomicBool>,
+    hydraulics_reset_requested: Arc<AtomicBool>,
+    user_roles: Arc<UserRoles>,
+}
+
+impl opcua::server::callbacks::Method for ResetSimulationMethod {
+    fn call(
+        &mut self,
+        session_id: &NodeId,
+        session_manager: Arc<opcua::sync::RwLock<SessionManager>>,
+        _request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+
+        let role: UserRole = self.user_roles
+            .get_session_role(session_id, session_manager);
+
+        if !role.can_inject_faults() {
+            return Err(StatusCode::BadUserAccessDenied);
+        }
+
+        self.simulation_reset_requested.store(true, Ordering::SeqCst);
+        self.hydraulics_reset_requested.store(true, Ordering::SeqCst);
+
+        return Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: None,
+            input_argument_diagnostic_infos: None,
+            output_arguments: None,
+        });
+    }
+}
+
+/// kind of value a controller node takes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControllerValueKind {
+    Number,
+    Boolean,
+    NumberArray,
+}
+
+/// a controller node, with the kind of value it takes
+#[derive(Debug, Clone, PartialEq)]
+struct ControllerNode {
+    node: NodeId,
+    kind: ControllerValueKind,
+    // fault nodes are only written by instructors
+    fault_node: bool,
+    // units and ranges of numeric nodes
+    analog_item: Option<AnalogItem>,
+}
+
+impl ControllerNode {
+
+    /// checks that a value written to the node is of the right 
+    /// kind and, for numbers, within the instrument range of the
+    /// node, numbers are stored as doubles, whichever numeric
+    /// type the client sends
+    fn check_value(&self, value: Variant) -> Result<Variant, StatusCode> {
+        match (self.kind, value) {
+            (ControllerValueKind::Boolean, Variant::Boolean(value)) => 
+                return Ok(Variant::Boolean(value)),
+            (ControllerValueKind::NumberArray, Variant::Array(array)) => {
+                for value in array.values.iter() {
+                    match value.as_f64() {
+                        Some(value) => self.check_range(value)?,
+                        None => return Err(StatusCode::BadTypeMismatch),
+                    }
+                }
+                return Ok(Variant::Array(array));
+            },
+            (ControllerValueKind::Number, value) => match value.as_f64() {
+                Some(value) => {
+                    self.check_range(value)?;
+                    return Ok(Variant::Double(value));
+                },
+                None => return Err(StatusCode::BadTypeMismatch),
+            },
+            _ => return Err(StatusCode::BadTypeMismatch),
+        }
+    }
+
+    /// rejects numbers outside the instrument range of the node,
+    /// including NaN and infinite values
+    fn check_range(&self, value: f64) -> Result<(), StatusCode> {
+        match &self.analog_item {
+            Some(analog_item) if !analog_item.is_in_instrument_range(value) => 
+                return Err(StatusCode::BadOutOfRange),
+            _ => return Ok(()),
+        }
+    }
+}
+
+/// value setter for a controller node, standard writes are 
+/// checked and queued as in write_controller_value
+///
+/// the Write service does not tell which session a write comes
+/// from, so the write is only allowed if the least privileged 
+/// role any session can have may make it
+struct ControllerNodeSetter {
+    controller_node: ControllerNode,
+    pending_controller_writes: Arc<Mutex<Vec<(NodeId, Variant)>>>,
+    least_privileged_role: UserRole,
+}
+
+impl opcua::server::callbacks::AttributeSetter for ControllerNodeSetter {
+    fn set(
+        &mut self,
+        _node_id: &NodeId,
+        _attribute_id: AttributeId,
+        index_range: NumericRange,
+        data_value: DataValue,
+    ) -> Result<(), StatusCode> {
+
+        if !self.least_privileged_role
+            .can_write_node(self.controller_node.fault_node) {
+            return Err(StatusCode::BadUserAccessDenied);
+        }
+
+        if index_range.has_range() {
+            return Err(StatusCode::BadWriteNotSupported);
+        }
+
+        let value: Variant = self.controller_node.check_value(
+            data_value.value.ok_or(StatusCode::BadTypeMismatch)?)?;
+
+        self.pending_controller_writes.lock().unwrap()
+            .push((self.controller_node.node.clone(), value));
+
+        return Ok(());
+    }
+}
+
+/// opcua method which writes a controller node, 
+/// write_controller_value(node_name, value)
+///
+/// only operators and instructors may call it, and only 
+/// instructors may write fault nodes, the value is checked 
+/// against the kind of node and then queued, to be set in the 
+/// address space by a polling action
+struct WriteControllerValueMethod {
+    controller_nodes: HashMap<String, ControllerNode>,
+    pending_controller_writes: Arc<Mutex<Vec<(NodeId, Variant)>>>,
+    user_roles: Arc<UserRoles>,
+}
+
+impl opcua::server::callbacks::Method for WriteControllerValueMethod {
+    fn call(
+        &mut self,
+        session_id: &NodeId,
+        session_manager: Arc<opcua::sync::RwLock<SessionManager>>,
+        request: &CallMethodRequest,
+    ) -> Result<CallMethodResult, StatusCode> {
+
+        let role: UserRole = self.user_roles
+            .get_session_role(session_id, session_manager);
+
+        if !role.can_write_controller_nodes() {
+            return Err(StatusCode::BadUserAccessDenied);
+        }
+
+        let (node_name, value) = match request.input_arguments.as_deref() {
+            Some([Variant::String(node_name), value]) => 
+                (node_name.as_ref().to_string(), value.clone()),
+            Some([_, _]) => return Err(StatusCode::BadInvalidArgument),
+            Some(arguments) if arguments.len() > 2 => 
+                return Err(StatusCode::BadTooManyArguments),
+            _ => return Err(StatusCode::BadArgumentsMissing),
+        };
+
+        let controller_node = self.controller_nodes.get(&node_name)
+            .ok_or(StatusCode::BadNodeIdUnknown)?;
+
+        if !role.can_write_node(controller_node.fault_node) {
+            return Err(StatusCode::BadUserAccessDenied);
+        }
+
+        let value: Variant = controller_node.check_value(value)?;
+
+        self.pending_controller_writes.lock().unwrap()
+            .push((controller_node.node.clone(), value));
+
+        return Ok(CallMethodResult {
+            status_code: StatusCode::Good,
+            input_argument_results: None,
+            input_argument_diagnostic_infos: None,
+            output_arguments: None,
+        });
+    }
+}
+
+/// returns a function which puts the component behind the 
+/// shared pointer back to the state it is in now
+fn get_reset_function<T: Clone + Send + Sync + 'static>(
+    shared_ptr: &Arc<Mutex<T>>) -> Box<dyn Fn() + Send + Sync> {
+
+    let initial_state: T = shared_ptr.lock().unwrap().clone();
+    let shared_ptr = shared_ptr.clone();
+
+    return Box::new(move || {
+        *shared_ptr.lock().unwrap() = initial_state.clone();
+    });
+}
+
+fn build_standard_server(server_config: &CIETServerConfig) -> Server {
 
     let server_builder = ServerBuilder::new();
 
     let server_builder = 
-        server_builder.application_name("test server_builder");
+        server_builder.application_name(&server_config.application_name);
 
     let server_builder =
-        server_builder.application_uri("urn:OPC UA Sample Server");
+        server_builder.application_uri(&server_config.application_uri);
 
 
 
 
-    let ip_address = get_ip_as_str();
+    let ip_address = server_config.get_bind_address();
 
     let server_builder = 
-        server_builder.host_and_port(&ip_address, 4840);
+        server_builder.host_and_port(&ip_address, server_config.port);
 
 
     let server_builder =
         server_builder.discovery_urls(
             vec![
-            CUSTOM_ENDPOINT_PATH.into(),
+            server_config.endpoint_path.clone(),
             ]);
 
 
-    // username and password is just anonymous
+    // the server key pair is read from the pki directory, 
+    // client certificates are only accepted once they are in 
+    // the trusted folder (unless trust_client_certs is set)
 
-    let user_id_anonymous = config::ANONYMOUS_USER_TOKEN_ID;
+    let security = &server_config.security;
 
+    let server_builder = server_builder
+        .pki_dir(security.pki_dir.clone())
+        .create_sample_keypair(security.create_sample_keypair);
 
-    let user_id_vector = 
-        vec![user_id_anonymous]
-        .iter()
-        .map(|u| u.to_string())
-        .collect::<Vec<String>>();
+    let server_builder = if security.trust_client_certs {
+        server_builder.trust_client_certs()
+    } else {
+        server_builder
+    };
 
+    // users log in anonymously (if allowed), with a username 
+    // and password, or with an X.509 certificate
 
+    let mut server_builder = server_builder;
 
+    for user in security.users.iter() {
+        let user_token = match (&user.password, &user.x509_certificate) {
+            (Some(password), _) => 
+                ServerUserToken::user_pass(user.name.as_str(), password.as_str()),
+            (None, Some(x509_certificate)) => 
+                ServerUserToken::x509(user.name.as_str(), x509_certificate),
+            (None, None) => unreachable!("validated in server config"),
+        };
+        server_builder = server_builder.user_token(user.name.as_str(), user_token);
+    }
 
-    let path = CUSTOM_ENDPOINT_PATH;
+    // the endpoint without security only takes anonymous 
+    // sessions, see get_user_token_ids
 
+    let path = server_config.endpoint_path.as_str();
 
-    let my_endpoints = vec![
-        ("custom_path", ServerEndpoint::new_none(path,&user_id_vector)),
-    ];
+
+    let my_endpoints: Vec<(&str, ServerEndpoint)> = security.endpoints
+        .iter()
+        .map(|endpoint_security| {
+            let user_id_vector = security.get_user_token_ids(*endpoint_security);
+            let endpoint = match endpoint_security {
+                EndpointSecurity::None => 
+                    ServerEndpoint::new_none(path, &user_id_vector),
+                EndpointSecurity::Basic256Sha256Sign => 
+                    ServerEndpoint::new_basic256sha256_sign(path, &user_id_vector),
+                EndpointSecurity::Basic256Sha256SignAndEncrypt => 
+                    ServerEndpoint::new_basic256sha256_sign_encrypt(path, &user_id_vector),
+            };
+            (endpoint_security.endpoint_id(), endpoint)
+        })
+        .collect();
 
 
     let server_builder = 
@@ -977,17 +3355,19 @@ fn build_standard_server() -> Server {
 
 }
 
-fn get_ip_as_str() -> String {
-
-    let my_local_ip = local_ip().unwrap();
-
-    // i can convert it to a string
-
-    let ip_add_string : String = my_local_ip.to_string();
-
-    return ip_add_string;
-
+/// puts the temperatures of the heater, CTAH or DHX thermal 
+/// models, given with the names of the components they model, 
+/// in the order those components appear in their branch
+///
+/// a component without a temperature is left out, the 
+/// hydraulics then report the temperatures as mismatched
+fn order_as_in_branch(section_names: &[String],
+    model_temperatures: &[(&String, ThermodynamicTemperature)]) 
+    -> Vec<ThermodynamicTemperature> {
+
+    return section_names.iter()
+        .filter_map(|section_name| model_temperatures.iter()
+            .find(|(component_name, _)| *component_name == section_name)
+            .map(|(_, fluid_temp)| *fluid_temp))
+        .collect();
 }
-
-
-
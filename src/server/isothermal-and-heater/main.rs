@@ -5,6 +5,19 @@ pub use ciet_libraries::*;
 pub mod examples;
 pub use examples::ciet_server_old_with_deviation;
 
+pub mod server_config;
+use server_config::CIETServerConfig;
+
+pub mod user_roles;
+
+pub mod object_model;
+
+pub mod engineering_units;
+
+use heater::{HeaterVersionSelection, AirHeatTransferModel, AxialPowerShape};
+use uom::si::f64::Ratio;
+use uom::si::ratio::ratio;
+
 /// The server code here is constructed using the thermal hydraulics 
 /// library
 /// The thermal_hydraulics_rs library was constructed with CIET 
@@ -77,8 +90,118 @@ pub use examples::ciet_server_old_with_deviation;
 /// Professor Per F. Peterson
 ///
 /// Btw, I no affiliation with the Rust Foundation. 
+///
+/// The heated section model is selected at startup with
+/// --heater <v2_bare|v2_insulated|v1_bare|v1_insulated>, 
+/// heater version 2 bare is used by default
+///
+/// The heat transfer to air is selected with 
+/// --air-heat-transfer <callibrated|natural_convection>, 
+/// and the surface emissivity for natural_convection with 
+/// --emissivity <value>. The callibrated heat transfer 
+/// coefficients are used by default
+///
+/// The initial heater axial power shape is selected with 
+/// --axial-power-shape <uniform|cosine|chopped_cosine>, 
+/// uniform is used by default
+///
+/// With --point-kinetics, the heater power is computed by 
+/// point reactor kinetics from the start
+///
+/// The bind address, port, endpoint path, application and namespace 
+/// URIs and polling periods are read from a TOML file given with 
+/// --config <path>, see ciet_server_config.toml for an example
+///
+/// The loop definition the hydraulics and thermal pipe chains are 
+/// built from is read from --loop-definition <path> (or the 
+/// loop_definition setting of the config file), the CIET loop as 
+/// built is used otherwise
+///
+/// Client certificates the server did not trust are placed in the 
+/// rejected folder of the pki directory, 
+/// --trust-certificate <file name> moves one of them into the 
+/// trusted folder and exits
 fn main() {
     let run_server = true;
-    ciet_server_old_with_deviation::construct_and_run_ciet_server(run_server);
+
+    let args: Vec<String> = std::env::args().collect();
+
+    let mut server_config: CIETServerConfig = 
+        match get_arg_value(&args, "--config") {
+            Some(path) => CIETServerConfig::from_file(&path)
+                .unwrap_or_else(|error| panic!("{}", error)),
+            None => CIETServerConfig::default(),
+        };
+
+    if let Some(path) = get_arg_value(&args, "--loop-definition") {
+        server_config.loop_definition = Some(path.into());
+    }
+
+    if let Some(certificate_name) = get_arg_value(&args, "--trust-certificate") {
+        match server_config.security.trust_rejected_certificate(&certificate_name) {
+            Ok(trusted_path) => println!("trusted {}", trusted_path.display()),
+            Err(error) => panic!("{}", error),
+        }
+        return;
+    }
+
+    for certificate_name in server_config.security.list_rejected_certificates() {
+        println!("rejected client certificate {}, trust it with \
+            --trust-certificate {}", certificate_name, certificate_name);
+    }
+
+    let heater_version: HeaterVersionSelection = 
+        match get_arg_value(&args, "--heater") {
+            Some(value) => value.parse().unwrap_or_else(
+                |error: String| panic!("{}", error)),
+            None => HeaterVersionSelection::default(),
+        };
+
+    let mut air_heat_transfer_model: AirHeatTransferModel = 
+        match get_arg_value(&args, "--air-heat-transfer") {
+            Some(value) => value.parse().unwrap_or_else(
+                |error: String| panic!("{}", error)),
+            None => AirHeatTransferModel::default(),
+        };
+
+    if let (Some(value), AirHeatTransferModel::NaturalConvectionAndRadiation { 
+        emissivity }) = (get_arg_value(&args, "--emissivity"), 
+        &mut air_heat_transfer_model) {
+        let emissivity_value: f64 = value.parse().unwrap_or_else(
+            |_| panic!("emissivity must be a number, got {:?}", value));
+        *emissivity = Ratio::new::<ratio>(emissivity_value);
+    }
+
+    let axial_power_shape: AxialPowerShape = 
+        match get_arg_value(&args, "--axial-power-shape") {
+            Some(value) => value.parse().unwrap_or_else(
+                |error: String| panic!("{}", error)),
+            None => AxialPowerShape::default(),
+        };
+
+    let point_kinetics_enabled: bool = 
+        args.iter().any(|arg| arg == "--point-kinetics");
+
+    ciet_server_old_with_deviation::construct_and_run_ciet_server(
+        run_server, &server_config, heater_version, air_heat_transfer_model,
+        axial_power_shape, point_kinetics_enabled);
 }
 
+/// reads the value of a command line argument given either as 
+/// "--flag value" or "--flag=value"
+fn get_arg_value(args: &[String], flag: &str) -> Option<String> {
+
+    let prefix = format!("{}=", flag);
+    let mut args_iter = args.iter();
+
+    while let Some(arg) = args_iter.next() {
+        if arg == flag {
+            return args_iter.next().cloned();
+        }
+        if let Some(value) = arg.strip_prefix(&prefix) {
+            return Some(value.to_string());
+        }
+    }
+
+    None
+}
@@ -0,0 +1,236 @@
+//! object model of CIET in the OPC-UA address space
+//!
+//! besides the flat folders of variables, the facility is
+//! described with object types so that generic OPC-UA browsers
+//! and historians can discover the plant structure:
+//!
+//! CIET (CIETFacilityType)
+//! -> heater_branch, ctah_branch, dhx_branch (CIETBranchType)
+//! -> pipe_4, static_mixer_10_label_2, ... (CIETComponentType)
+//! -> fluid_temperature_degC, pressure_change_pa, friction_loss_pa ...
+//!
+//! every level is linked with HasComponent references, and
+//! each object has a HasTypeDefinition reference to its type
+use opcua::server::prelude::*;
+
+/// browse names of the branch objects within the facility
+pub const HEATER_BRANCH_OBJECT_NAME: &str = "heater_branch";
+pub const CTAH_BRANCH_OBJECT_NAME: &str = "ctah_branch";
+pub const DHX_BRANCH_OBJECT_NAME: &str = "dhx_branch";
+
+/// browse names of the variables in a branch
+pub const BRANCH_MASS_FLOWRATE_NAME: &str = "mass_flowrate_kg_per_s";
+
+/// browse names of the variables in a component, the pressure
+/// breakdown names match those in the hydraulics breakdown folder
+pub const COMPONENT_FLUID_TEMPERATURE_NAME: &str = "fluid_temperature_degC";
+pub const COMPONENT_PRESSURE_CHANGE_NAME: &str = "pressure_change_pa";
+pub const COMPONENT_PRESSURE_BREAKDOWN_NAMES: [&str; 4] = [
+    "friction_loss_pa",
+    "form_loss_pa",
+    "hydrostatic_pressure_change_pa",
+    "internal_pressure_source_pa",
+];
+
+/// node ids of the CIET object types
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETObjectTypes {
+    pub facility_type: NodeId,
+    pub branch_type: NodeId,
+    pub component_type: NodeId,
+}
+
+/// node ids of the variables of a branch object
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETBranchObjectNodes {
+    pub branch_object: NodeId,
+    pub mass_flowrate: NodeId,
+}
+
+/// node ids of the variables of a component object which are
+/// not already in the hydraulics breakdown folder
+#[derive(Debug, Clone, PartialEq)]
+pub struct CIETComponentObjectNodes {
+    pub component_object: NodeId,
+    /// components without a fluid temperature (eg. the branch
+    /// valves) leave this out
+    pub fluid_temperature: Option<NodeId>,
+    pub pressure_change: NodeId,
+}
+
+/// adds the facility, branch and component object types as
+/// subtypes of BaseObjectType, each with the variables its
+/// instances have
+pub fn add_ciet_object_types(address_space: &mut AddressSpace,
+    ns: u16) -> CIETObjectTypes {
+
+    let object_types = CIETObjectTypes {
+        facility_type: NodeId::new(ns, "CIETFacilityType"),
+        branch_type: NodeId::new(ns, "CIETBranchType"),
+        component_type: NodeId::new(ns, "CIETComponentType"),
+    };
+
+    for (type_id, type_name) in [
+        (&object_types.facility_type, "CIETFacilityType"),
+        (&object_types.branch_type, "CIETBranchType"),
+        (&object_types.component_type, "CIETComponentType"),
+    ] {
+        ObjectTypeBuilder::new(type_id, type_name, type_name)
+            .subtype_of(ObjectTypeId::BaseObjectType)
+            .insert(address_space);
+    }
+
+    // the facility always has the three primary loop branches
+    for branch_name in [HEATER_BRANCH_OBJECT_NAME,
+        CTAH_BRANCH_OBJECT_NAME, DHX_BRANCH_OBJECT_NAME] {
+        ObjectBuilder::new(
+            &get_child_node_id(&object_types.facility_type, branch_name),
+            branch_name, branch_name)
+            .component_of(object_types.facility_type.clone())
+            .has_type_definition(object_types.branch_type.clone())
+            .reference(ObjectId::ModellingRule_Mandatory,
+                ReferenceTypeId::HasModellingRule,
+                ReferenceDirection::Forward)
+            .insert(address_space);
+    }
+
+    add_variable_declaration(address_space, &object_types.branch_type,
+        BRANCH_MASS_FLOWRATE_NAME, ObjectId::ModellingRule_Mandatory);
+
+    add_variable_declaration(address_space, &object_types.component_type,
+        COMPONENT_FLUID_TEMPERATURE_NAME, ObjectId::ModellingRule_Optional);
+    add_variable_declaration(address_space, &object_types.component_type,
+        COMPONENT_PRESSURE_CHANGE_NAME, ObjectId::ModellingRule_Mandatory);
+
+    for variable_name in COMPONENT_PRESSURE_BREAKDOWN_NAMES {
+        add_variable_declaration(address_space, &object_types.component_type,
+            variable_name, ObjectId::ModellingRule_Mandatory);
+    }
+
+    return object_types;
+}
+
+/// adds a double variable to an object type
+fn add_variable_declaration(address_space: &mut AddressSpace,
+    type_id: &NodeId,
+    variable_name: &str,
+    modelling_rule: ObjectId) {
+
+    VariableBuilder::new(&get_child_node_id(type_id, variable_name),
+        variable_name, variable_name)
+        .data_type(DataTypeId::Double)
+        .value(0 as f64)
+        .component_of(type_id.clone())
+        .has_type_definition(VariableTypeId::BaseDataVariableType)
+        .has_modelling_rule(modelling_rule)
+        .insert(address_space);
+}
+
+/// adds the CIET facility object under the objects folder
+pub fn add_ciet_facility_object(address_space: &mut AddressSpace,
+    ns: u16,
+    object_types: &CIETObjectTypes) -> NodeId {
+
+    let facility_object = NodeId::new(ns, "CIET");
+
+    ObjectBuilder::new(&facility_object, "CIET", "CIET")
+        .organized_by(ObjectId::ObjectsFolder)
+        .has_type_definition(object_types.facility_type.clone())
+        .insert(address_space);
+
+    return facility_object;
+}
+
+/// adds a branch object to the facility
+pub fn add_ciet_branch_object(address_space: &mut AddressSpace,
+    object_types: &CIETObjectTypes,
+    facility_object: &NodeId,
+    branch_name: &str) -> CIETBranchObjectNodes {
+
+    let branch_object = get_child_node_id(facility_object, branch_name);
+
+    ObjectBuilder::new(&branch_object, branch_name, branch_name)
+        .component_of(facility_object.clone())
+        .has_type_definition(object_types.branch_type.clone())
+        .insert(address_space);
+
+    let mass_flowrate = add_object_variable(address_space,
+        &branch_object, BRANCH_MASS_FLOWRATE_NAME, 0 as f64);
+
+    return CIETBranchObjectNodes {
+        branch_object,
+        mass_flowrate,
+    };
+}
+
+/// adds a component object to a branch
+///
+/// the pressure breakdown variables already exist in the
+/// hydraulics breakdown folder, so the component references
+/// them rather than making copies
+pub fn add_ciet_component_object(address_space: &mut AddressSpace,
+    object_types: &CIETObjectTypes,
+    branch_object: &NodeId,
+    component_name: &str,
+    pressure_breakdown_variables: [&NodeId; 4],
+    has_fluid_temperature: bool) -> CIETComponentObjectNodes {
+
+    let component_object = get_child_node_id(branch_object, component_name);
+
+    ObjectBuilder::new(&component_object, component_name, component_name)
+        .component_of(branch_object.clone())
+        .has_type_definition(object_types.component_type.clone())
+        .insert(address_space);
+
+    let fluid_temperature = if has_fluid_temperature {
+        Some(add_object_variable(address_space, &component_object,
+            COMPONENT_FLUID_TEMPERATURE_NAME, 79.12 as f64))
+    } else {
+        None
+    };
+
+    let pressure_change = add_object_variable(address_space,
+        &component_object, COMPONENT_PRESSURE_CHANGE_NAME, 0 as f64);
+
+    for variable in pressure_breakdown_variables {
+        address_space.insert_reference(&component_object, variable,
+            ReferenceTypeId::HasComponent);
+    }
+
+    return CIETComponentObjectNodes {
+        component_object,
+        fluid_temperature,
+        pressure_change,
+    };
+}
+
+/// adds a read only double variable to an object
+fn add_object_variable(address_space: &mut AddressSpace,
+    object: &NodeId,
+    variable_name: &str,
+    initial_value: f64) -> NodeId {
+
+    let variable = get_child_node_id(object, variable_name);
+
+    VariableBuilder::new(&variable, variable_name, variable_name)
+        .data_type(DataTypeId::Double)
+        .value(initial_value)
+        .component_of(object.clone())
+        .has_type_definition(VariableTypeId::BaseDataVariableType)
+        .insert(address_space);
+
+    return variable;
+}
+
+/// node id of a child node, which is the parent node id followed
+/// by the child browse name, eg. CIET.heater_branch.pipe_4
+pub fn get_child_node_id(parent: &NodeId, child_name: &str) -> NodeId {
+
+    let parent_name = match &parent.identifier {
+        Identifier::String(parent_name) => parent_name.as_ref().to_string(),
+        parent_identifier => parent_identifier.to_string(),
+    };
+
+    return NodeId::new(parent.namespace,
+        format!("{}.{}", parent_name, child_name));
+}
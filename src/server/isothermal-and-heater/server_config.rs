@@ -0,0 +1,531 @@
+//! configuration of the CIET OPC-UA server
+//!
+//! by default, the server binds to the local ip address on port
+//! 4840 with the endpoint path /rust_ciet_opcua_server. When
+//! several servers share one machine, these (along with the
+//! application and namespace URIs and the polling periods) can
+//! be read from a TOML file given with --config, see
+//! ciet_server_config.toml for an example
+//!
+//! any setting left out of the file keeps its default value
+//!
+//! the loop definition (.toml or .json) the hydraulics and the
+//! thermal pipe chains are built from can be given in the file or
+//! with --loop-definition, the CIET loop as built is used otherwise
+//!
+//! the [security] section sets which endpoints are offered
+//! (no security, or Basic256Sha256 Sign and SignAndEncrypt), the
+//! pki directory holding the server key pair and the trusted and
+//! rejected client certificates, and which user tokens
+//! (anonymous, username and password, or X.509) are accepted,
+//! along with the role (observer, operator or instructor) of each
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use local_ip_address::local_ip;
+use opcua::server::config::ANONYMOUS_USER_TOKEN_ID;
+use serde::{Deserialize, Serialize};
+
+use crate::user_roles::UserRole;
+use crate::loop_definition::{CIETLoopDefinition, LoopDefinitionError};
+
+/// the default OPC-UA port
+pub const DEFAULT_PORT: u16 = 4840;
+
+/// the default endpoint path clients connect to
+pub const DEFAULT_ENDPOINT_PATH: &str = "/rust_ciet_opcua_server";
+
+/// address used if the bind address is not given and the local
+/// ip address cannot be found
+pub const FALLBACK_BIND_ADDRESS: &str = "127.0.0.1";
+
+/// settings for the server endpoint, namespace and polling periods
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CIETServerConfig {
+    /// address the server binds to, eg. 127.0.0.1 for local
+    /// connections only or 0.0.0.0 for all interfaces
+    ///
+    /// if left out, the local ip address of the machine is used
+    pub bind_address: Option<String>,
+    /// port the server listens on
+    pub port: u16,
+    /// endpoint path, eg. /rust_ciet_opcua_server
+    pub endpoint_path: String,
+    /// application name shown to OPC-UA clients
+    pub application_name: String,
+    /// application URI of the server
+    pub application_uri: String,
+    /// namespace URI under which the CIET nodes are registered
+    pub namespace_uri: String,
+    /// how often the endpoint url is printed to the terminal
+    pub endpoint_print_period_ms: u64,
+    /// how often the isothermal hydraulics are recalculated
+    pub hydraulics_calculation_period_ms: u64,
+    /// timestep of the heater loop, the heater loop is polled
+    /// this often so that it runs in real time
+    pub heater_timestep_ms: u64,
+    /// loop definition file (.toml or .json) the hydraulics and
+    /// thermal pipe chains are built from, if left out the CIET
+    /// loop as built is used
+    pub loop_definition: Option<PathBuf>,
+    /// endpoint security, certificates and user tokens
+    pub security: ServerSecurityConfig,
+}
+
+impl Default for CIETServerConfig {
+    fn default() -> Self {
+        return Self {
+            bind_address: None,
+            port: DEFAULT_PORT,
+            endpoint_path: DEFAULT_ENDPOINT_PATH.to_string(),
+            application_name: "test server_builder".to_string(),
+            application_uri: "urn:OPC UA Sample Server".to_string(),
+            namespace_uri: "urn:simple-server".to_string(),
+            endpoint_print_period_ms: 5000,
+            hydraulics_calculation_period_ms: 500,
+            heater_timestep_ms: 15,
+            loop_definition: None,
+            security: ServerSecurityConfig::default(),
+        };
+    }
+}
+
+/// errors from reading the server configuration
+#[derive(Debug)]
+pub enum ServerConfigError {
+    /// the file could not be read
+    Io(std::io::Error),
+    /// the TOML could not be parsed
+    TomlParse(toml::de::Error),
+    /// the file extension is not .toml
+    UnknownFileFormat(String),
+    /// a setting has a value the server cannot run with
+    InvalidSetting(String),
+}
+
+impl fmt::Display for ServerConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ServerConfigError::Io(error) =>
+                write!(f, "could not read server config: {}", error),
+            ServerConfigError::TomlParse(error) =>
+                write!(f, "could not parse server config TOML: {}", error),
+            ServerConfigError::UnknownFileFormat(path) =>
+                write!(f, "server config {} must be a .toml file", path),
+            ServerConfigError::InvalidSetting(message) =>
+                write!(f, "invalid server config: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ServerConfigError {}
+
+impl From<std::io::Error> for ServerConfigError {
+    fn from(error: std::io::Error) -> Self {
+        return ServerConfigError::Io(error);
+    }
+}
+
+impl From<toml::de::Error> for ServerConfigError {
+    fn from(error: toml::de::Error) -> Self {
+        return ServerConfigError::TomlParse(error);
+    }
+}
+
+impl CIETServerConfig {
+
+    /// reads the server config from a TOML string
+    pub fn from_toml_str(toml_str: &str) -> Result<Self, ServerConfigError> {
+        let server_config: Self = toml::from_str(toml_str)?;
+        server_config.validate()?;
+        return Ok(server_config);
+    }
+
+    /// reads the server config from a .toml file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, ServerConfigError> {
+        let path = path.as_ref();
+
+        let is_toml = path.extension()
+            .and_then(|extension| extension.to_str())
+            .map(|extension| extension.eq_ignore_ascii_case("toml"))
+            .unwrap_or(false);
+
+        if !is_toml {
+            return Err(ServerConfigError::UnknownFileFormat(
+                    path.display().to_string()));
+        }
+
+        let toml_str = std::fs::read_to_string(path)?;
+        return Self::from_toml_str(&toml_str);
+    }
+
+    /// checks that the endpoint path starts with a slash and that
+    /// the port and polling periods are non zero
+    pub fn validate(&self) -> Result<(), ServerConfigError> {
+
+        if self.port == 0 {
+            return Err(ServerConfigError::InvalidSetting(
+                    "port must be non zero".to_string()));
+        }
+
+        if !self.endpoint_path.starts_with('/') {
+            return Err(ServerConfigError::InvalidSetting(format!(
+                    "endpoint_path {:?} must start with /",
+                    self.endpoint_path)));
+        }
+
+        let polling_periods = [
+            ("endpoint_print_period_ms", self.endpoint_print_period_ms),
+            ("hydraulics_calculation_period_ms", self.hydraulics_calculation_period_ms),
+            ("heater_timestep_ms", self.heater_timestep_ms),
+        ];
+
+        for (setting_name, period_ms) in polling_periods {
+            if period_ms == 0 {
+                return Err(ServerConfigError::InvalidSetting(format!(
+                        "{} must be non zero", setting_name)));
+            }
+        }
+
+        self.security.validate()?;
+
+        return Ok(());
+    }
+
+    /// reads the loop definition file, or returns the CIET loop
+    /// as built if no file is given
+    pub fn get_loop_definition(&self) -> Result<CIETLoopDefinition, LoopDefinitionError> {
+        match &self.loop_definition {
+            Some(path) => return CIETLoopDefinition::from_file(path),
+            None => return Ok(CIETLoopDefinition::default()),
+        }
+    }
+
+    /// returns the address the server binds to,
+    /// this is the local ip address unless one is given
+    pub fn get_bind_address(&self) -> String {
+        match &self.bind_address {
+            Some(bind_address) => bind_address.clone(),
+            None => get_local_ip_or_fallback(),
+        }
+    }
+
+    /// returns the url clients should connect to
+    ///
+    /// if the server binds to all interfaces (0.0.0.0), the local
+    /// ip address is shown instead
+    pub fn get_endpoint_url(&self) -> String {
+        let bind_address = self.get_bind_address();
+
+        let host = if bind_address == "0.0.0.0" {
+            get_local_ip_or_fallback()
+        } else {
+            bind_address
+        };
+
+        return format!("opc.tcp://{}:{}{}", host, self.port, self.endpoint_path);
+    }
+}
+
+/// returns the local ip address as a string, or 127.0.0.1
+/// if there is none (eg. no network connection)
+fn get_local_ip_or_fallback() -> String {
+    match local_ip() {
+        Ok(my_local_ip) => my_local_ip.to_string(),
+        Err(_) => FALLBACK_BIND_ADDRESS.to_string(),
+    }
+}
+
+/// security policy and message security mode of an endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EndpointSecurity {
+    /// no signing or encryption
+    None,
+    /// messages signed with Basic256Sha256
+    Basic256Sha256Sign,
+    /// messages signed and encrypted with Basic256Sha256
+    Basic256Sha256SignAndEncrypt,
+}
+
+impl EndpointSecurity {
+    /// endpoint id used in the server configuration
+    pub fn endpoint_id(&self) -> &'static str {
+        match self {
+            EndpointSecurity::None => "custom_path",
+            EndpointSecurity::Basic256Sha256Sign => "basic256sha256_sign",
+            EndpointSecurity::Basic256Sha256SignAndEncrypt =>
+                "basic256sha256_sign_encrypt",
+        }
+    }
+}
+
+/// a user allowed to log in to the server, identified either by
+/// a password or by an X.509 certificate
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerUserConfig {
+    /// user name, this is also the user token id
+    pub name: String,
+    /// password for username and password login
+    #[serde(default)]
+    pub password: Option<String>,
+    /// path to the user's X.509 certificate (.der or .pem) for
+    /// certificate login
+    #[serde(default)]
+    pub x509_certificate: Option<PathBuf>,
+    /// observer, operator or instructor, users are observers
+    /// unless given a role
+    #[serde(default)]
+    pub role: UserRole,
+}
+
+/// endpoint security, certificates and user tokens of the server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerSecurityConfig {
+    /// directory with the server key pair (own/cert.der and
+    /// private/private.pem) and the trusted/ and rejected/
+    /// client certificate folders
+    pub pki_dir: PathBuf,
+    /// generate a key pair if none is found in the pki directory
+    pub create_sample_keypair: bool,
+    /// trust every client certificate, rather than only those
+    /// in the trusted folder, only for testing
+    pub trust_client_certs: bool,
+    /// endpoints offered by the server
+    pub endpoints: Vec<EndpointSecurity>,
+    /// accept anonymous sessions
+    pub allow_anonymous: bool,
+    /// role of anonymous sessions, observer by default so that
+    /// only users with a user token drive the simulator
+    pub anonymous_role: UserRole,
+    /// users who may log in with a password or certificate
+    pub users: Vec<ServerUserConfig>,
+}
+
+impl Default for ServerSecurityConfig {
+    fn default() -> Self {
+        return Self {
+            pki_dir: PathBuf::from("pki"),
+            create_sample_keypair: false,
+            trust_client_certs: false,
+            endpoints: vec![
+                EndpointSecurity::None,
+                EndpointSecurity::Basic256Sha256Sign,
+                EndpointSecurity::Basic256Sha256SignAndEncrypt,
+            ],
+            allow_anonymous: true,
+            anonymous_role: UserRole::Observer,
+            users: vec![],
+        };
+    }
+}
+
+impl ServerSecurityConfig {
+
+    /// checks that there is at least one endpoint and one way
+    /// to log in, and that each user has either a password or a
+    /// certificate (but not both)
+    pub fn validate(&self) -> Result<(), ServerConfigError> {
+
+        if self.endpoints.is_empty() {
+            return Err(ServerConfigError::InvalidSetting(
+                    "security.endpoints must not be empty".to_string()));
+        }
+
+        if !self.allow_anonymous && self.users.is_empty() {
+            return Err(ServerConfigError::InvalidSetting(
+                    "anonymous sessions are not allowed and there \
+                    are no users".to_string()));
+        }
+
+        if !self.allow_anonymous && self.endpoints.iter()
+            .all(|endpoint| *endpoint == EndpointSecurity::None) {
+            return Err(ServerConfigError::InvalidSetting(
+                    "anonymous sessions are not allowed and users \
+                    cannot log in on an endpoint without security".to_string()));
+        }
+
+        for (index, user) in self.users.iter().enumerate() {
+
+            if user.name.is_empty() || user.name == ANONYMOUS_USER_TOKEN_ID {
+                return Err(ServerConfigError::InvalidSetting(format!(
+                        "user name {:?} is not allowed", user.name)));
+            }
+
+            if self.users[..index].iter().any(|other| other.name == user.name) {
+                return Err(ServerConfigError::InvalidSetting(format!(
+                        "user {} is listed more than once", user.name)));
+            }
+
+            if user.password.is_some() == user.x509_certificate.is_some() {
+                return Err(ServerConfigError::InvalidSetting(format!(
+                        "user {} needs either a password or an \
+                        x509_certificate", user.name)));
+            }
+        }
+
+        return Ok(());
+    }
+
+    /// user token ids accepted on an endpoint, passwords and 
+    /// certificates would be sent in the clear on an endpoint 
+    /// without security, so it only takes anonymous sessions
+    pub fn get_user_token_ids(&self, 
+        endpoint_security: EndpointSecurity) -> Vec<String> {
+        let mut user_token_ids: Vec<String> = match endpoint_security {
+            EndpointSecurity::None => vec![],
+            _ => self.users.iter()
+                .map(|user| user.name.clone())
+                .collect(),
+        };
+
+        if self.allow_anonymous {
+            user_token_ids.insert(0, ANONYMOUS_USER_TOKEN_ID.to_string());
+        }
+
+        return user_token_ids;
+    }
+
+    /// folder holding client certificates which were rejected,
+    /// an operator trusts a client by moving its certificate from
+    /// here to the trusted folder
+    pub fn rejected_certificates_dir(&self) -> PathBuf {
+        return self.pki_dir.join("rejected");
+    }
+
+    /// folder holding trusted client certificates
+    pub fn trusted_certificates_dir(&self) -> PathBuf {
+        return self.pki_dir.join("trusted");
+    }
+
+    /// file names of the client certificates in the rejected folder
+    pub fn list_rejected_certificates(&self) -> Vec<String> {
+        let entries = match std::fs::read_dir(self.rejected_certificates_dir()) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut certificate_names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .map(|entry| entry.file_name().to_string_lossy().to_string())
+            .collect();
+
+        certificate_names.sort();
+        return certificate_names;
+    }
+
+    /// moves a rejected client certificate into the trusted folder,
+    /// so that the client may connect
+    pub fn trust_rejected_certificate(&self, 
+        certificate_name: &str) -> Result<PathBuf, ServerConfigError> {
+
+        // only plain file names, so nothing outside the rejected
+        // folder can be moved
+        let is_plain_file_name = Path::new(certificate_name)
+            .file_name()
+            .map(|file_name| file_name == certificate_name)
+            .unwrap_or(false);
+
+        if !is_plain_file_name {
+            return Err(ServerConfigError::InvalidSetting(format!(
+                    "{:?} is not a certificate file name", certificate_name)));
+        }
+
+        let trusted_dir = self.trusted_certificates_dir();
+        std::fs::create_dir_all(&trusted_dir)?;
+
+        let trusted_path = trusted_dir.join(certificate_name);
+        std::fs::rename(
+            self.rejected_certificates_dir().join(certificate_name),
+            &trusted_path)?;
+
+        return Ok(trusted_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn security_from_toml(toml_str: &str) -> ServerSecurityConfig {
+        return toml::from_str(toml_str)
+            .expect("test security config should parse");
+    }
+
+    #[test]
+    fn default_config_is_valid() {
+        assert!(CIETServerConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn unusable_security_settings_are_rejected() {
+        let invalid_security_configs = [
+            // no endpoints
+            "endpoints = []",
+            // nobody can log in
+            "allow_anonymous = false",
+            // users could only log in without security
+            r#"allow_anonymous = false
+            endpoints = ["none"]
+            users = [{ name = "operator", password = "pw" }]"#,
+            // neither a password nor a certificate
+            r#"users = [{ name = "operator" }]"#,
+            // both a password and a certificate
+            r#"[[users]]
+            name = "operator"
+            password = "pw"
+            x509_certificate = "pki/operator.der""#,
+            // listed twice
+            r#"users = [{ name = "operator", password = "pw" },
+                { name = "operator", password = "other" }]"#,
+            // clashes with the anonymous user token id
+            r#"users = [{ name = "ANONYMOUS", password = "pw" }]"#,
+        ];
+
+        for toml_str in invalid_security_configs {
+            assert!(matches!(security_from_toml(toml_str).validate(),
+                Err(ServerConfigError::InvalidSetting(_))), "{}", toml_str);
+        }
+    }
+
+    #[test]
+    fn users_with_a_password_or_certificate_are_valid() {
+        let security = security_from_toml(r#"
+            allow_anonymous = false
+            users = [{ name = "operator", password = "pw" },
+                { name = "instructor", x509_certificate = "pki/instructor.der" }]
+            "#);
+
+        assert!(security.validate().is_ok());
+    }
+
+    #[test]
+    fn users_only_log_in_on_secure_endpoints() {
+        let security = security_from_toml(r#"
+            users = [{ name = "operator", password = "pw" }]
+            "#);
+
+        assert_eq!(security.get_user_token_ids(EndpointSecurity::None),
+            vec![ANONYMOUS_USER_TOKEN_ID.to_string()]);
+        assert_eq!(security.get_user_token_ids(
+                EndpointSecurity::Basic256Sha256SignAndEncrypt),
+            vec![ANONYMOUS_USER_TOKEN_ID.to_string(), "operator".to_string()]);
+    }
+
+    #[test]
+    fn anonymous_token_is_left_out_when_not_allowed() {
+        let security = security_from_toml(r#"
+            allow_anonymous = false
+            users = [{ name = "operator", password = "pw" }]
+            "#);
+
+        assert!(security.get_user_token_ids(EndpointSecurity::None).is_empty());
+        assert_eq!(security.get_user_token_ids(
+                EndpointSecurity::Basic256Sha256Sign),
+            vec!["operator".to_string()]);
+    }
+}
@@ -0,0 +1,238 @@
+//! user roles for the CIET OPC-UA server
+//!
+//! each user token maps to a role:
+//!
+//! observer: read only
+//! operator: may also write the controller nodes (pump pressure
+//! and speed, valves, heater power, fan speeds)
+//! instructor: may also inject faults (trip and reset the ctah
+//! pump, scram, external reactivity, point kinetics and the 
+//! heater axial power profile) and reset the simulation
+//!
+//! anonymous sessions are observers unless configured otherwise,
+//! so operators log in with a user token
+//!
+//! the opcua server we use does not tell the Write service which
+//! session a write comes from, so the standard Write service is
+//! gated by the least privileged role any session can have, eg.
+//! with anonymous observers, only the write_controller_value 
+//! method in the Controller folder, whose caller can be 
+//! identified, writes the controller nodes
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use opcua::crypto::CertificateStore;
+use opcua::server::prelude::*;
+use opcua::server::session::SessionManager;
+use serde::{Deserialize, Serialize};
+
+use crate::server_config::{ServerSecurityConfig, ServerConfigError};
+
+/// what a user may do on the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+    Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UserRole {
+    /// may only read
+    #[default]
+    Observer,
+    /// may write the controller nodes
+    Operator,
+    /// may write the controller nodes and inject faults
+    Instructor,
+}
+
+impl UserRole {
+    pub fn can_write_controller_nodes(&self) -> bool {
+        return *self >= UserRole::Operator;
+    }
+
+    pub fn can_inject_faults(&self) -> bool {
+        return *self == UserRole::Instructor;
+    }
+
+    /// whether a session with this role may write a controller 
+    /// node, fault nodes are written by instructors only
+    pub fn can_write_node(&self, fault_node: bool) -> bool {
+        match fault_node {
+            true => return self.can_inject_faults(),
+            false => return self.can_write_controller_nodes(),
+        }
+    }
+}
+
+/// looks up the role of the user behind a session
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserRoles {
+    anonymous_role: UserRole,
+    roles_by_user_name: HashMap<String, UserRole>,
+    // X.509 user tokens are identified by their certificate
+    // subject name
+    roles_by_certificate_subject: HashMap<String, UserRole>,
+    // the role of the least privileged user or of anonymous 
+    // sessions, whichever is lower
+    least_privileged_role: UserRole,
+}
+
+impl UserRoles {
+
+    /// reads the roles of the configured users, the certificates
+    /// of X.509 users are read to find their subject names
+    ///
+    /// returns an error if a user certificate cannot be read, 
+    /// as that user could never log in
+    pub fn new(security: &ServerSecurityConfig) -> 
+        Result<Self, ServerConfigError> {
+
+        let mut roles_by_user_name = HashMap::new();
+        let mut roles_by_certificate_subject = HashMap::new();
+
+        for user in security.users.iter() {
+            match &user.x509_certificate {
+                Some(certificate_path) => {
+                    let certificate = CertificateStore::read_cert(certificate_path)
+                        .map_err(|error| ServerConfigError::InvalidSetting(format!(
+                            "could not read certificate of user {}: {}",
+                            user.name, error)))?;
+                    roles_by_certificate_subject.insert(
+                        certificate.subject_name(), user.role);
+                },
+                None => {
+                    roles_by_user_name.insert(user.name.clone(), user.role);
+                },
+            }
+        }
+
+        let least_privileged_role: UserRole = security.users.iter()
+            .map(|user| user.role)
+            .chain(security.allow_anonymous.then_some(security.anonymous_role))
+            .min()
+            .unwrap_or(UserRole::Observer);
+
+        return Ok(Self {
+            anonymous_role: security.anonymous_role,
+            roles_by_user_name,
+            roles_by_certificate_subject,
+            least_privileged_role,
+        });
+    }
+
+    /// returns the role of the user logged in to the session,
+    /// unknown sessions are observers
+    pub fn get_session_role(&self, session_id: &NodeId,
+        session_manager: Arc<opcua::sync::RwLock<SessionManager>>) -> UserRole {
+
+        let session = match session_manager.read().find_session_by_id(session_id) {
+            Some(session) => session,
+            None => return UserRole::Observer,
+        };
+
+        let client_user_id = session.read().client_user_id();
+
+        if client_user_id.is_null() {
+            return self.anonymous_role;
+        }
+
+        let client_user_id = client_user_id.as_ref();
+
+        return self.roles_by_user_name.get(client_user_id)
+            .or_else(|| self.roles_by_certificate_subject.get(client_user_id))
+            .copied()
+            .unwrap_or(UserRole::Observer);
+    }
+
+    /// returns the lowest role any session can have, this gates 
+    /// the standard Write service, which does not tell which 
+    /// session a write comes from
+    pub fn get_least_privileged_role(&self) -> UserRole {
+        return self.least_privileged_role;
+    }
+
+    /// user access level of a controller node for the standard
+    /// Write service, writable only if every session may write it
+    pub fn get_user_access_level(&self, fault_node: bool) -> UserAccessLevel {
+        match self.least_privileged_role.can_write_node(fault_node) {
+            true => return UserAccessLevel::CURRENT_READ 
+                | UserAccessLevel::CURRENT_WRITE,
+            false => return UserAccessLevel::CURRENT_READ,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_roles_from_toml(toml_str: &str) -> UserRoles {
+        let security: ServerSecurityConfig = toml::from_str(toml_str)
+            .expect("test security config should parse");
+
+        return UserRoles::new(&security)
+            .expect("password users need no certificates to be read");
+    }
+
+    #[test]
+    fn roles_are_ordered_by_privilege() {
+        assert!(UserRole::Observer < UserRole::Operator);
+        assert!(UserRole::Operator < UserRole::Instructor);
+        assert_eq!(UserRole::default(), UserRole::Observer);
+    }
+
+    #[test]
+    fn only_instructors_write_fault_nodes() {
+        let roles = [UserRole::Observer, UserRole::Operator, UserRole::Instructor];
+        let can_write_controller_nodes = [false, true, true];
+        let can_write_fault_nodes = [false, false, true];
+
+        for (index, role) in roles.iter().enumerate() {
+            assert_eq!(role.can_write_node(false), can_write_controller_nodes[index]);
+            assert_eq!(role.can_write_node(true), can_write_fault_nodes[index]);
+        }
+    }
+
+    #[test]
+    fn anonymous_observers_are_least_privileged_by_default() {
+        let user_roles = user_roles_from_toml(r#"
+            users = [{ name = "instructor", password = "pw", role = "instructor" }]
+            "#);
+
+        assert_eq!(user_roles.get_least_privileged_role(), UserRole::Observer);
+        assert_eq!(user_roles.get_user_access_level(false).bits(),
+            UserAccessLevel::CURRENT_READ.bits());
+    }
+
+    #[test]
+    fn least_privileged_role_leaves_out_disallowed_anonymous_sessions() {
+        let user_roles = user_roles_from_toml(r#"
+            allow_anonymous = false
+            users = [{ name = "operator", password = "pw", role = "operator" },
+                { name = "instructor", password = "pw", role = "instructor" }]
+            "#);
+
+        assert_eq!(user_roles.get_least_privileged_role(), UserRole::Operator);
+        assert_eq!(user_roles.get_user_access_level(false).bits(),
+            (UserAccessLevel::CURRENT_READ | UserAccessLevel::CURRENT_WRITE).bits());
+        assert_eq!(user_roles.get_user_access_level(true).bits(),
+            UserAccessLevel::CURRENT_READ.bits());
+    }
+
+    #[test]
+    fn least_privileged_role_counts_anonymous_sessions() {
+        let user_roles = user_roles_from_toml(r#"
+            anonymous_role = "operator"
+            users = [{ name = "instructor", password = "pw", role = "instructor" }]
+            "#);
+
+        assert_eq!(user_roles.get_least_privileged_role(), UserRole::Operator);
+    }
+
+    #[test]
+    fn users_without_a_role_are_observers() {
+        let user_roles = user_roles_from_toml(r#"
+            anonymous_role = "instructor"
+            users = [{ name = "trainee", password = "pw" }]
+            "#);
+
+        assert_eq!(user_roles.get_least_privileged_role(), UserRole::Observer);
+    }
+}